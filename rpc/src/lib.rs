@@ -0,0 +1,131 @@
+//! RPC interface for the double auction pallet.
+//!
+//! Serves the `DoubleAuctionApi` runtime API to front-ends, so they can list open auctions
+//! without iterating storage maps through state queries.
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use pallet_double_auction::{
+    runtime_api::DoubleAuctionApi as DoubleAuctionRuntimeApi, AuctionDetail,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Error code of failed runtime API calls
+const RUNTIME_ERROR: i32 = 1;
+
+#[rpc(client, server)]
+pub trait DoubleAuctionApi<BlockHash, AccountId, AuctionId, BlockNumber, Price, Quantity> {
+    /// Live auctions of `tier`, oldest first.
+    #[method(name = "doubleAuction_openAuctions")]
+    fn open_auctions(
+        &self,
+        tier: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<AuctionDetail<AccountId, AuctionId, BlockNumber, Price, Quantity>>>;
+
+    /// Detail of a live auction.
+    #[method(name = "doubleAuction_auctionDetail")]
+    fn auction_detail(
+        &self,
+        auction_id: AuctionId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AuctionDetail<AccountId, AuctionId, BlockNumber, Price, Quantity>>>;
+
+    /// Bidder and amount of the leading bid of a live auction, none before any bid leads.
+    #[method(name = "doubleAuction_bestBid")]
+    fn best_bid(
+        &self,
+        auction_id: AuctionId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<(AccountId, Price)>>;
+}
+
+/// Double auction RPC handler, queries the best block unless told otherwise.
+pub struct DoubleAuction<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> DoubleAuction<C, Block> {
+    /// Create a new handler serving the runtime API of `client`.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+// Report a failed runtime API call to the caller
+fn runtime_error(error: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Unable to query the double auction runtime API.",
+        Some(format!("{:?}", error)),
+    ))
+    .into()
+}
+
+impl<C, Block, AccountId, AuctionId, BlockNumber, Price, Quantity>
+    DoubleAuctionApiServer<
+        <Block as BlockT>::Hash,
+        AccountId,
+        AuctionId,
+        BlockNumber,
+        Price,
+        Quantity,
+    > for DoubleAuction<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: DoubleAuctionRuntimeApi<Block, AccountId, AuctionId, BlockNumber, Price, Quantity>,
+    AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    AuctionId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    BlockNumber: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    Price: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    Quantity: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn open_auctions(
+        &self,
+        tier: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<AuctionDetail<AccountId, AuctionId, BlockNumber, Price, Quantity>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .open_auctions(at, tier)
+            .map_err(runtime_error)
+    }
+
+    fn auction_detail(
+        &self,
+        auction_id: AuctionId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AuctionDetail<AccountId, AuctionId, BlockNumber, Price, Quantity>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .auction_detail(at, auction_id)
+            .map_err(runtime_error)
+    }
+
+    fn best_bid(
+        &self,
+        auction_id: AuctionId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<(AccountId, Price)>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .best_bid(at, auction_id)
+            .map_err(runtime_error)
+    }
+}
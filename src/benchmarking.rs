@@ -1,35 +1,1029 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-double-auction
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
+use crate::Pallet as DoubleAuction;
+use crate::{escrow::Escrow, traits::BenchmarkHelper};
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::sp_runtime::{
+    traits::{Bounded, Saturating},
+    Perbill, Permill,
+};
+use frame_support::{
+    traits::{Currency, EnsureOrigin, Get, Hooks},
+    weights::Weight,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use scale_info::prelude::vec::Vec;
 
-#[benchmarks]
+// Starting price of benchmarked auctions, bids climb from it
+const STARTING_PRICE: u128 = 1_000;
+
+// Give `who` enough free balance to cover any deposit or bid
+fn funded<T: Config<I>, I: 'static>(name: &'static str, index: u32) -> T::AccountId {
+    let who: T::AccountId = account(name, index, 0);
+    T::Currency::make_free_balance_be(&who, BalanceOf::<T, I>::max_value() / 4u32.into());
+    who
+}
+
+// Origin passing `AdminOrigin`, governance calls are benchmarked from it
+fn admin<T: Config<I>, I: 'static>() -> T::RuntimeOrigin {
+    T::AdminOrigin::try_successful_origin().expect("admin origin is benchmarkable")
+}
+
+// List a first-tier auction from `seller` and return its id
+fn listed<T: Config<I>, I: 'static>(seller: &T::AccountId) -> T::AuctionId {
+    listed_with::<T, I>(seller, 5, None, None)
+}
+
+// List a first-tier auction of `period` minutes from `seller`, cleared by `match_rule` and
+// bought at once for `buyout_price`
+fn listed_with<T: Config<I>, I: 'static>(
+    seller: &T::AccountId,
+    period: u16,
+    match_rule: Option<MatchingAlgorithm>,
+    buyout_price: Option<u128>,
+) -> T::AuctionId {
+    let auction_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+    DoubleAuction::<T, I>::new(
+        RawOrigin::Signed(seller.clone()).into(),
+        1,
+        STARTING_PRICE,
+        period,
+        ListingOptions {
+            match_rule,
+            buyout_price,
+            ..Default::default()
+        },
+    )
+    .expect("auction is listed");
+    auction_id
+}
+
+// Place `b` increasing bids from distinct buyers on `auction_id`
+fn bid_on<T: Config<I>, I: 'static>(auction_id: T::AuctionId, b: u32) {
+    for index in 0..b {
+        let buyer = funded::<T, I>("buyer", index);
+        DoubleAuction::<T, I>::bid(
+            RawOrigin::Signed(buyer).into(),
+            auction_id,
+            STARTING_PRICE + 100 * (index as u128 + 1),
+            None,
+        )
+        .expect("bid is placed");
+    }
+}
+
+// List an auction from `seller` and let it end without a bid, returning its id
+fn expired<T: Config<I>, I: 'static>(seller: &T::AccountId) -> T::AuctionId {
+    let auction_id = listed::<T, I>(seller);
+    let end_at = Auctions::<T, I>::get(auction_id)
+        .expect("auction is live")
+        .end_at;
+    frame_system::Pallet::<T>::set_block_number(end_at);
+    DoubleAuction::<T, I>::on_idle(end_at, Weight::MAX);
+    auction_id
+}
+
+// Hold the price of a sale from `seller` to `buyer` until its delivery, returning its auction id
+fn delivered<T: Config<I>, I: 'static>(
+    seller: &T::AccountId,
+    buyer: &T::AccountId,
+) -> T::AuctionId {
+    let auction_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+    let now = frame_system::Pallet::<T>::block_number();
+    let price = T::Price::from(STARTING_PRICE);
+    Escrow::<T, I>::lock(auction_id, buyer, price).expect("payment is held");
+    Deliveries::<T, I>::insert(
+        auction_id,
+        buyer,
+        Delivery {
+            seller: seller.clone(),
+            quantity: T::Quantity::from(1u128),
+            price,
+            window: DeliveryWindow {
+                delivery_start: now,
+                delivery_end: now,
+            },
+            disputed: false,
+        },
+    );
+    auction_id
+}
+
+// Register `who` as a participant in `role`
+fn registered<T: Config<I>, I: 'static>(who: &T::AccountId, role: PartyType) {
+    match role {
+        // governance grants the sponsor role
+        PartyType::Sponsor => DoubleAuction::<T, I>::register_sponsor(admin::<T, I>(), who.clone()),
+        role => {
+            DoubleAuction::<T, I>::register_participant(RawOrigin::Signed(who.clone()).into(), role)
+        }
+    }
+    .expect("participant is registered");
+}
+
+#[instance_benchmarks]
 mod benchmarks {
     use super::*;
 
     #[benchmark]
-    fn do_something() {
-        let value = 100u32.into();
-        let caller: T::AccountId = whitelisted_caller();
-        // #[extrinsic_call]
-        // do_something(RawOrigin::Signed(caller), value);
+    fn new() {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(seller),
+            1,
+            STARTING_PRICE,
+            5,
+            Default::default(),
+        );
+
+        assert!(Auctions::<T, I>::contains_key(auction_id));
+    }
+
+    // `b` bids already placed on the auction
+    #[benchmark]
+    fn bid(b: Linear<0, { T::MaxBidsPerAuction::get() - 1 }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(auction_id, b);
+        let caller = funded::<T, I>("caller", 0);
+        let bid = STARTING_PRICE + 100 * (b as u128 + 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), auction_id, bid, None);
+
+        let auction = Auctions::<T, I>::get(auction_id).expect("auction is live");
+        assert_eq!(auction.highest_bid.bidder, caller);
+    }
+
+    // `b` bids refunded by the cancellation
+    #[benchmark]
+    fn cancel(b: Linear<0, { T::MaxBidsPerAuction::get() }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(auction_id, b);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(seller), auction_id);
+
+        assert!(!Auctions::<T, I>::contains_key(auction_id));
+    }
+
+    // `q` auctions with a bid each executed in the same block's idle time
+    #[benchmark]
+    fn execute_auctions(q: Linear<0, { T::MaxExecutionsPerBlock::get() }>) {
+        for index in 0..q {
+            let seller = funded::<T, I>("seller", index);
+            let auction_id = listed::<T, I>(&seller);
+            let buyer = funded::<T, I>("buyer", index);
+            DoubleAuction::<T, I>::bid(
+                RawOrigin::Signed(buyer).into(),
+                auction_id,
+                STARTING_PRICE + 100,
+                None,
+            )
+            .expect("bid is placed");
+        }
+        let now = frame_system::Pallet::<T>::block_number();
+        let end_at: BlockNumberFor<T> = Auctions::<T, I>::iter_values()
+            .map(|auction| auction.end_at)
+            .max()
+            .unwrap_or(now);
+
+        #[block]
+        {
+            DoubleAuction::<T, I>::on_idle(end_at, Weight::MAX);
+        }
+
+        assert_eq!(Auctions::<T, I>::iter().count(), 0);
+    }
+
+    // `a` open auctions of the tier, all swept by the buyer
+    #[benchmark]
+    fn market_buy(a: Linear<1, { T::MaxAuctionsPerTier::get().min(100) }>) {
+        for index in 0..a {
+            let seller = funded::<T, I>("seller", index);
+            listed::<T, I>(&seller);
+        }
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, u128::MAX, u128::MAX);
+
+        assert!(TierAuctions::<T, I>::get(1).is_empty());
+    }
+
+    // `o` standing buy orders of the tier, all filled by the seller
+    #[benchmark]
+    fn market_sell(o: Linear<1, { T::MaxStandingBuyOrders::get().min(100) }>) {
+        for index in 0..o {
+            let buyer = funded::<T, I>("buyer", index);
+            DoubleAuction::<T, I>::place_buy_order(
+                RawOrigin::Signed(buyer).into(),
+                1,
+                1,
+                STARTING_PRICE,
+                None,
+            )
+            .expect("buy order is placed");
+        }
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, o as u128, 0);
+
+        assert!(BuyOrders::<T, I>::get(1).is_empty());
+    }
+
+    #[benchmark]
+    fn extend_auction() {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        let end_at = Auctions::<T, I>::get(auction_id)
+            .expect("auction is live")
+            .end_at;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(seller), auction_id, 1u32.into());
+
+        let auction = Auctions::<T, I>::get(auction_id).expect("auction is live");
+        assert!(auction.end_at > end_at);
+    }
+
+    #[benchmark]
+    fn relist() {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = expired::<T, I>(&seller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(seller), auction_id, 5, STARTING_PRICE);
+
+        assert!(!UnsoldAuctions::<T, I>::contains_key(auction_id));
+    }
+
+    #[benchmark]
+    fn set_tier_default_period() {
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, 1, Some(10u32.into()));
+
+        assert!(TierDefaultPeriods::<T, I>::contains_key(1));
+    }
+
+    #[benchmark]
+    fn set_tier_min_bidders() {
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, 1, 2);
+
+        assert_eq!(TierMinBidders::<T, I>::get(1), 2);
+    }
+
+    #[benchmark]
+    fn register_referrer() {
+        let caller = funded::<T, I>("caller", 0);
+        let referrer = funded::<T, I>("referrer", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), referrer);
+
+        assert!(Referrers::<T, I>::contains_key(caller));
+    }
+
+    #[benchmark]
+    fn claim_referral_rewards() {
+        let caller = funded::<T, I>("caller", 0);
+        T::Currency::make_free_balance_be(
+            &DoubleAuction::<T, I>::account_id(),
+            BalanceOf::<T, I>::max_value() / 4u32.into(),
+        );
+        ReferralRewards::<T, I>::insert(&caller, BalanceOf::<T, I>::from(1_000u32));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(!ReferralRewards::<T, I>::contains_key(caller));
+    }
+
+    #[benchmark]
+    fn sponsor() {
+        let caller = funded::<T, I>("caller", 0);
+        registered::<T, I>(&caller, PartyType::Sponsor);
+        let account: T::AccountId = account("account", 0, 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            account.clone(),
+            BalanceOf::<T, I>::from(1_000u32),
+            10,
+        );
+
+        assert!(Sponsorships::<T, I>::contains_key(account));
+    }
+
+    #[benchmark]
+    fn end_sponsorship() {
+        let caller = funded::<T, I>("caller", 0);
+        registered::<T, I>(&caller, PartyType::Sponsor);
+        let account: T::AccountId = account("account", 0, 0);
+        DoubleAuction::<T, I>::sponsor(
+            RawOrigin::Signed(caller.clone()).into(),
+            account.clone(),
+            BalanceOf::<T, I>::from(1_000u32),
+            10,
+        )
+        .expect("account is sponsored");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), account.clone());
+
+        assert!(!Sponsorships::<T, I>::contains_key(account));
+    }
+
+    // `b` bids already placed on the auction, the relayed bid leads
+    #[benchmark]
+    fn bid_signed(b: Linear<0, { T::MaxBidsPerAuction::get() - 1 }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(auction_id, b);
+        let caller = funded::<T, I>("caller", 0);
+        let bidder = T::BenchmarkHelper::signer();
+        T::Currency::make_free_balance_be(&bidder, BalanceOf::<T, I>::max_value() / 4u32.into());
+        let payload = BidPayload {
+            bidder: bidder.clone(),
+            auction_id,
+            bid: STARTING_PRICE + 100 * (b as u128 + 1),
+            memo: None,
+            nonce: 0,
+            expires_at: frame_system::Pallet::<T>::block_number(),
+        };
+        let signature =
+            T::BenchmarkHelper::sign(&DoubleAuction::<T, I>::bid_signing_payload(&payload));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), payload, signature);
+
+        let auction = Auctions::<T, I>::get(auction_id).expect("auction is live");
+        assert_eq!(auction.highest_bid.bidder, bidder);
+    }
+
+    #[benchmark]
+    fn set_tier_matching_algorithm() {
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, 1, MatchingAlgorithm::PayAsBid);
+
+        assert!(PendingMatchingAlgorithms::<T, I>::contains_key(1));
+    }
+
+    // `b` bids on the auction, the lowest raised above all of them
+    #[benchmark]
+    fn top_up_bid(b: Linear<1, { T::MaxBidsPerAuction::get() }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(auction_id, b);
+        let caller: T::AccountId = account("buyer", 0, 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            auction_id,
+            100 * b as u128,
+        );
+
+        let auction = Auctions::<T, I>::get(auction_id).expect("auction is live");
+        assert_eq!(auction.highest_bid.bidder, caller);
+    }
+
+    #[benchmark]
+    fn transfer_auction() {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        let new_seller = funded::<T, I>("seller", 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(seller), auction_id, new_seller.clone());
+
+        let auction = Auctions::<T, I>::get(auction_id).expect("auction is live");
+        assert_eq!(auction.seller_id, new_seller);
+    }
+
+    #[benchmark]
+    fn set_tier_halted() {
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, 1, true);
+
+        assert!(HaltedTiers::<T, I>::get(1));
+    }
+
+    // `b` bids on the auction, the losers refunded on execution
+    #[benchmark]
+    fn force_execute(b: Linear<0, { T::MaxBidsPerAuction::get() }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(auction_id, b);
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, auction_id);
+
+        assert!(!Auctions::<T, I>::contains_key(auction_id));
+    }
+
+    // `b` bids on the matched auction, the losers refunded on settlement
+    #[benchmark]
+    fn accept_match(b: Linear<1, { T::MaxBidsPerAuction::get() }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(auction_id, b);
+        let mut auction = Auctions::<T, I>::take(auction_id).expect("auction is live");
+        AuctionsExecutionQueue::<T, I>::remove(auction.end_at, auction_id);
+        auction.auction_status = AuctionStatus::Matched;
+        AcceptanceDeadlines::<T, I>::insert(auction.end_at, auction_id, ());
+        let caller = auction.highest_bid.bidder.clone();
+        TierQueueDepths::<T, I>::insert(auction.auction_category.level, (1, 0));
+        AwaitingAcceptance::<T, I>::insert(auction_id, auction);
 
-        // assert_eq!(Something::<T>::get(), Some(value));
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), auction_id);
+
+        assert!(!AwaitingAcceptance::<T, I>::contains_key(auction_id));
     }
 
     #[benchmark]
-    fn cause_error() {
-        Something::<T>::put(100u32);
-        let caller: T::AccountId = whitelisted_caller();
-        // #[extrinsic_call]
-        // cause_error(RawOrigin::Signed(caller));
+    fn create_organization() {
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            STARTING_PRICE,
+            STARTING_PRICE * 10,
+        );
+
+        assert!(OrganizationOf::<T, I>::contains_key(caller));
+    }
+
+    #[benchmark]
+    fn set_organization_member() {
+        let caller = funded::<T, I>("caller", 0);
+        let organization = OrganizationIndex::<T, I>::get();
+        DoubleAuction::<T, I>::create_organization(
+            RawOrigin::Signed(caller.clone()).into(),
+            STARTING_PRICE,
+            STARTING_PRICE * 10,
+        )
+        .expect("organization is created");
+        let member: T::AccountId = account("member", 0, 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            organization,
+            member.clone(),
+            true,
+        );
+
+        assert_eq!(OrganizationOf::<T, I>::get(member), Some(organization));
+    }
+
+    #[benchmark]
+    fn set_organization_limits() {
+        let caller = funded::<T, I>("caller", 0);
+        let organization = OrganizationIndex::<T, I>::get();
+        DoubleAuction::<T, I>::create_organization(
+            RawOrigin::Signed(caller.clone()).into(),
+            STARTING_PRICE,
+            STARTING_PRICE * 10,
+        )
+        .expect("organization is created");
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            organization,
+            STARTING_PRICE * 2,
+            STARTING_PRICE * 20,
+        );
+
+        let org = Organizations::<T, I>::get(organization).expect("organization exists");
+        assert_eq!(org.budget, T::Price::from(STARTING_PRICE * 20));
+    }
+
+    // `s` accounts already subscribed to the tier
+    #[benchmark]
+    fn subscribe_tier(s: Linear<0, { T::MaxTierSubscribers::get() - 1 }>) {
+        for index in 0..s {
+            let subscriber: T::AccountId = account("subscriber", index, 0);
+            DoubleAuction::<T, I>::subscribe_tier(RawOrigin::Signed(subscriber).into(), 1)
+                .expect("tier is subscribed");
+        }
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), 1);
+
+        assert!(TierSubscribers::<T, I>::get(1).contains(&caller));
+    }
+
+    // `s` accounts subscribed to the tier, the caller last
+    #[benchmark]
+    fn unsubscribe_tier(s: Linear<1, { T::MaxTierSubscribers::get() }>) {
+        for index in 0..s {
+            let subscriber: T::AccountId = account("subscriber", index, 0);
+            DoubleAuction::<T, I>::subscribe_tier(RawOrigin::Signed(subscriber).into(), 1)
+                .expect("tier is subscribed");
+        }
+        let caller: T::AccountId = account("subscriber", s - 1, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), 1);
+
+        assert!(!TierSubscribers::<T, I>::get(1).contains(&caller));
+    }
+
+    #[benchmark]
+    fn set_parameter() {
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            MarketParameter::CommissionRate(Some(Perbill::from_percent(1))),
+        );
+
+        assert!(Parameters::<T, I>::get().commission_rate.is_some());
+    }
+
+    // `b` bids already placed on the buy leg, the caller's bid leads
+    #[benchmark]
+    fn pair_orders(b: Linear<0, { T::MaxBidsPerAuction::get() - 1 }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let buy_auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(buy_auction_id, b);
+        let caller = funded::<T, I>("caller", 0);
+        let sell_auction_id = listed::<T, I>(&caller);
+        DoubleAuction::<T, I>::extend_auction(
+            RawOrigin::Signed(caller.clone()).into(),
+            sell_auction_id,
+            1u32.into(),
+        )
+        .expect("sell leg ends after the buy leg");
+        let bid = STARTING_PRICE + 100 * (b as u128 + 1);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            buy_auction_id,
+            bid,
+            sell_auction_id,
+        );
+
+        assert!(OrderPairs::<T, I>::contains_key(buy_auction_id));
+    }
+
+    // `o` standing buy orders already placed in the tier
+    #[benchmark]
+    fn place_buy_order(o: Linear<0, { T::MaxStandingBuyOrders::get() - 1 }>) {
+        for index in 0..o {
+            let buyer = funded::<T, I>("buyer", index);
+            DoubleAuction::<T, I>::place_buy_order(
+                RawOrigin::Signed(buyer).into(),
+                1,
+                1,
+                STARTING_PRICE,
+                None,
+            )
+            .expect("buy order is placed");
+        }
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, 1, STARTING_PRICE, None);
+
+        assert_eq!(BuyOrders::<T, I>::get(1).len() as u32, o + 1);
+    }
+
+    // `o` standing buy orders in the tier, the caller's last
+    #[benchmark]
+    fn cancel_buy_order(o: Linear<1, { T::MaxStandingBuyOrders::get() }>) {
+        for index in 0..o {
+            let buyer = funded::<T, I>("buyer", index);
+            DoubleAuction::<T, I>::place_buy_order(
+                RawOrigin::Signed(buyer).into(),
+                1,
+                1,
+                STARTING_PRICE,
+                None,
+            )
+            .expect("buy order is placed");
+        }
+        let caller: T::AccountId = account("buyer", o - 1, 0);
+        let order_id = BuyOrderIndex::<T, I>::get() - 1;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, order_id);
+
+        assert_eq!(BuyOrders::<T, I>::get(1).len() as u32, o - 1);
+    }
+
+    // `b` bids already placed on the partial-fill auction, the caller's leads
+    #[benchmark]
+    fn bid_quantity(b: Linear<0, { T::MaxBidsPerAuction::get() - 1 }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id =
+            listed_with::<T, I>(&seller, 5, Some(MatchingAlgorithm::PartialFill), None);
+        for index in 0..b {
+            let buyer = funded::<T, I>("buyer", index);
+            DoubleAuction::<T, I>::bid_quantity(
+                RawOrigin::Signed(buyer).into(),
+                auction_id,
+                1,
+                STARTING_PRICE + 100 * (index as u128 + 1),
+            )
+            .expect("bid is placed");
+        }
+        let caller = funded::<T, I>("caller", 0);
+        let bid = STARTING_PRICE + 100 * (b as u128 + 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), auction_id, 1, bid);
+
+        assert!(Bids::<T, I>::contains_key(auction_id, caller));
+    }
+
+    // `b` bids on the auction, the leader withdraws and the next best is promoted
+    #[benchmark]
+    fn cancel_bid(b: Linear<1, { T::MaxBidsPerAuction::get() }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+        bid_on::<T, I>(auction_id, b);
+        let caller: T::AccountId = account("buyer", b - 1, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), auction_id);
+
+        assert!(!Bids::<T, I>::contains_key(auction_id, caller));
+    }
+
+    // `b` bids already committed to the sealed-bid auction
+    #[benchmark]
+    fn commit_bid(b: Linear<0, { T::MaxBidsPerAuction::get() - 1 }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed_with::<T, I>(&seller, 60, Some(MatchingAlgorithm::SealedBid), None);
+        for index in 0..b {
+            let buyer = funded::<T, I>("buyer", index);
+            let commitment = DoubleAuction::<T, I>::sealed_commitment(
+                &buyer,
+                auction_id,
+                STARTING_PRICE,
+                &[0; 32],
+            );
+            DoubleAuction::<T, I>::commit_bid(
+                RawOrigin::Signed(buyer).into(),
+                auction_id,
+                commitment,
+            )
+            .expect("bid is committed");
+        }
+        let caller = funded::<T, I>("caller", 0);
+        let commitment =
+            DoubleAuction::<T, I>::sealed_commitment(&caller, auction_id, STARTING_PRICE, &[0; 32]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), auction_id, commitment);
+
+        assert!(SealedBids::<T, I>::contains_key(auction_id, caller));
+    }
+
+    // `b` bids already revealed on the sealed-bid auction, the caller's leads
+    #[benchmark]
+    fn reveal_bid(b: Linear<0, { T::MaxBidsPerAuction::get() - 1 }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed_with::<T, I>(&seller, 60, Some(MatchingAlgorithm::SealedBid), None);
+        let caller = funded::<T, I>("caller", 0);
+        let mut bidders: Vec<(T::AccountId, u128)> = (0..b)
+            .map(|index| {
+                (
+                    funded::<T, I>("buyer", index),
+                    STARTING_PRICE + 100 * (index as u128 + 1),
+                )
+            })
+            .collect();
+        let bid = STARTING_PRICE + 100 * (b as u128 + 1);
+        bidders.push((caller.clone(), bid));
+        for (bidder, bid) in bidders.iter() {
+            let commitment =
+                DoubleAuction::<T, I>::sealed_commitment(bidder, auction_id, *bid, &[0; 32]);
+            DoubleAuction::<T, I>::commit_bid(
+                RawOrigin::Signed(bidder.clone()).into(),
+                auction_id,
+                commitment,
+            )
+            .expect("bid is committed");
+        }
+        let end_at = Auctions::<T, I>::get(auction_id)
+            .expect("auction is live")
+            .end_at;
+        frame_system::Pallet::<T>::set_block_number(end_at.saturating_sub(T::RevealWindow::get()));
+        bidders.pop();
+        for (bidder, bid) in bidders {
+            DoubleAuction::<T, I>::reveal_bid(
+                RawOrigin::Signed(bidder).into(),
+                auction_id,
+                bid,
+                [0; 32],
+            )
+            .expect("bid is revealed");
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), auction_id, bid, [0; 32]);
+
+        let auction = Auctions::<T, I>::get(auction_id).expect("auction is live");
+        assert_eq!(auction.highest_bid.bidder, caller);
+    }
+
+    #[benchmark]
+    fn new_dutch() {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(seller),
+            1,
+            STARTING_PRICE,
+            STARTING_PRICE / 2,
+            10,
+            1u32.into(),
+            5,
+        );
+
+        assert!(DutchAuctions::<T, I>::contains_key(auction_id));
+    }
+
+    // `b` bids on the auction, all refunded by the buyout
+    #[benchmark]
+    fn buyout(b: Linear<0, { T::MaxBidsPerAuction::get() }>) {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed_with::<T, I>(&seller, 5, None, Some(STARTING_PRICE * 100));
+        bid_on::<T, I>(auction_id, b);
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), auction_id);
+
+        assert!(!Auctions::<T, I>::contains_key(auction_id));
+    }
+
+    // `t` boundaries, splitting the quantities in `t + 1` tiers
+    #[benchmark]
+    fn set_tier_boundaries(t: Linear<0, { T::MaxTiers::get() - 1 }>) {
+        let origin = admin::<T, I>();
+        let boundaries: Vec<u128> = (1..=t).map(|tier| 1_000 * tier as u128).collect();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, boundaries);
+
+        assert_eq!(
+            TierConfig::<T, I>::get().map_or(0, |tiers| tiers.len()) as u32,
+            t
+        );
+    }
+
+    #[benchmark]
+    fn cancel_recurring() {
+        let seller = funded::<T, I>("seller", 0);
+        let recurring_id = RecurringIndex::<T, I>::get();
+        DoubleAuction::<T, I>::create_recurring_auction(
+            RawOrigin::Signed(seller.clone()).into(),
+            1,
+            STARTING_PRICE,
+            5,
+            T::MaxAuctionPeriod::get(),
+        )
+        .expect("recurring auction is created");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(seller), recurring_id);
+
+        assert!(!RecurringAuctions::<T, I>::contains_key(recurring_id));
+    }
+
+    #[benchmark]
+    fn register_participant() {
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), PartyType::Prosumer);
+
+        assert!(Participants::<T, I>::contains_key(caller));
+    }
+
+    #[benchmark]
+    fn set_participant_status() {
+        let who = funded::<T, I>("participant", 0);
+        registered::<T, I>(&who, PartyType::Prosumer);
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who.clone(), true, false);
+
+        let participant = Participants::<T, I>::get(who).expect("participant is registered");
+        assert!(participant.kyc_verified);
+    }
+
+    #[benchmark]
+    fn register_sponsor() {
+        let who = funded::<T, I>("sponsor", 0);
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who.clone());
+
+        let participant = Participants::<T, I>::get(who).expect("participant is registered");
+        assert_eq!(participant.role, PartyType::Sponsor);
+    }
+
+    #[benchmark]
+    fn new_demand() {
+        let caller = funded::<T, I>("caller", 0);
+        let demand_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, STARTING_PRICE, 5);
+
+        assert!(DemandAuctions::<T, I>::contains_key(demand_id));
+    }
+
+    #[benchmark]
+    fn offer() {
+        let buyer = funded::<T, I>("buyer", 0);
+        let demand_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+        DoubleAuction::<T, I>::new_demand(RawOrigin::Signed(buyer).into(), 1, STARTING_PRICE, 5)
+            .expect("demand is posted");
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), demand_id, STARTING_PRICE / 2);
+
+        let demand = DemandAuctions::<T, I>::get(demand_id).expect("demand is open");
+        assert_eq!(demand.offer_count, 1);
+    }
+
+    #[benchmark]
+    fn cancel_demand() {
+        let caller = funded::<T, I>("caller", 0);
+        let demand_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+        DoubleAuction::<T, I>::new_demand(
+            RawOrigin::Signed(caller.clone()).into(),
+            1,
+            STARTING_PRICE,
+            5,
+        )
+        .expect("demand is posted");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), demand_id);
+
+        assert!(!DemandAuctions::<T, I>::contains_key(demand_id));
+    }
+
+    #[benchmark]
+    fn update_auction() {
+        let seller = funded::<T, I>("seller", 0);
+        let auction_id = listed::<T, I>(&seller);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(seller),
+            auction_id,
+            1,
+            STARTING_PRICE * 2,
+            10,
+        );
+
+        let auction = Auctions::<T, I>::get(auction_id).expect("auction is live");
+        assert_eq!(auction.starting_bid.bid, T::Price::from(STARTING_PRICE * 2));
+    }
+
+    #[benchmark]
+    fn set_zone_link() {
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            1,
+            2,
+            Some(Permill::from_percent(1)),
+        );
+
+        assert!(ZoneTopology::<T, I>::contains_key(2, 1));
+    }
+
+    #[benchmark]
+    fn confirm_delivery() {
+        let seller = funded::<T, I>("seller", 0);
+        let caller = funded::<T, I>("caller", 0);
+        let auction_id = delivered::<T, I>(&seller, &caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), auction_id);
+
+        assert!(!Deliveries::<T, I>::contains_key(auction_id, caller));
+    }
+
+    #[benchmark]
+    fn dispute_delivery() {
+        let seller = funded::<T, I>("seller", 0);
+        let caller = funded::<T, I>("caller", 0);
+        let auction_id = delivered::<T, I>(&seller, &caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), auction_id);
+
+        let delivery = Deliveries::<T, I>::get(auction_id, caller).expect("delivery is pending");
+        assert!(delivery.disputed);
+    }
+
+    // the dispute is upheld, slashing the seller's bond
+    #[benchmark]
+    fn resolve_delivery() {
+        let seller = funded::<T, I>("seller", 0);
+        DoubleAuction::<T, I>::bond_capacity(
+            RawOrigin::Signed(seller.clone()).into(),
+            BalanceOf::<T, I>::from(1_000u32),
+        )
+        .expect("capacity is bonded");
+        let buyer = funded::<T, I>("buyer", 0);
+        let auction_id = delivered::<T, I>(&seller, &buyer);
+        DoubleAuction::<T, I>::dispute_delivery(
+            RawOrigin::Signed(buyer.clone()).into(),
+            auction_id,
+        )
+        .expect("delivery is disputed");
+        let origin = T::ArbitrationOrigin::try_successful_origin()
+            .expect("arbitration origin is benchmarkable");
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, auction_id, buyer.clone(), true);
+
+        assert!(!Deliveries::<T, I>::contains_key(auction_id, buyer));
+    }
+
+    #[benchmark]
+    fn bond_capacity() {
+        let caller = funded::<T, I>("caller", 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            BalanceOf::<T, I>::from(1_000u32),
+        );
+
+        assert_eq!(
+            SellerBonds::<T, I>::get(caller),
+            BalanceOf::<T, I>::from(1_000u32)
+        );
+    }
+
+    #[benchmark]
+    fn pause_market() {
+        let origin = admin::<T, I>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(MarketPaused::<T, I>::get());
+    }
+
+    #[benchmark]
+    fn resume_market() {
+        let origin = admin::<T, I>();
+        MarketPaused::<T, I>::put(true);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
 
-        // assert_eq!(Something::<T>::get(), Some(101u32));
+        assert!(!MarketPaused::<T, I>::get());
     }
 
-    impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+    impl_benchmark_test_suite!(
+        DoubleAuction,
+        crate::mock::new_test_ext(),
+        crate::mock::Test
+    );
 }
@@ -0,0 +1,115 @@
+//! Uniform-price clearing of `MatchingAlgorithm::UniformPrice` auctions.
+//!
+//! Every `Config::ClearingInterval` blocks the open uniform-price auctions of a tier are crossed
+//! with the tier's standing buy orders at a single price per unit. Auctions are sold whole: the
+//! cheapest asks go to the highest orders able to take them until asks exceed the limits of the
+//! orders already matched. The clearing price is the midpoint between the marginal ask and the
+//! lowest matched limit, so no matched seller gets less than its ask and no matched buyer pays
+//! more than its limit.
+//!
+//! A clearing only takes place when the demand able to buy at the cheapest ask reaches
+//! `Config::ClearingQuorum` of the quantity offered, otherwise the asks wait for the next one.
+use frame_support::sp_runtime::Perbill;
+use scale_info::prelude::vec::Vec;
+
+/// Auction offered to a clearing, asking `price` for its whole `quantity`.
+#[derive(Clone, PartialEq, Eq, frame_support::RuntimeDebug)]
+pub struct Ask<Id> {
+    pub id: Id,
+    pub quantity: u128,
+    pub price: u128,
+}
+
+/// Demand taking part in a clearing, for up to `quantity` at `unit_price` at most.
+#[derive(Clone, PartialEq, Eq, frame_support::RuntimeDebug)]
+pub struct Order<Id> {
+    pub id: Id,
+    pub quantity: u128,
+    pub unit_price: u128,
+}
+
+/// Outcome of a clearing.
+#[derive(Clone, PartialEq, Eq, frame_support::RuntimeDebug)]
+pub struct Clearing<AskId, OrderId> {
+    /// Price per unit every match settles at.
+    pub unit_price: u128,
+    /// Auctions sold, cheapest first, with the order buying each.
+    pub matches: Vec<(AskId, OrderId)>,
+}
+
+/// Cross `asks` with `orders` at one price per unit, `None` when nothing crosses.
+///
+/// Orders are served highest limit first, and in the given order among equal limits. At most
+/// `max_matches` asks are sold, the dearer ones wait for the next clearing.
+pub fn clear<AskId, OrderId: Clone>(
+    mut asks: Vec<Ask<AskId>>,
+    mut orders: Vec<Order<OrderId>>,
+    max_matches: usize,
+) -> Option<Clearing<AskId, OrderId>> {
+    // cheapest per unit first, compared without dividing
+    asks.sort_by(|a, b| {
+        a.price
+            .saturating_mul(b.quantity)
+            .cmp(&b.price.saturating_mul(a.quantity))
+    });
+    orders.sort_by(|a, b| b.unit_price.cmp(&a.unit_price));
+
+    let mut matches = Vec::new();
+    let mut marginal_ask = 0u128;
+    let mut lowest_limit = u128::MAX;
+    for ask in asks {
+        if matches.len() >= max_matches {
+            break;
+        }
+
+        // asks only get dearer, none is met once one exceeds a matched limit
+        let ask_unit_price = unit_price(&ask);
+        if ask_unit_price > lowest_limit {
+            break;
+        }
+
+        let taker = orders
+            .iter_mut()
+            .find(|order| order.quantity >= ask.quantity && order.unit_price >= ask_unit_price);
+        if let Some(order) = taker {
+            order.quantity -= ask.quantity;
+            marginal_ask = ask_unit_price;
+            lowest_limit = lowest_limit.min(order.unit_price);
+            matches.push((ask.id, order.id.clone()));
+        }
+    }
+
+    if matches.is_empty() {
+        return None;
+    }
+    Some(Clearing {
+        unit_price: marginal_ask + (lowest_limit - marginal_ask) / 2,
+        matches,
+    })
+}
+
+/// Whether the quantity of `orders` able to buy at the cheapest of `asks` reaches `quorum` of
+/// the quantity `asks` offer.
+pub fn meets_quorum<AskId, OrderId>(
+    asks: &[Ask<AskId>],
+    orders: &[Order<OrderId>],
+    quorum: Perbill,
+) -> bool {
+    let cheapest = match asks.iter().map(unit_price).min() {
+        Some(cheapest) => cheapest,
+        None => return true,
+    };
+    let supply = asks
+        .iter()
+        .fold(0u128, |supply, ask| supply.saturating_add(ask.quantity));
+    let demand = orders
+        .iter()
+        .filter(|order| order.unit_price >= cheapest)
+        .fold(0u128, |demand, order| demand.saturating_add(order.quantity));
+    demand >= quorum.mul_ceil(supply)
+}
+
+// Price per unit of an ask, rounded up so a seller never gets less than it asks
+fn unit_price<Id>(ask: &Ask<Id>) -> u128 {
+    ask.price.saturating_add(ask.quantity.saturating_sub(1)) / ask.quantity.max(1)
+}
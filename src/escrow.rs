@@ -0,0 +1,119 @@
+//! Escrow of bids under `Config::OnChainSettlement`.
+//!
+//! Funds backing a bid are reserved from the bidder and tracked per auction in `Escrows`. They
+//! are released when the bid is outbid, its auction is cancelled or expires without a match, and
+//! paid to the seller when the bid wins.
+//!
+//! Auctions paid in an asset hold it in the pallet account instead, `Assets` having no reserves.
+use crate::pallet::{AuctionAssets, BalanceOf, Config, Escrows, Pallet};
+use core::marker::PhantomData;
+use frame_support::{
+    dispatch::DispatchResult,
+    sp_runtime::traits::{UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+    traits::{fungibles::Transfer, Currency, ExistenceRequirement, Get, ReservableCurrency},
+};
+
+/// Bid escrow of a pallet instance.
+pub struct Escrow<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> Escrow<T, I> {
+    /// Whether bids are held by this instance, paper markets never hold funds.
+    pub fn enabled() -> bool {
+        T::OnChainSettlement::get() && !T::Simulation::get()
+    }
+
+    /// Hold `bid` for `bidder` on `auction_id` in place of what was held before.
+    pub fn lock(auction_id: T::AuctionId, bidder: &T::AccountId, bid: T::Price) -> DispatchResult {
+        if !Self::enabled() {
+            return Ok(());
+        }
+
+        let amount = Self::balance_of(bid);
+        let held = Escrows::<T, I>::get(auction_id, bidder);
+        match AuctionAssets::<T, I>::get(auction_id) {
+            Some(asset) if amount > held => {
+                T::Assets::transfer(
+                    asset,
+                    bidder,
+                    &Pallet::<T, I>::account_id(),
+                    amount - held,
+                    false,
+                )?;
+            }
+            Some(asset) if held > amount => {
+                T::Assets::transfer(
+                    asset,
+                    &Pallet::<T, I>::account_id(),
+                    bidder,
+                    held - amount,
+                    false,
+                )?;
+            }
+            Some(_) => {}
+            None if amount > held => T::Currency::reserve(bidder, amount - held)?,
+            None => {
+                T::Currency::unreserve(bidder, held - amount);
+            }
+        }
+        Escrows::<T, I>::insert(auction_id, bidder, amount);
+        Ok(())
+    }
+
+    /// Release everything held for `bidder` on `auction_id`.
+    pub fn release(auction_id: T::AuctionId, bidder: &T::AccountId) {
+        let held = Escrows::<T, I>::take(auction_id, bidder);
+        if held.is_zero() {
+            return;
+        }
+        match AuctionAssets::<T, I>::get(auction_id) {
+            // the pallet account holds every asset it escrowed
+            Some(asset) => {
+                let _ =
+                    T::Assets::transfer(asset, &Pallet::<T, I>::account_id(), bidder, held, false);
+            }
+            None => {
+                T::Currency::unreserve(bidder, held);
+            }
+        }
+    }
+
+    /// Amount held for `bidder` on `auction_id`, in price units.
+    pub fn held(auction_id: T::AuctionId, bidder: &T::AccountId) -> u128 {
+        Escrows::<T, I>::get(auction_id, bidder).unique_saturated_into()
+    }
+
+    /// Pay `price` from `buyer` to `seller`, releasing what `buyer` held on `auction_id` first.
+    ///
+    /// Fails when `buyer` cannot pay `price`, leaving what it held in place.
+    pub fn pay(
+        auction_id: T::AuctionId,
+        buyer: &T::AccountId,
+        seller: &T::AccountId,
+        price: T::Price,
+    ) -> DispatchResult {
+        frame_support::storage::with_storage_layer(|| {
+            Self::release(auction_id, buyer);
+            if !Self::enabled() {
+                return Ok(());
+            }
+
+            // funds may have moved since the bid was checked, a short payment reverts the release
+            match AuctionAssets::<T, I>::get(auction_id) {
+                Some(asset) => {
+                    T::Assets::transfer(asset, buyer, seller, Self::balance_of(price), false)
+                        .map(|_| ())
+                }
+                None => T::Currency::transfer(
+                    buyer,
+                    seller,
+                    Self::balance_of(price),
+                    ExistenceRequirement::AllowDeath,
+                ),
+            }
+        })
+    }
+
+    fn balance_of(price: T::Price) -> BalanceOf<T, I> {
+        let price: u128 = price.unique_saturated_into();
+        BalanceOf::<T, I>::unique_saturated_from(price)
+    }
+}
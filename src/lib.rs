@@ -16,7 +16,12 @@
 //!
 //! Auctions are executed in the auction execution queue based on their ending time
 //!
-//! NOTE: this mocdule does not implement how payment is handled.
+//! NOTE: this mocdule does not implement how payment is handled, unless `OnChainSettlement`
+//! is set: bids are then held in `Currency` and winning bids paid to sellers.
+//! Bids on listings with a `payment_asset` are held and paid in that asset of `Assets` instead.
+//!
+//! Blocks settling auctions commit to their `SettlementResult`s in a digest item
+//! prefixed by `SETTLEMENT_DIGEST_ID`.
 //!
 //! `Data`:     
 //!     --  AuctionData<AccountId, BlockNumber, Bid, Tier> {
@@ -24,33 +29,156 @@
 //!             pub seller_id: AccountId,
 //!             pub quantity: Quantity,
 //!             pub starting_bid: Bid,
-//!             pub bids: Vec<Bid>,
+//!             pub bid_count: u32,
 //!             pub auction_period: BlockNumber,
 //!             pub auction_status: AuctionStatus,
 //!             pub start_at: BlockNumber,
 //!             pub end_at: BlockNumber,
 //!             pub highest_bid: Bid,
 //!             pub auction_category: Tier,
-//!         }
-//!     -- AuctionInfoo<AccountId, PartyType> {
-//!             pub participant_id: Option<AccountId>,
-//!             pub party_type: PartyType,
-//!             pub auctions: Vec<AuctionId>, // Maximum of 5 auction id
+//!             pub metadata: Option<Metadata>,
+//!             pub min_bidders: u32,
+//!             pub match_rule: Option<MatchingAlgorithm>,
+//!             pub settlement_lag: BlockNumber,
+//!             pub reserve_price: Option<ReservePrice<Price>>,
+//!             pub zone: ZoneId,
+//!             pub delivery_window: Option<DeliveryWindow<BlockNumber>>,
 //!         }
 //!     -- AuctionsExecutionQueue: { (execution_block, auction_id) -> () }
+//!     -- PendingAuctions: { (start_block, auction_id) -> () }
+//!     -- RecurringAuctions { recurring_id -> RecurringAuction }
+//!     -- RecurringOf { seller_id -> BoundedVec<recurring_id, MaxRecurringAuctions> }
+//!     -- RecurringSchedule: { (listing_block, recurring_id) -> () }
+//!     -- DemandAuctions { demand_id -> DemandAuction }
+//!     -- DemandExecutionQueue: { (execution_block, demand_id) -> () }
+//!     -- AwaitingAcceptance { auction_id -> AuctionData }
+//!     -- AwaitingSettlement { auction_id -> AuctionData }
+//!     -- TierQueueDepths { tier -> (awaiting_acceptance, awaiting_settlement) }
+//!     -- SettlementQueue: { (settlement_block, auction_id) -> () }
+//!     -- OrderPairs { buy_auction_id -> OrderPair }
+//!     -- PairedSellLegs { sell_auction_id -> buy_auction_id }
+//!     -- SkippedWinners { account_id -> u32 }
+//!     -- ListingCooldowns { (seller_id, quantity) -> block_number }
+//!     -- BuyOrders { tier -> BoundedVec<BuyOrder, MaxStandingBuyOrders> }
 //!     -- Tier: u128,  // 0, 1, 2, ...
+//!     -- TierConfig: BoundedVec<quantity, MaxTiers>  // lowest quantity of every tier above the first
 //!     -- Auctions { auction_id -> AuctionData }
-//!     -- AuctionsOf { account_id -> AuctionInfo }
+//!     -- Bids { (auction_id, bidder_id) -> BidRecord }
+//!     -- Escrows { (auction_id, bidder_id) -> Balance }
+//!     -- SealedBids { (auction_id, bidder_id) -> commitment }
+//!     -- ProxyBids { (auction_id, bidder_id) -> max_bid }
+//!     -- DutchAuctions { auction_id -> PriceDecay }
+//!     -- BuyoutPrices { auction_id -> price }
+//!     -- AuctionFills { auction_id -> BoundedVec<Fill, MaxFillsPerAuction> }
+//!     -- AuctionSummaries { auction_id -> AuctionSummary }
+//!     -- Notifications { account_id -> BoundedVec<Notification, MaxNotifications> }
+//!     -- TierSubscribers { tier -> BoundedVec<account_id, MaxTierSubscribers> }
+//!     -- Parameters: MarketParameters
+//!     -- MarketActivity: BoundedVec<Activity, MaxMarketActivity>
+//!     -- RecentClearings { tier -> BoundedVec<(Quantity, Price), MaxRecentClearings> }
+//!     -- TradeHistory { tier -> BoundedVec<(block_number, Quantity, Price), MaxTradeHistory> }
+//!     -- SellOrdersOf { account_id -> BoundedVec<auction_id, MaxSellOrders> }
+//!     -- BuyOrdersOf { account_id -> BoundedVec<auction_id, MaxBuyOrders> }
+//!     -- Referrers { account_id -> referrer_id }
+//!     -- ReferralRewards { referrer_id -> Balance }
+//!     -- Sponsorships { account_id -> Sponsorship }
+//!     -- Organizations { organization_id -> Organization }
+//!     -- OrganizationOf { account_id -> organization_id }
+//!     -- Participants { account_id -> Participant }
+//!     -- DepositPayers { auction_id -> sponsor_id }
+//!     -- AuctionDeposits { auction_id -> Balance }
+//!     -- SellerBonds { seller_id -> Balance }
+//!     -- MarketPaused: bool
+//!     -- UnsoldStreaks { seller_id -> u32 }
+//!     -- OpenAuctionCounts { seller_id -> u32 }
+//!     -- OpenBidCounts { buyer_id -> u32 }
+//!     -- BidNonces { account_id -> u64 }
+//!     -- EraVolumes { (era, account_id) -> EraVolume }
+//!     -- Leaderboards { era -> Leaderboard<BoundedVec<(account_id, Quantity), MaxLeaderboardSize>> }
+//!     -- BidHistograms { (tier, era) -> BoundedVec<u32, MaxHistogramBuckets> }
+//!     -- TierMatchingAlgorithms { tier -> MatchingAlgorithm }
+//!     -- ZoneTopology { (zone, neighbour) -> loss }
+//!     -- Deliveries { (auction_id, buyer_id) -> Delivery }
+//!     -- Reputation { account_id -> ReputationStats }
 //!
 //! `Interface`:
 //!     -- new(...)
 //!     -- bid(...)
 //!     -- cancel(...)
+//!     -- extend_auction(...)
+//!     -- relist(...)
+//!     -- set_tier_default_period(...)
+//!     -- set_tier_min_bidders(...)
+//!     -- register_referrer(...)
+//!     -- claim_referral_rewards(...)
+//!     -- sponsor(...)
+//!     -- end_sponsorship(...)
+//!     -- bid_signed(...)
+//!     -- set_tier_matching_algorithm(...)
+//!     -- top_up_bid(...)
+//!     -- transfer_auction(...)
+//!     -- set_tier_halted(...)
+//!     -- market_buy(...)
+//!     -- force_execute(...)
+//!     -- accept_match(...)
+//!     -- create_organization(...)
+//!     -- set_organization_member(...)
+//!     -- set_organization_limits(...)
+//!     -- subscribe_tier(...)
+//!     -- unsubscribe_tier(...)
+//!     -- set_parameter(...)
+//!     -- pair_orders(...)
+//!     -- place_buy_order(...)
+//!     -- cancel_buy_order(...)
+//!     -- bid_quantity(...)
+//!     -- cancel_bid(...)
+//!     -- commit_bid(...)
+//!     -- reveal_bid(...)
+//!     -- new_dutch(...)
+//!     -- buyout(...)
+//!     -- set_tier_boundaries(...)
+//!     -- force_cancel(...)
+//!     -- create_recurring_auction(...)
+//!     -- cancel_recurring(...)
+//!     -- bid_proxy(...)
+//!     -- register_participant(...)
+//!     -- set_participant_status(...)
+//!     -- new_demand(...)
+//!     -- offer(...)
+//!     -- cancel_demand(...)
+//!     -- update_auction(...)
+//!     -- bid_batch(...)
+//!     -- set_zone_link(...)
+//!     -- confirm_delivery(...)
+//!     -- dispute_delivery(...)
+//!     -- resolve_delivery(...)
+//!     -- bond_capacity(...)
+//!     -- pause_market(...)
+//!     -- resume_market(...)
+//!     -- force_remove(...)
+//!     -- market_sell(...)
+//!     -- register_sponsor(...)
 //!
 //! `Hooks`:
 //!     -- on_auction_ended
+//!     -- start_pending_auctions, at the start block of scheduled auctions
+//!     -- list_recurring_auctions, every interval of recurring auction templates
+//!     -- execute_demand_auctions, at the end block of demand auctions
+//!     -- clear_tier, every `ClearingInterval` blocks, at most `MaxMatchesPerClearing` sales a zone
+//!     -- try_state, checking auction, bid and order invariants under `try-runtime`
 //!
 //! `RPC`:
+//!     -- DoubleAuctionApi::bid_history(account, from_block, limit)
+//!     -- DoubleAuctionApi::current_era()
+//!     -- DoubleAuctionApi::leaderboard(era)
+//!     -- DoubleAuctionApi::suggest_starting_price(tier, quantity)
+//!     -- DoubleAuctionApi::price_oracle(tier)
+//!     -- DoubleAuctionApi::bid_histogram(tier)
+//!     -- DoubleAuctionApi::market_status()
+//!     -- DoubleAuctionApi::open_auctions(tier)
+//!     -- DoubleAuctionApi::auction_detail(auction_id)
+//!     -- DoubleAuctionApi::best_bid(auction_id)
+//!     -- DoubleAuctionApi::quote_bid(auction_id, quantity)
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -64,27 +192,58 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
-// pub mod weights;
-// pub use weights::*;
+
+pub mod clearing;
+pub mod escrow;
+pub mod migrations;
+pub mod runtime_api;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod traits;
+pub mod weights;
+pub use weights::*;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use crate::pallet::sp_runtime::{traits::AtLeast32BitUnsigned, FixedPointOperand};
+    use crate::pallet::sp_runtime::{
+        traits::{
+            AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, Hash, IdentifyAccount, One,
+            Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Verify, Zero,
+        },
+        DigestItem, FixedPointOperand, Perbill, Permill,
+    };
+    use crate::{
+        clearing::{self, Ask, Order},
+        escrow::Escrow,
+        migrations::{SteppedMigration, MAX_CURSOR_LEN},
+        traits::{ExecutionCandidate, ExecutionPriority, OnAuctionExecuted},
+    };
     use frame_support::{
-        dispatch::{fmt::Debug, Codec, EncodeLike},
+        dispatch::{fmt::Debug, Codec, EncodeLike, Pays},
         pallet_prelude::*,
         sp_runtime,
+        traits::{
+            fungibles::{self, Inspect as _, Transfer as _},
+            BalanceStatus, Currency, ExistenceRequirement, OnUnbalanced, ReservableCurrency,
+            WithdrawReasons,
+        },
+        PalletId,
     };
     use frame_system::pallet_prelude::*;
-    use scale_info::prelude::{vec, vec::Vec};
+    use scale_info::prelude::vec::Vec;
+
+    /// Prefix of the digest item committing to the settlements of a block
+    pub const SETTLEMENT_DIGEST_ID: [u8; 4] = *b"dasr";
+
+    /// Domain tag prefixing the bytes a relayed bid is signed over
+    pub const BID_SIGNING_TAG: [u8; 8] = *b"dabidsig";
 
     /// The current storage version.
     const STORAGE_VERSION: frame_support::traits::StorageVersion =
-        frame_support::traits::StorageVersion::new(1);
+        frame_support::traits::StorageVersion::new(7);
 
     #[pallet::pallet]
-    #[pallet::without_storage_info]
     #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
@@ -95,8 +254,8 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self, I>>
             + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-        // /// Type representing the weight of this pallet
-        // type WeightInfo: WeightInfo;
+        /// Type representing the weight of this pallet
+        type WeightInfo: WeightInfo;
 
         // Unique auction identifier
         type AuctionId: Parameter
@@ -142,6 +301,308 @@ pub mod pallet {
             + TypeInfo
             + FixedPointOperand
             + From<u128>;
+
+        /// Number of decimals of prices, for display
+        #[pallet::constant]
+        type PriceDecimals: Get<u8>;
+
+        /// Highest tier level, tiers range from 1 to `MaxTiers`
+        #[pallet::constant]
+        type MaxTiers: Get<u32>;
+
+        /// Quantity from which auctions are in the second tier, until governance sets tier
+        /// boundaries
+        #[pallet::constant]
+        type TierThreshold: Get<Self::Quantity>;
+
+        /// Minimum amount by which a bid must exceed the highest bid to replace it
+        #[pallet::constant]
+        type MinBidIncrement: Get<Self::Price>;
+
+        /// Lowest reputation score allowed to list or bid, zero to let anyone trade
+        #[pallet::constant]
+        type MinReputation: Get<u32>;
+
+        /// Currency in which storage deposits, and bids under `OnChainSettlement`, are held
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// Fungible assets sellers may be paid in instead of `Currency`, such as a stablecoin
+        type Assets: fungibles::Inspect<Self::AccountId, Balance = BalanceOf<Self, I>>
+            + fungibles::Transfer<Self::AccountId>;
+
+        /// Whether bids are held in `Currency` and winning bids paid to sellers by this pallet,
+        /// runtimes settling payments off-chain opt out
+        #[pallet::constant]
+        type OnChainSettlement: Get<bool>;
+
+        /// Whether this instance is a paper market: orders, matching and clearing events are
+        /// unchanged, but no deposits, commissions or penalties are applied
+        #[pallet::constant]
+        type Simulation: Get<bool>;
+
+        /// Deposit held from the seller per byte of auction data kept in state
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self, I>>;
+
+        /// Deposit held from the seller of every auction, returned when it settles or is cancelled
+        #[pallet::constant]
+        type AuctionDeposit: Get<BalanceOf<Self, I>>;
+
+        /// Auctions a seller may leave unsold in a row before their auction deposits are slashed
+        #[pallet::constant]
+        type MaxUnsoldStreak: Get<u32>;
+
+        /// Share of a sale's price slashed from its seller's bond, and paid to the buyer, when
+        /// arbitration upholds a delivery dispute
+        #[pallet::constant]
+        type NonDeliverySlash: Get<Permill>;
+
+        /// Account holding commissions collected at settlement
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Commission charged to each party of a settled auction, on the clearing price
+        #[pallet::constant]
+        type CommissionRate: Get<Perbill>;
+
+        /// Share of a participant's commission accrued to their referrer
+        #[pallet::constant]
+        type ReferralShare: Get<Perbill>;
+
+        /// Market fee taken from the seller on the clearing price of every executed auction
+        #[pallet::constant]
+        type FeeRate: Get<Permill>;
+
+        /// Quantity below which executed auctions are exempt from the market fee
+        #[pallet::constant]
+        type FeeExemptQuantity: Get<Self::Quantity>;
+
+        /// Handler of collected market fees, such as a treasury
+        type OnFeeCollected: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
+        /// Length in blocks of a market era, over which sponsorship allowances and
+        /// leaderboards are counted
+        #[pallet::constant]
+        type EraLength: Get<BlockNumberFor<Self>>;
+
+        /// Number of top sellers and buyers ranked per era
+        #[pallet::constant]
+        type MaxLeaderboardSize: Get<u32>;
+
+        /// Number of past eras whose leaderboards are kept
+        #[pallet::constant]
+        type LeaderboardRetention: Get<u32>;
+
+        /// Signature of bids relayed on behalf of their bidder
+        type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+        /// Public key verifying relayed bids, identifying the bidder
+        type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Signer of the relayed bids placed by the `bid_signed` benchmark
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: crate::traits::BenchmarkHelper<
+            Self::OffchainSignature,
+            Self::AccountId,
+        >;
+
+        /// Origin allowed to configure the market
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to cancel any seller's auction
+        type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to resolve delivery disputes
+        type ArbitrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Whether only registered participants may list and bid, in the role they registered
+        #[pallet::constant]
+        type RequireRegistration: Get<bool>;
+
+        /// Whether registered participants must also be KYC-verified to list and bid
+        #[pallet::constant]
+        type RequireKyc: Get<bool>;
+
+        /// Storage migration run in steps from `on_idle` once started
+        type SteppedMigration: SteppedMigration;
+
+        /// Order in which auctions due in the same block are executed
+        type ExecutionPriority: ExecutionPriority<Self::AuctionId, Self::Price, Self::Quantity>;
+
+        /// Handler told of every executed auction, such as a settlement, metering or
+        /// certificate pallet
+        type OnAuctionExecuted: OnAuctionExecuted<
+            Self::AuctionId,
+            Self::AccountId,
+            Self::Quantity,
+            Self::Price,
+        >;
+
+        /// Maximum number of auctions executed per block, the rest is deferred to the next block
+        #[pallet::constant]
+        type MaxExecutionsPerBlock: Get<u32>;
+
+        /// Maximum number of auctions ending in the same block, later listings end in the
+        /// next block with room
+        #[pallet::constant]
+        type MaxAuctionsPerEndBlock: Get<u32>;
+
+        /// Maximum total duration of an auction in blocks, including extensions
+        #[pallet::constant]
+        type MaxAuctionDuration: Get<BlockNumberFor<Self>>;
+
+        /// Block time in milliseconds, converting auction periods given in minutes to blocks
+        #[pallet::constant]
+        type BlockTimeMillis: Get<u64>;
+
+        /// Minimum period in blocks an auction is listed for
+        #[pallet::constant]
+        type MinAuctionPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Maximum period in blocks an auction is listed for, at most `MaxAuctionDuration`
+        #[pallet::constant]
+        type MaxAuctionPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Blocks a matched buyer has to accept the match before it falls to the next bidder,
+        /// zero settles matches immediately
+        #[pallet::constant]
+        type AcceptanceWindow: Get<BlockNumberFor<Self>>;
+
+        /// Last blocks of a sealed-bid auction's period in which commitments are revealed
+        #[pallet::constant]
+        type RevealWindow: Get<BlockNumberFor<Self>>;
+
+        /// Blocks between the uniform-price clearings of every tier
+        #[pallet::constant]
+        type ClearingInterval: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of auctions a single zone clearing sells
+        #[pallet::constant]
+        type MaxMatchesPerClearing: Get<u32>;
+
+        /// Share of the quantity offered to a zone clearing that demand able to buy at the
+        /// cheapest ask must reach, otherwise the clearing is postponed to the next round
+        #[pallet::constant]
+        type ClearingQuorum: Get<Perbill>;
+
+        /// Last blocks of an auction's period in which a bid extends it, zero disables
+        /// extensions
+        #[pallet::constant]
+        type ExtensionThreshold: Get<BlockNumberFor<Self>>;
+
+        /// Blocks an auction is extended by when bid on in its last `ExtensionThreshold` blocks
+        #[pallet::constant]
+        type ExtensionPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Blocks a seller waits before listing the same quantity again after cancelling an
+        /// auction that had bids
+        #[pallet::constant]
+        type RelistCooldown: Get<BlockNumberFor<Self>>;
+
+        /// Times an auction ending without bids is listed again automatically, zero to
+        /// archive it for a manual relist at once
+        #[pallet::constant]
+        type MaxRelistAttempts: Get<u32>;
+
+        /// Discount on the starting price of every automatic relist
+        #[pallet::constant]
+        type RelistDiscount: Get<Permill>;
+
+        /// Maximum length in bytes of auction metadata
+        #[pallet::constant]
+        type MetadataLimit: Get<u32>;
+
+        /// Maximum length in bytes of a bid memo
+        #[pallet::constant]
+        type MemoLimit: Get<u32>;
+
+        /// Maximum number of auctions tracked per seller
+        #[pallet::constant]
+        type MaxSellOrders: Get<u32>;
+
+        /// Maximum number of auctions tracked per buyer
+        #[pallet::constant]
+        type MaxBuyOrders: Get<u32>;
+
+        /// Maximum number of bidders of an auction
+        #[pallet::constant]
+        type MaxBidsPerAuction: Get<u32>;
+
+        /// Maximum number of bids placed by a single `bid_batch` call
+        #[pallet::constant]
+        type MaxBatchBids: Get<u32>;
+
+        /// Maximum number of listed auctions a seller has open at once
+        #[pallet::constant]
+        type MaxOpenAuctionsPerSeller: Get<u32>;
+
+        /// Maximum number of open auctions a buyer holds bids on at once
+        #[pallet::constant]
+        type MaxOpenBidsPerBuyer: Get<u32>;
+
+        /// Maximum number of open and pending auctions listed in a tier at once
+        #[pallet::constant]
+        type MaxAuctionsPerTier: Get<u32>;
+
+        /// Maximum number of standing buy orders per tier
+        #[pallet::constant]
+        type MaxStandingBuyOrders: Get<u32>;
+
+        /// Maximum number of recurring auction templates per seller
+        #[pallet::constant]
+        type MaxRecurringAuctions: Get<u32>;
+
+        /// Maximum number of buyers a partial-fill auction is split across
+        #[pallet::constant]
+        type MaxFillsPerAuction: Get<u32>;
+
+        /// Maximum number of past bids kept per account
+        #[pallet::constant]
+        type MaxBidHistory: Get<u32>;
+
+        /// Maximum number of recent outcome notifications kept per account
+        #[pallet::constant]
+        type MaxNotifications: Get<u32>;
+
+        /// Maximum number of accounts subscribed to a tier
+        #[pallet::constant]
+        type MaxTierSubscribers: Get<u32>;
+
+        /// Number of recent market events kept for tickers
+        #[pallet::constant]
+        type MaxMarketActivity: Get<u32>;
+
+        /// Number of recent clearings per tier used to suggest starting prices
+        #[pallet::constant]
+        type MaxRecentClearings: Get<u32>;
+
+        /// Maximum number of executed trades kept per tier
+        #[pallet::constant]
+        type MaxTradeHistory: Get<u32>;
+
+        /// Blocks an executed trade is kept in the trade history of its tier
+        #[pallet::constant]
+        type HistoryDepth: Get<BlockNumberFor<Self>>;
+
+        /// Last blocks whose trades the price oracle of a tier averages, at most `HistoryDepth`
+        #[pallet::constant]
+        type OracleWindow: Get<BlockNumberFor<Self>>;
+
+        /// Spread around the volume-weighted average price of suggested starting prices
+        #[pallet::constant]
+        type PriceSpread: Get<Perbill>;
+
+        /// Number of price buckets of the bid histogram of a tier
+        #[pallet::constant]
+        type MaxHistogramBuckets: Get<u32>;
+
+        /// Width of a bid histogram bucket in price per unit of energy, the last bucket is open
+        #[pallet::constant]
+        type HistogramBucketWidth: Get<Self::Price>;
+
+        /// Number of eras, including the current one, covered by bid histograms
+        #[pallet::constant]
+        type HistogramWindow: Get<u32>;
     }
 
     //////////////////////
@@ -149,43 +610,172 @@ pub mod pallet {
     /////////////////////
 
     // Buyers bid
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-    pub struct Bid<AccountId, Price> {
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Bid<AccountId, Price, Memo> {
         pub bidder: AccountId,
         pub bid: Price,
+        pub memo: Option<Memo>,
     }
 
-    // Status of an auction, live auctions accepts bids
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    // Leading bid of a bidder on an auction, keyed by auction and bidder
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct BidRecord<Price, Memo, BlockNumber, Quantity> {
+        pub bid: Price,
+        pub memo: Option<Memo>,
+        pub placed_at: BlockNumber,
+        pub quantity: Option<Quantity>, // part of a partial-fill auction, whole quantity if none
+    }
+
+    // Lifecycle state of an auction, live auctions accepts bids
+    //
+    // Pending -> Open -> Matched -> Settled
+    //    |         |        |
+    //    |         |        +----> Expired
+    //    |         +-------------> Expired
+    //    +---------+-------------> Cancelled
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
     pub enum AuctionStatus {
         Open,
-        Closed,
+        Pending,   // listed with a start in the future, not accepting bids yet
+        Matched,   // waiting for acceptance or settlement with its buyer
+        Settled,   // sold and paid
+        Expired,   // ended without a sale
+        Cancelled, // withdrawn before it ended
     }
     impl Default for AuctionStatus {
         fn default() -> Self {
             AuctionStatus::Open
         }
     }
+    impl AuctionStatus {
+        /// Whether an auction in this state may move to `next`
+        pub fn can_transition_to(&self, next: &AuctionStatus) -> bool {
+            use AuctionStatus::*;
+            matches!(
+                (self, next),
+                (Pending, Open)
+                    | (Pending, Cancelled)
+                    | (Open, Matched)
+                    | (Open, Expired)
+                    | (Open, Cancelled)
+                    | (Matched, Settled)
+                    | (Matched, Expired)
+            )
+        }
+    }
 
     // Essential data for an auction
-    #[derive(Clone, Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-    pub struct AuctionData<AccountId, AuctionId, Bid, BlockNumber, Quantity, Tier> {
+    #[derive(
+        Clone, Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct AuctionData<AccountId, AuctionId, Bid, BlockNumber, Quantity, Tier, Metadata, Price> {
         pub auction_id: AuctionId,
         pub seller_id: AccountId,
         pub quantity: Quantity,
         pub starting_bid: Bid,
-        pub bids: Vec<Bid>,
+        pub bid_count: u32,
         pub auction_period: BlockNumber,
         pub auction_status: AuctionStatus,
         pub start_at: BlockNumber,
         pub end_at: BlockNumber,
         pub highest_bid: Bid,
         pub auction_category: Tier,
+        pub metadata: Option<Metadata>,
+        pub min_bidders: u32,
+        pub match_rule: Option<MatchingAlgorithm>,
+        pub settlement_lag: BlockNumber,
+        pub reserve_price: Option<ReservePrice<Price>>,
+        pub zone: ZoneId, // grid zone the energy is fed into
+        pub delivery_window: Option<DeliveryWindow<BlockNumber>>,
+    }
+
+    // Blocks the energy of an auction is delivered in, payment waits for the buyer's confirmation
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DeliveryWindow<BlockNumber> {
+        pub delivery_start: BlockNumber,
+        pub delivery_end: BlockNumber,
+    }
+
+    // Settled sale whose payment stays in escrow until its delivery is confirmed or arbitrated
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Delivery<AccountId, BlockNumber, Price, Quantity> {
+        pub seller: AccountId,
+        pub quantity: Quantity,
+        pub price: Price,
+        pub window: DeliveryWindow<BlockNumber>,
+        pub disputed: bool, // frozen until `ArbitrationOrigin` resolves it
+    }
+
+    // Track record of a participant across its auctions
+    #[derive(
+        Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct ReputationStats {
+        pub completed_trades: u32,
+        pub cancellations: u32, // listings and bids withdrawn
+        pub disputes: u32,      // delivery disputes decided against the account
+        pub confirmed_deliveries: u32,
+    }
+    impl ReputationStats {
+        // Trades and confirmed deliveries, less cancellations and lost disputes
+        pub fn score(&self) -> u32 {
+            self.completed_trades
+                .saturating_add(self.confirmed_deliveries)
+                .saturating_sub(self.cancellations.saturating_add(self.disputes))
+        }
+    }
+
+    // Lowest price a seller accepts for the whole quantity, an auction ends unsold below it
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ReservePrice<Price> {
+        pub price: Price,
+        pub relist: bool, // list again for another period when the reserve is not met
+    }
+
+    // Terms of a new listing beyond its quantity, price and period, each left out takes the
+    // default noted
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct ListingOptions<Metadata, BlockNumber, AssetId> {
+        pub metadata: Option<Metadata>,
+        pub min_bidders: Option<u32>, // defaults to the tier minimum
+        pub match_rule: Option<MatchingAlgorithm>, // defaults to the tier algorithm
+        pub settlement_lag: Option<BlockNumber>, // blocks from bidding close to settlement
+        pub reserve_price: Option<ReservePrice<u128>>, // no sale below it, none to sell at any bid
+        pub buyout_price: Option<u128>, // sells at once to a buyer paying it, none to wait for the end
+        pub start_at: Option<BlockNumber>, // block bidding opens, none to open now
+        pub zone: Option<ZoneId>,       // grid zone fed into, none for the default zone
+        pub delivery_window: Option<DeliveryWindow<BlockNumber>>, // none to pay on settlement
+        pub payment_asset: Option<AssetId>, // asset bids are paid in, none for `Currency`
+    }
+    impl<Metadata, BlockNumber, AssetId> Default for ListingOptions<Metadata, BlockNumber, AssetId> {
+        fn default() -> Self {
+            ListingOptions {
+                metadata: None,
+                min_bidders: None,
+                match_rule: None,
+                settlement_lag: None,
+                reserve_price: None,
+                buyout_price: None,
+                start_at: None,
+                zone: None,
+                delivery_window: None,
+                payment_asset: None,
+            }
+        }
+    }
+
+    // Descending ask of a Dutch auction, lowered by `decrement` every `interval` blocks
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PriceDecay<Price, BlockNumber> {
+        pub floor: Price,
+        pub decrement: Price,
+        pub interval: BlockNumber,
     }
 
     // Tier of an auction sale
     // Higher quantity of energy for sale leads to higher tier
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub struct Tier {
         pub level: u32,
     }
@@ -195,25 +785,491 @@ pub mod pallet {
         }
     }
 
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
     pub enum PartyType {
         Seller,
         Buyer,
+        // both lists and bids, as a producer consuming energy
+        Prosumer,
+        // covers the deposits and fees of small accounts
+        Sponsor,
     }
     impl Default for PartyType {
         fn default() -> Self {
             PartyType::Seller
         }
     }
+    impl PartyType {
+        // Whether an account registered with this role may act as `party`
+        pub fn allows(&self, party: PartyType) -> bool {
+            matches!(
+                (self, party),
+                (
+                    PartyType::Prosumer,
+                    PartyType::Seller | PartyType::Buyer | PartyType::Prosumer
+                ) | (PartyType::Seller, PartyType::Seller)
+                    | (PartyType::Buyer, PartyType::Buyer)
+                    | (PartyType::Sponsor, PartyType::Sponsor)
+            )
+        }
+    }
+
+    // Registered market participant, verified and suspended by governance
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Participant<BlockNumber> {
+        pub role: PartyType,
+        pub kyc_verified: bool,
+        pub suspended: bool,
+        pub registered_at: BlockNumber,
+    }
+
+    // Engine clearing the auctions of a tier, or of a listing when chosen by its seller
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub enum MatchingAlgorithm {
+        // highest bidder takes the whole quantity at its bid
+        SingleWinner,
+        // quantity is split across several bids
+        PartialFill,
+        // all matches of a tier clear at one price
+        UniformPrice,
+        // every matched bidder pays its own bid
+        PayAsBid,
+        // bids stay hidden until the auction closes
+        SealedBid,
+        // quantity is only sold whole
+        AllOrNothing,
+        // highest bidder takes the whole quantity at the second-highest bid
+        Vickrey,
+    }
+    impl Default for MatchingAlgorithm {
+        fn default() -> Self {
+            MatchingAlgorithm::SingleWinner
+        }
+    }
+
+    // Fixed-size view of a live auction for light clients
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct AuctionSummary<BlockNumber, Price, Quantity> {
+        pub best_bid: Price,
+        pub quantity_remaining: Quantity,
+        pub end_at: BlockNumber,
+        pub status: AuctionStatus,
+    }
+
+    // View of a live auction served to front-ends by the runtime API
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub struct AuctionDetail<AccountId, AuctionId, BlockNumber, Price, Quantity> {
+        pub auction_id: AuctionId,
+        pub seller_id: AccountId,
+        pub tier: u32,
+        pub quantity: Quantity,
+        pub starting_price: Price,
+        pub highest_bid: Price, // starting price until a bid leads
+        pub bid_count: u32,
+        pub status: AuctionStatus,
+        pub start_at: BlockNumber,
+        pub end_at: BlockNumber,
+        pub algorithm: MatchingAlgorithm,
+        pub reserve_price: Option<Price>,
+        pub buyout_price: Option<Price>,
+    }
+
+    // Match settled by an auction, committed to in the block digest
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct SettlementResult<AccountId, AuctionId, Price, Quantity> {
+        pub auction_id: AuctionId,
+        pub seller_id: AccountId,
+        pub buyer_id: AccountId,
+        pub quantity: Quantity,
+        pub price: Price,
+    }
+
+    // Outcome of a bid placed by a buyer
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum BidOutcome {
+        Pending,
+        Won,
+        Outbid,
+        Refunded,
+    }
+
+    // What the clearing price of an executed auction was set by
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum PriceBasis {
+        // the winner pays its own bid
+        HighestBid,
+        // the winner pays the second-highest bid
+        SecondPrice,
+        // every match of a tier clearing pays the same price
+        UniformPrice,
+    }
+
+    // How a batch of bids treats a bid that fails
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum BatchMode {
+        // the whole batch fails with the first failing bid
+        AllOrNothing,
+        // failing bids are skipped and the others placed
+        BestEffort,
+    }
+
+    // Why the execution of an auction was pushed to a later block
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum DeferralReason {
+        // more auctions were due than `MaxExecutionsPerBlock` or the block's idle time allows
+        WeightCap,
+        // trading in the auction's tier is halted
+        TierHalted,
+        // the buy leg of the auction's order pair was moved to or past its end
+        PairedBuyLeg,
+    }
 
-    // Auctions linked to an auction participant
+    // Participation of a small account covered by a sponsor, limits are per era
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Sponsorship<AccountId, Balance> {
+        pub sponsor: AccountId,
+        pub deposit_limit: Balance,
+        pub call_limit: u32,
+        pub era: u32,
+        pub deposit_used: Balance,
+        pub calls_used: u32,
+    }
+
+    // Institutional buyer whose members bid under one identity, within shared limits
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Organization<AccountId, Price> {
+        pub admin: AccountId,
+        pub bid_limit: Price,
+        pub budget: Price,
+        pub spent: Price,
+    }
+
+    // Bid signed by its bidder and relayed by a gateway
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-    pub struct AuctionInfo<AccountId, AuctionId, Bid, BlockNumber, Tier, PartyType, Quantity> {
-        pub participant_id: Option<AccountId>,
-        pub party_type: PartyType,
-        pub auctions: Vec<AuctionData<AccountId, AuctionId, Bid, BlockNumber, Quantity, Tier>>, /* Maximum* length of 5 */
+    pub struct BidPayload<AccountId, AuctionId, Memo, BlockNumber> {
+        pub bidder: AccountId,
+        pub auction_id: AuctionId,
+        pub bid: u128,
+        pub memo: Option<Memo>,
+        pub nonce: u64,
+        pub expires_at: BlockNumber, // last block the bid can be relayed in
+    }
+
+    // Energy settled by an account during an era
+    #[derive(
+        Clone, Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct EraVolume<Quantity> {
+        pub sold: Quantity,
+        pub bought: Quantity,
+    }
+
+    // Top sellers and buyers of an era by settled volume, highest first
+    // `Ranking` is a list of `(account, volume)`, bounded in storage
+    #[derive(
+        Clone, Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct Leaderboard<Ranking> {
+        pub sellers: Ranking,
+        pub buyers: Ranking,
+    }
+
+    // Outcome of an auction for one of its participants
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum Outcome<Price> {
+        // buyer won the auction at `price`
+        Won { price: Price },
+        // buyer's bid was beaten by a bid of `by`
+        Outbid { by: Price },
+        // buyer's bid of `amount` is released
+        RefundReady { amount: Price },
+        // seller sold at `price`
+        Sold { price: Price },
+        // seller's auction closed without a match
+        ExpiredUnsold,
+        // auction listed at starting `price` in a subscribed tier
+        Listed { tier: u32, price: Price },
+        // auction of a subscribed tier cleared at `price`
+        Cleared { tier: u32, price: Price },
+    }
+
+    // Recent outcome kept for wallets
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Notification<AuctionId, BlockNumber, Price> {
+        pub auction_id: AuctionId,
+        pub outcome: Outcome<Price>,
+        pub at: BlockNumber,
+    }
+
+    // Kind of market event shown on tickers
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum ActivityKind {
+        Created,
+        Matched,
+        Canceled,
+    }
+
+    // Recent market event, price is the starting price or the clearing price when matched
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Activity<AuctionId, BlockNumber, Price, Quantity> {
+        pub kind: ActivityKind,
+        pub auction_id: AuctionId,
+        pub quantity: Quantity,
+        pub price: Price,
+        pub at: BlockNumber,
+    }
+
+    // Starting price suggested from recent clearings of a tier
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PriceSuggestion<Price> {
+        pub low: Price,
+        pub price: Price,
+        pub high: Price,
+    }
+
+    // Average clearing price per unit of quantity of a tier over the oracle window
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct OraclePrice<Price, Quantity> {
+        // each trade's price weighted by the blocks it stayed the last one
+        pub time_weighted: Price,
+        // each trade's price weighted by its quantity
+        pub volume_weighted: Price,
+        pub volume: Quantity,
+        pub trades: u32,
+    }
+
+    // Buy leg and later sell leg of a storage operator, settled together or not at all
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct OrderPair<AccountId, AuctionId> {
+        pub owner: AccountId,
+        pub buy_auction_id: AuctionId,
+        pub sell_auction_id: AuctionId,
+    }
+
+    // Template of an auction listed again every `interval` blocks
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RecurringAuction<AccountId, BlockNumber, Price, Quantity> {
+        pub seller: AccountId,
+        pub quantity: Quantity,
+        pub starting_price: Price,
+        pub auction_period: BlockNumber,
+        pub interval: BlockNumber,
+        pub next_at: BlockNumber, // block the next auction is listed
+    }
+
+    // Demand posted by a buyer, for which sellers offer ever lower prices until it ends
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DemandAuction<AccountId, AuctionId, BlockNumber, Price, Quantity> {
+        pub demand_id: AuctionId,
+        pub buyer: AccountId,
+        pub quantity: Quantity,
+        pub max_price: Price,
+        pub best_offer: Option<(AccountId, Price)>, // lowest offer, winning at the end
+        pub offer_count: u32,
+        pub start_at: BlockNumber,
+        pub end_at: BlockNumber,
+    }
+
+    // Standing buy order of a tier, filled by new listings that cross it
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct BuyOrder<AccountId, BlockNumber, Price, Quantity> {
+        pub order_id: u64,
+        pub buyer: AccountId,
+        pub quantity: Quantity, // still to be bought
+        pub max_unit_price: Price,
+        pub placed_at: BlockNumber,
+        pub zone: ZoneId, // grid zone the energy is drawn from
+    }
+
+    // Part of a partial-fill auction settled with one buyer
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Fill<AccountId, Price, Quantity> {
+        pub buyer: AccountId,
+        pub quantity: Quantity,
+        pub price: Price,
+    }
+
+    // Market parameter tunable by governance, `None` restores the runtime constant
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum MarketParameter<Price, BlockNumber> {
+        MinBidIncrement(Option<Price>),
+        CommissionRate(Option<Perbill>),
+        MaxExecutionsPerBlock(Option<u32>),
+        ExtensionThreshold(Option<BlockNumber>),
+        ExtensionPeriod(Option<BlockNumber>),
+    }
+
+    // Governance overrides of runtime constants, unset values fall back to the constants
+    #[derive(
+        Clone, Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct MarketParameters<Price, BlockNumber> {
+        pub min_bid_increment: Option<Price>,
+        pub commission_rate: Option<Perbill>,
+        pub max_executions_per_block: Option<u32>,
+        pub extension_threshold: Option<BlockNumber>,
+        pub extension_period: Option<BlockNumber>,
+    }
+
+    // Past bid of a participant
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct BidHistoryEntry<AuctionId, BlockNumber, Price> {
+        pub auction_id: AuctionId,
+        pub bid: Price,
+        pub placed_at: BlockNumber,
+        pub outcome: BidOutcome,
+    }
+
+    // Trading state of a tier
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum TierState {
+        // listings, bids and executions proceed
+        Open,
+        // trading proceeds while a stepped storage migration is ongoing
+        Migrating,
+        // listings and bids are rejected and executions deferred by governance
+        Halted,
+        // listings and bids are rejected by `pause_market` while executions proceed
+        Paused,
+    }
+
+    // Health of a tier for operators and bots
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct MarketStatus<BlockNumber> {
+        pub tier: u32,
+        pub state: TierState,
+        pub open_auctions: u32,
+        pub awaiting_acceptance: u32,
+        pub awaiting_settlement: u32,
+        // blocks until the next auction of the tier is due, `None` without live auctions
+        pub next_clearing_in: Option<BlockNumber>,
+    }
+
+    // Balance of the currency used for deposits
+    pub type BalanceOf<T, I = ()> =
+        <<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type NegativeImbalanceOf<T, I = ()> = <<T as Config<I>>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    // Id of the assets auctions may be paid in
+    pub type AssetIdOf<T, I = ()> = <<T as Config<I>>::Assets as fungibles::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::AssetId;
+
+    // Grid zone energy is fed into or drawn from, `DEFAULT_ZONE` unless set
+    pub type ZoneId = u32;
+    pub const DEFAULT_ZONE: ZoneId = 0;
+
+    // Free-form reference attached to a bid
+    pub type MemoOf<T, I = ()> = BoundedVec<u8, <T as Config<I>>::MemoLimit>;
+
+    // Bid as stored by this pallet instance
+    pub type BidOf<T, I = ()> =
+        Bid<<T as frame_system::Config>::AccountId, <T as Config<I>>::Price, MemoOf<T, I>>;
+
+    // Relayed bid as accepted by this pallet instance
+    pub type BidPayloadOf<T, I = ()> = BidPayload<
+        <T as frame_system::Config>::AccountId,
+        <T as Config<I>>::AuctionId,
+        MemoOf<T, I>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    // Settlement as produced by this pallet instance
+    pub type SettlementResultOf<T, I = ()> = SettlementResult<
+        <T as frame_system::Config>::AccountId,
+        <T as Config<I>>::AuctionId,
+        <T as Config<I>>::Price,
+        <T as Config<I>>::Quantity,
+    >;
+
+    // Bid record as stored by this pallet instance
+    pub type BidRecordOf<T, I = ()> = BidRecord<
+        <T as Config<I>>::Price,
+        MemoOf<T, I>,
+        BlockNumberFor<T>,
+        <T as Config<I>>::Quantity,
+    >;
+
+    // Off-chain listing reference (e.g. an IPFS CID of contract terms)
+    pub type MetadataOf<T, I = ()> = BoundedVec<u8, <T as Config<I>>::MetadataLimit>;
+
+    // Lowest quantity of every tier above the first, ascending
+    pub type TierBoundariesOf<T, I = ()> =
+        BoundedVec<<T as Config<I>>::Quantity, <T as Config<I>>::MaxTiers>;
+
+    // Pending delivery as stored by this pallet instance
+    pub type DeliveryOf<T, I = ()> = Delivery<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as Config<I>>::Price,
+        <T as Config<I>>::Quantity,
+    >;
+
+    // Standing buy order as stored by this pallet instance
+    pub type BuyOrderOf<T, I = ()> = BuyOrder<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as Config<I>>::Price,
+        <T as Config<I>>::Quantity,
+    >;
+
+    // Recurring auction template as stored by this pallet instance
+    pub type RecurringAuctionOf<T, I = ()> = RecurringAuction<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as Config<I>>::Price,
+        <T as Config<I>>::Quantity,
+    >;
+
+    pub type DemandAuctionOf<T, I = ()> = DemandAuction<
+        <T as frame_system::Config>::AccountId,
+        <T as Config<I>>::AuctionId,
+        BlockNumberFor<T>,
+        <T as Config<I>>::Price,
+        <T as Config<I>>::Quantity,
+    >;
+
+    pub type RankingOf<T, I = ()> = BoundedVec<
+        (
+            <T as frame_system::Config>::AccountId,
+            <T as Config<I>>::Quantity,
+        ),
+        <T as Config<I>>::MaxLeaderboardSize,
+    >;
+
+    // Auction execution counters of a block
+    #[derive(
+        Clone, Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct ExecutionTelemetry<BlockNumber, Quantity> {
+        pub block: BlockNumber,
+        pub executed: u32,
+        pub deferred: u32,
+        pub unmatched: u32,
+        pub energy_cleared: Quantity,
     }
 
+    // Listing terms as accepted by this pallet instance
+    pub type ListingOptionsOf<T, I = ()> =
+        ListingOptions<MetadataOf<T, I>, BlockNumberFor<T>, AssetIdOf<T, I>>;
+
+    // Auction data as stored by this pallet instance
+    pub type AuctionDataOf<T, I = ()> = AuctionData<
+        <T as frame_system::Config>::AccountId,
+        <T as Config<I>>::AuctionId,
+        BidOf<T, I>,
+        BlockNumberFor<T>,
+        <T as Config<I>>::Quantity,
+        Tier,
+        MetadataOf<T, I>,
+        <T as Config<I>>::Price,
+    >;
+
     //////////////////////
     // Storage item    //
     /////////////////////
@@ -221,25 +1277,28 @@ pub mod pallet {
     #[pallet::getter(fn auctions_index)]
     pub(super) type AuctionIndex<T: Config<I>, I: 'static = ()> = StorageValue<_, T::AuctionId>;
 
-    /// Stores on-going and future auctions of participants
-    /// Maximum of 5 auction cached at a time
-    // TODO: use BoundedVec
+    /// Stores on-going and future auctions of sellers, least recently active first
+    /// Least recently active auction is dropped once `MaxSellOrders` is reached
     #[pallet::storage]
-    #[pallet::getter(fn auctions_of)]
-    pub(super) type AuctionsOf<T: Config<I>, I: 'static = ()> = StorageMap<
+    #[pallet::getter(fn sell_orders_of)]
+    pub(super) type SellOrdersOf<T: Config<I>, I: 'static = ()> = StorageMap<
         _,
         Twox64Concat,
         T::AccountId,
-        AuctionInfo<
-            T::AccountId,
-            T::AuctionId,
-            Bid<T::AccountId, T::Price>,
-            BlockNumberFor<T>,
-            Tier,
-            PartyType,
-            T::Quantity,
-        >,
-        OptionQuery,
+        BoundedVec<T::AuctionId, T::MaxSellOrders>,
+        ValueQuery,
+    >;
+
+    /// Stores on-going auctions buyers have bid on, least recently active first
+    /// Least recently active auction is dropped once `MaxBuyOrders` is reached
+    #[pallet::storage]
+    #[pallet::getter(fn buy_orders_of)]
+    pub(super) type BuyOrdersOf<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<T::AuctionId, T::MaxBuyOrders>,
+        ValueQuery,
     >;
 
     /// Stores on-going and future auctions of participants
@@ -253,446 +1312,7447 @@ pub mod pallet {
         AuctionData<
             T::AccountId,
             T::AuctionId,
-            Bid<T::AccountId, T::Price>,
+            BidOf<T, I>,
             BlockNumberFor<T>,
             T::Quantity,
             Tier,
+            MetadataOf<T, I>,
+            T::Price,
         >,
         OptionQuery,
     >;
 
-    /// Index auctions by end time.
+    /// Compact summary of every live auction, kept apart from the full auction data
     #[pallet::storage]
-    #[pallet::getter(fn auction_execution_queue)]
-    pub(super) type AuctionsExecutionQueue<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+    #[pallet::getter(fn auction_summary)]
+    pub(super) type AuctionSummaries<T: Config<I>, I: 'static = ()> = StorageMap<
         _,
         Twox64Concat,
-        BlockNumberFor<T>,
-        Blake2_128Concat,
         T::AuctionId,
-        (),
+        AuctionSummary<BlockNumberFor<T>, T::Price, T::Quantity>,
         OptionQuery,
     >;
 
-    /////////////////////
-    // Genesis config //
-    ////////////////////
+    /// Storage deposit held from the seller of an auction
+    /// Released when the auction is removed from state
+    #[pallet::storage]
+    #[pallet::getter(fn storage_deposit)]
+    pub(super) type StorageDeposits<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, BalanceOf<T, I>, ValueQuery>;
+
+    /// Listing deposit held from the seller of an auction
+    /// Kept while an unsold auction is archived, released when it settles, is cancelled or relisted
+    #[pallet::storage]
+    #[pallet::getter(fn auction_deposit)]
+    pub(super) type AuctionDeposits<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, BalanceOf<T, I>, ValueQuery>;
+
+    /// Stake reserved by a seller to back its deliveries, slashed when it fails to deliver
+    #[pallet::storage]
+    #[pallet::getter(fn seller_bond)]
+    pub(super) type SellerBonds<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T, I>, ValueQuery>;
+
+    /// Auctions of a seller that ended unsold since their last sale
+    #[pallet::storage]
+    #[pallet::getter(fn unsold_streak)]
+    pub(super) type UnsoldStreaks<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Open and pending auctions listed by a seller, bounded by `MaxOpenAuctionsPerSeller`
+    #[pallet::storage]
+    #[pallet::getter(fn open_auction_count)]
+    pub(super) type OpenAuctionCounts<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Auctions a buyer holds a bid on, bounded by `MaxOpenBidsPerBuyer`
+    #[pallet::storage]
+    #[pallet::getter(fn open_bid_count)]
+    pub(super) type OpenBidCounts<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Open and pending auctions of a tier, oldest first, bounded by `MaxAuctionsPerTier`
+    #[pallet::storage]
+    #[pallet::getter(fn tier_auctions)]
+    pub(super) type TierAuctions<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        BoundedVec<T::AuctionId, T::MaxAuctionsPerTier>,
+        ValueQuery,
+    >;
+
+    /// Account that referred a participant
+    #[pallet::storage]
+    #[pallet::getter(fn referrer_of)]
+    pub(super) type Referrers<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// Referral rewards accrued by a referrer and not yet claimed
+    #[pallet::storage]
+    #[pallet::getter(fn referral_rewards)]
+    pub(super) type ReferralRewards<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T, I>, ValueQuery>;
+
+    /// Sponsorship covering the fees and deposits of an account
+    #[pallet::storage]
+    #[pallet::getter(fn sponsorship_of)]
+    pub(super) type Sponsorships<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Sponsorship<T::AccountId, BalanceOf<T, I>>,
+        OptionQuery,
+    >;
+
+    /// Next organization identifier
+    #[pallet::storage]
+    #[pallet::getter(fn organizations_index)]
+    pub(super) type OrganizationIndex<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, u32, ValueQuery>;
+
+    /// Registered organizations
+    #[pallet::storage]
+    #[pallet::getter(fn organization)]
+    pub(super) type Organizations<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, Organization<T::AccountId, T::Price>, OptionQuery>;
+
+    /// Organization an account bids for
+    #[pallet::storage]
+    #[pallet::getter(fn organization_of)]
+    pub(super) type OrganizationOf<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, OptionQuery>;
+
+    /// Registered market participants
+    #[pallet::storage]
+    #[pallet::getter(fn participant)]
+    pub(super) type Participants<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, Participant<BlockNumberFor<T>>, OptionQuery>;
+
+    /// Account holding the storage deposit of an auction when it is not its seller
+    #[pallet::storage]
+    #[pallet::getter(fn deposit_payer)]
+    pub(super) type DepositPayers<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, T::AccountId, OptionQuery>;
+
+    /// Nonce expected in the next relayed bid of an account
+    #[pallet::storage]
+    #[pallet::getter(fn bid_nonce)]
+    pub(super) type BidNonces<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
+
+    /// Tiers in which trading is halted, by tier level
+    #[pallet::storage]
+    #[pallet::getter(fn tier_halted)]
+    pub(super) type HaltedTiers<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, bool, ValueQuery>;
+
+    /// Whether governance paused listings and bids in every tier
+    #[pallet::storage]
+    #[pallet::getter(fn market_paused)]
+    pub(super) type MarketPaused<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+    /// Matching algorithm applied to the auctions of a tier, by tier level
+    #[pallet::storage]
+    #[pallet::getter(fn matching_algorithm)]
+    pub(super) type TierMatchingAlgorithms<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, MatchingAlgorithm, ValueQuery>;
+
+    /// Share of energy lost flowing between adjacent grid zones, stored both ways
+    /// Zones without a link cannot trade with each other
+    #[pallet::storage]
+    #[pallet::getter(fn zone_link)]
+    pub(super) type ZoneTopology<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        ZoneId,
+        Twox64Concat,
+        ZoneId, // neighbour
+        Permill,
+        OptionQuery,
+    >;
+
+    /// Matching algorithm switches applied at the next era boundary, by tier level
+    #[pallet::storage]
+    #[pallet::getter(fn pending_matching_algorithm)]
+    pub(super) type PendingMatchingAlgorithms<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, MatchingAlgorithm, OptionQuery>;
+
+    /// Energy settled by an account in an era
+    #[pallet::storage]
+    #[pallet::getter(fn era_volume)]
+    pub(super) type EraVolumes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32, // era
+        Twox64Concat,
+        T::AccountId,
+        EraVolume<T::Quantity>,
+        ValueQuery,
+    >;
+
+    /// Leaderboard of an era, updated at every settlement
+    #[pallet::storage]
+    #[pallet::getter(fn leaderboard)]
+    pub(super) type Leaderboards<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, Leaderboard<RankingOf<T, I>>, OptionQuery>;
+
+    /// Number of bids per price bucket placed in a tier during an era
+    #[pallet::storage]
+    #[pallet::getter(fn era_bid_histogram)]
+    pub(super) type BidHistograms<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32, // tier
+        Twox64Concat,
+        u32, // era
+        BoundedVec<u32, T::MaxHistogramBuckets>,
+        ValueQuery,
+    >;
+
+    /// Quantity bands of the tiers set by governance, `TierThreshold` splits two tiers without
+    #[pallet::storage]
+    #[pallet::getter(fn tier_boundaries)]
+    pub(super) type TierConfig<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, TierBoundariesOf<T, I>, OptionQuery>;
+
+    /// Auction period in blocks used when a seller does not specify one, by tier level
+    #[pallet::storage]
+    #[pallet::getter(fn tier_default_period)]
+    pub(super) type TierDefaultPeriods<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, BlockNumberFor<T>, OptionQuery>;
+
+    /// Minimum number of distinct bidders for an auction to clear, by tier level
+    #[pallet::storage]
+    #[pallet::getter(fn tier_min_bidders)]
+    pub(super) type TierMinBidders<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+    /// Fills of settled partial-fill auctions, in price-time priority
+    #[pallet::storage]
+    #[pallet::getter(fn auction_fills)]
+    pub(super) type AuctionFills<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        BoundedVec<Fill<T::AccountId, T::Price, T::Quantity>, T::MaxFillsPerAuction>,
+        ValueQuery,
+    >;
+
+    /// Auctions that ended without a bid besides the seller's starting bid
+    /// Kept so the seller can relist them
+    #[pallet::storage]
+    #[pallet::getter(fn unsold_auctions)]
+    pub(super) type UnsoldAuctions<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, AuctionDataOf<T, I>, OptionQuery>;
+
+    /// Automatic relists that led to a live auction, from the auction's original listing
+    #[pallet::storage]
+    #[pallet::getter(fn relist_attempts)]
+    pub(super) type RelistAttempts<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, u32, ValueQuery>;
+
+    /// Track record of every account that traded, listed or bid
+    #[pallet::storage]
+    #[pallet::getter(fn reputation)]
+    pub(super) type Reputation<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, ReputationStats, ValueQuery>;
+
+    /// Settled sales of auctions with a delivery window, paid once the buyer confirms delivery
+    #[pallet::storage]
+    #[pallet::getter(fn delivery)]
+    pub(super) type Deliveries<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        Twox64Concat,
+        T::AccountId, // buyer
+        DeliveryOf<T, I>,
+        OptionQuery,
+    >;
+
+    /// Matched auctions waiting for their buyer to accept the match until `end_at`
+    #[pallet::storage]
+    #[pallet::getter(fn awaiting_acceptance)]
+    pub(super) type AwaitingAcceptance<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, AuctionDataOf<T, I>, OptionQuery>;
+
+    /// Matched auctions whose settlement is delayed by their seller until `end_at`
+    #[pallet::storage]
+    #[pallet::getter(fn awaiting_settlement)]
+    pub(super) type AwaitingSettlement<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, AuctionDataOf<T, I>, OptionQuery>;
+
+    /// Auctions of a tier awaiting acceptance and awaiting settlement, so the market status
+    /// is read without iterating either map
+    #[pallet::storage]
+    #[pallet::getter(fn tier_queue_depths)]
+    pub(super) type TierQueueDepths<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, (u32, u32), ValueQuery>;
+
+    /// Index delayed settlements by settlement block.
+    #[pallet::storage]
+    #[pallet::getter(fn settlement_queue)]
+    pub(super) type SettlementQueue<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        T::AuctionId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Order pairs by the auction of their buy leg
+    #[pallet::storage]
+    #[pallet::getter(fn order_pair)]
+    pub(super) type OrderPairs<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        OrderPair<T::AccountId, T::AuctionId>,
+        OptionQuery,
+    >;
+
+    /// Buy leg auction of paired sell leg auctions
+    #[pallet::storage]
+    #[pallet::getter(fn paired_sell_leg)]
+    pub(super) type PairedSellLegs<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, T::AuctionId, OptionQuery>;
+
+    /// Index matches waiting for acceptance by deadline.
+    #[pallet::storage]
+    #[pallet::getter(fn acceptance_deadline)]
+    pub(super) type AcceptanceDeadlines<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        T::AuctionId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Next buy order identifier
+    #[pallet::storage]
+    #[pallet::getter(fn buy_orders_index)]
+    pub(super) type BuyOrderIndex<T: Config<I>, I: 'static = ()> = StorageValue<_, u64, ValueQuery>;
+
+    /// Next recurring auction template identifier
+    #[pallet::storage]
+    #[pallet::getter(fn recurring_index)]
+    pub(super) type RecurringIndex<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, u32, ValueQuery>;
+
+    /// Recurring auction templates
+    #[pallet::storage]
+    #[pallet::getter(fn recurring_auction)]
+    pub(super) type RecurringAuctions<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, RecurringAuctionOf<T, I>, OptionQuery>;
+
+    /// Recurring auction templates of a seller
+    #[pallet::storage]
+    #[pallet::getter(fn recurring_of)]
+    pub(super) type RecurringOf<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<u32, T::MaxRecurringAuctions>,
+        ValueQuery,
+    >;
+
+    /// Index recurring auction templates by the block their next auction is listed
+    #[pallet::storage]
+    #[pallet::getter(fn recurring_schedule)]
+    pub(super) type RecurringSchedule<T: Config<I>, I: 'static = ()> =
+        StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Twox64Concat, u32, (), OptionQuery>;
+
+    /// Demand auctions of buyers, numbered from the same sequence as auctions
+    #[pallet::storage]
+    #[pallet::getter(fn demand_auction)]
+    pub(super) type DemandAuctions<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, DemandAuctionOf<T, I>, OptionQuery>;
+
+    /// Index demand auctions by end time
+    #[pallet::storage]
+    #[pallet::getter(fn demand_execution_queue)]
+    pub(super) type DemandExecutionQueue<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        T::AuctionId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Standing buy orders of a tier, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn buy_orders)]
+    pub(super) type BuyOrders<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        BoundedVec<BuyOrderOf<T, I>, T::MaxStandingBuyOrders>,
+        ValueQuery,
+    >;
+
+    /// Block from which a seller may list a quantity again after cancelling an auction with bids
+    #[pallet::storage]
+    #[pallet::getter(fn listing_cooldown)]
+    pub(super) type ListingCooldowns<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::Quantity,
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// Number of times an account won an auction it could not pay for and was skipped
+    #[pallet::storage]
+    #[pallet::getter(fn skipped_wins)]
+    pub(super) type SkippedWinners<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Committed hashes of sealed bids not revealed yet
+    #[pallet::storage]
+    #[pallet::getter(fn sealed_bid)]
+    pub(super) type SealedBids<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        Twox64Concat,
+        T::AccountId,
+        T::Hash,
+        OptionQuery,
+    >;
+
+    /// Highest price a leading bidder lets the pallet raise its bid to when challenged
+    /// Caps are readable on chain like any other state
+    #[pallet::storage]
+    #[pallet::getter(fn proxy_bid)]
+    pub(super) type ProxyBids<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        Twox64Concat,
+        T::AccountId,
+        T::Price,
+        OptionQuery,
+    >;
+
+    /// Price schedule of descending-price auctions, sold to the first bid reaching the ask
+    #[pallet::storage]
+    #[pallet::getter(fn price_decay)]
+    pub(super) type DutchAuctions<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        PriceDecay<T::Price, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Buy-it-now price of auctions a buyer may take whole at once
+    #[pallet::storage]
+    #[pallet::getter(fn buyout_price)]
+    pub(super) type BuyoutPrices<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, T::Price, OptionQuery>;
+
+    /// Asset the bids of an auction are paid in, `Currency` for auctions without one. Kept
+    /// after settlement, the fills and deliveries of the auction are paid in it
+    #[pallet::storage]
+    #[pallet::getter(fn payment_asset)]
+    pub(super) type AuctionAssets<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::AuctionId, AssetIdOf<T, I>, OptionQuery>;
+
+    /// Funds held for a bidder on an auction under `OnChainSettlement`, in the auction's
+    /// payment asset when it has one
+    #[pallet::storage]
+    #[pallet::getter(fn escrowed)]
+    pub(super) type Escrows<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        Twox64Concat,
+        T::AccountId,
+        BalanceOf<T, I>,
+        ValueQuery,
+    >;
+
+    /// Latest leading bid of every bidder of a live auction
+    #[pallet::storage]
+    #[pallet::getter(fn bid_of)]
+    pub(super) type Bids<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AuctionId,
+        Twox64Concat,
+        T::AccountId,
+        BidRecordOf<T, I>,
+        OptionQuery,
+    >;
+
+    /// Stores the most recent bids of participants and their outcomes
+    /// Oldest entry is dropped once `MaxBidHistory` is reached
+    #[pallet::storage]
+    #[pallet::getter(fn bid_history_of)]
+    pub(super) type BidHistory<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<BidHistoryEntry<T::AuctionId, BlockNumberFor<T>, T::Price>, T::MaxBidHistory>,
+        ValueQuery,
+    >;
+
+    /// Recent outcomes of an account, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn notifications_of)]
+    pub(super) type Notifications<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<Notification<T::AuctionId, BlockNumberFor<T>, T::Price>, T::MaxNotifications>,
+        ValueQuery,
+    >;
+
+    /// Market parameters set by governance in place of the runtime constants
+    #[pallet::storage]
+    #[pallet::getter(fn parameters)]
+    pub(super) type Parameters<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, MarketParameters<T::Price, BlockNumberFor<T>>, ValueQuery>;
+
+    /// Accounts notified of listings and clearings in a tier
+    #[pallet::storage]
+    #[pallet::getter(fn tier_subscribers)]
+    pub(super) type TierSubscribers<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        BoundedVec<T::AccountId, T::MaxTierSubscribers>,
+        ValueQuery,
+    >;
+
+    /// Most recent market events, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn market_activity)]
+    pub(super) type MarketActivity<T: Config<I>, I: 'static = ()> = StorageValue<
+        _,
+        BoundedVec<
+            Activity<T::AuctionId, BlockNumberFor<T>, T::Price, T::Quantity>,
+            T::MaxMarketActivity,
+        >,
+        ValueQuery,
+    >;
+
+    /// Quantity and price of the latest clearings of a tier, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn recent_clearings)]
+    pub(super) type RecentClearings<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        BoundedVec<(T::Quantity, T::Price), T::MaxRecentClearings>,
+        ValueQuery,
+    >;
+
+    /// Block, quantity and clearing price of the trades executed in a tier over the last
+    /// `HistoryDepth` blocks, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn trade_history)]
+    pub(super) type TradeHistory<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        BoundedVec<(BlockNumberFor<T>, T::Quantity, T::Price), T::MaxTradeHistory>,
+        ValueQuery,
+    >;
+
+    /// Cursor of the ongoing stepped migration, empty until the first step
+    #[pallet::storage]
+    #[pallet::getter(fn ongoing_migration)]
+    pub(super) type OngoingMigration<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BoundedVec<u8, ConstU32<MAX_CURSOR_LEN>>, OptionQuery>;
+
+    /// Execution counters of the last block that processed auctions
+    #[pallet::storage]
+    #[pallet::getter(fn last_execution_telemetry)]
+    pub(super) type LastExecutionTelemetry<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, ExecutionTelemetry<BlockNumberFor<T>, T::Quantity>, OptionQuery>;
+
+    /// First block whose due auctions may not all have been executed yet
+    #[pallet::storage]
+    #[pallet::getter(fn execution_cursor)]
+    pub(super) type ExecutionCursor<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+    /// Counters and settlements of the auctions cleared and executed so far in the current
+    /// block, taken by `on_finalize` so it never outlives the block
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub(super) type BlockExecution<T: Config<I>, I: 'static = ()> = StorageValue<
+        _,
+        (
+            ExecutionTelemetry<BlockNumberFor<T>, T::Quantity>,
+            Vec<SettlementResultOf<T, I>>,
+        ),
+        OptionQuery,
+    >;
+
+    /// Index auctions waiting for their start by start time
+    #[pallet::storage]
+    #[pallet::getter(fn pending_auction)]
+    pub(super) type PendingAuctions<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        T::AuctionId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Index auctions by end time.
+    #[pallet::storage]
+    #[pallet::getter(fn auction_execution_queue)]
+    pub(super) type AuctionsExecutionQueue<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        T::AuctionId,
+        (),
+        OptionQuery,
+    >;
+
+    /////////////////////
+    // Genesis config //
+    ////////////////////
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
         pub auction_index: T::AuctionId,
+        /// Lowest quantity of every tier above the first
+        pub tier_boundaries: Vec<T::Quantity>,
+        /// Registered participants with their role and whether they are KYC-verified
+        pub participants: Vec<(T::AccountId, PartyType, bool)>,
+        /// Open auctions as seller, quantity, starting price and period in blocks, sellers
+        /// must hold their deposits
+        pub auctions: Vec<(T::AccountId, T::Quantity, T::Price, BlockNumberFor<T>)>,
+    }
+
+    impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+        fn default() -> Self {
+            Self {
+                auction_index: Default::default(),
+                tier_boundaries: Vec::new(),
+                participants: Vec::new(),
+                auctions: Vec::new(),
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
+        fn build(&self) {
+            let initial_id = self.auction_index;
+            <AuctionIndex<T, I>>::put(initial_id);
+
+            if !self.tier_boundaries.is_empty() {
+                assert!(
+                    (self.tier_boundaries.len() as u32) < T::MaxTiers::get()
+                        && !self.tier_boundaries[0].is_zero()
+                        && self
+                            .tier_boundaries
+                            .windows(2)
+                            .all(|pair| pair[0] < pair[1]),
+                    "tier boundaries must be increasing, above zero and fewer than `MaxTiers`"
+                );
+                let boundaries: TierBoundariesOf<T, I> = self
+                    .tier_boundaries
+                    .clone()
+                    .try_into()
+                    .expect("boundaries are fewer than `MaxTiers`; qed");
+                TierConfig::<T, I>::put(boundaries);
+            }
+
+            for (who, role, kyc_verified) in self.participants.iter() {
+                Participants::<T, I>::insert(
+                    who,
+                    Participant {
+                        role: *role,
+                        kyc_verified: *kyc_verified,
+                        suspended: false,
+                        registered_at: Zero::zero(),
+                    },
+                );
+            }
+
+            // auctions are listed like `new` would, in the tiers set above
+            for (seller, quantity, starting_price, auction_period) in self.auctions.iter() {
+                let category = Pallet::<T, I>::categorize(*quantity);
+                Pallet::<T, I>::create_auction(
+                    seller.clone(),
+                    *quantity,
+                    *starting_price,
+                    *auction_period,
+                    None,
+                    TierMinBidders::<T, I>::get(category.level),
+                    None,
+                    Zero::zero(),
+                    None,
+                    None,
+                    DEFAULT_ZONE,
+                    None,
+                )
+                .expect("genesis auctions are valid and their sellers funded");
+            }
+        }
+    }
+
+    ///////////////////
+    // Pallet hooks //
+    //////////////////
+    #[pallet::extra_constants]
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        /// Unit of auction quantities
+        #[pallet::constant_name(QuantityUnit)]
+        pub fn quantity_unit() -> Vec<u8> {
+            b"kWh".to_vec()
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            // switch matching algorithms and roll leaderboards over at the start of an era
+            if (now % T::EraLength::get()).is_zero() {
+                Self::apply_pending_matching_algorithms();
+                Self::end_era(Self::current_era());
+            }
+
+            // scheduled auctions open for bids, recurring ones are listed again and demand
+            // auctions ending now sell to their lowest offer
+            let started = Self::start_pending_auctions(now);
+            let listed = Self::list_recurring_auctions(now);
+            let demanded = Self::execute_demand_auctions(now);
+
+            // uniform-price auctions of every tier clear together at each clearing interval,
+            // before any of them is due
+            let mut clearing_weight = Weight::zero();
+            if (now % T::ClearingInterval::get()).is_zero() {
+                let mut settlements: Vec<SettlementResultOf<T, I>> = Vec::new();
+                for tier in 1..=T::MaxTiers::get() {
+                    if !Self::tier_halted(tier) {
+                        let (read, cleared) = Self::clear_tier(tier, now);
+                        clearing_weight = clearing_weight
+                            .saturating_add(T::DbWeight::get().reads_writes(read.into(), 1))
+                            .saturating_add(T::WeightInfo::execute_auctions(cleared.len() as u32));
+                        settlements.extend(cleared);
+                    }
+                }
+                if !settlements.is_empty() {
+                    let mut telemetry = ExecutionTelemetry::default();
+                    for settlement in settlements.iter() {
+                        telemetry.executed += 1;
+                        telemetry.energy_cleared =
+                            telemetry.energy_cleared.saturating_add(settlement.quantity);
+                    }
+                    BlockExecution::<T, I>::put((telemetry, settlements));
+                }
+            }
+
+            // due auctions are executed and weighed in `on_idle`, only the rest of the end of
+            // the block is reserved
+            T::WeightInfo::execute_auctions(0)
+                .saturating_add(
+                    T::DbWeight::get()
+                        .reads_writes(3, 4)
+                        .saturating_mul(started.into()),
+                )
+                .saturating_add(
+                    T::WeightInfo::new()
+                        .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+                        .saturating_mul(listed.into()),
+                )
+                .saturating_add(
+                    T::DbWeight::get()
+                        .reads_writes(4, 4)
+                        .saturating_mul(demanded.into()),
+                )
+                .saturating_add(clearing_weight)
+        }
+
+        fn on_finalize(now: BlockNumberFor<T>) {
+            // auctions cleared and executed earlier in the block
+            let (mut telemetry, mut settlements) =
+                BlockExecution::<T, I>::take().unwrap_or_default();
+            telemetry.block = now;
+
+            // delayed settlements are due
+            let scheduled: Vec<T::AuctionId> = SettlementQueue::<T, I>::drain_prefix(now)
+                .map(|(auction_id, _)| auction_id)
+                .collect();
+            for auction_id in scheduled {
+                if let Some(auction_data) = Self::take_awaiting_settlement(auction_id) {
+                    // a buyer that can no longer pay falls to the next bidder
+                    let settled = match Self::complete_settlement(auction_data.clone(), now) {
+                        Ok(settlement) => Vec::from([settlement]),
+                        Err(_) => Self::fail_over(auction_data, now),
+                    };
+                    for settlement in settled {
+                        telemetry.executed += 1;
+                        telemetry.energy_cleared =
+                            telemetry.energy_cleared.saturating_add(settlement.quantity);
+                        settlements.push(settlement);
+                    }
+                }
+            }
+
+            // matches not accepted in time fall to the next bidder
+            let lapsed: Vec<T::AuctionId> = AcceptanceDeadlines::<T, I>::drain_prefix(now)
+                .map(|(auction_id, _)| auction_id)
+                .collect();
+            for auction_id in lapsed {
+                Self::lapse_acceptance(auction_id, now);
+            }
+
+            // record block summary when auctions were processed
+            if telemetry.executed + telemetry.deferred + telemetry.unmatched > 0 {
+                Self::deposit_event(Event::BlockExecutionSummary {
+                    block: now,
+                    executed: telemetry.executed,
+                    deferred: telemetry.deferred,
+                    unmatched: telemetry.unmatched,
+                    energy_cleared: telemetry.energy_cleared,
+                });
+                LastExecutionTelemetry::<T, I>::put(telemetry);
+            }
+
+            // commit to the block's settlements so they can be checked against the header
+            if !settlements.is_empty() {
+                let commitment = T::Hashing::hash_of(&settlements);
+                <frame_system::Pallet<T>>::deposit_log(DigestItem::Other(
+                    (SETTLEMENT_DIGEST_ID, commitment).encode(),
+                ));
+            }
+        }
+
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            // due auctions come first, a migration steps with what is left
+            let used = Self::execute_due_auctions(now, remaining_weight);
+            used.saturating_add(Self::step_migration(remaining_weight.saturating_sub(used)))
+        }
+
+        fn integrity_test() {
+            assert!(
+                !T::MaxAuctionDuration::get().is_zero(),
+                "`MaxAuctionDuration` must be greater than zero"
+            );
+            assert!(
+                T::MinAuctionPeriod::get() <= T::MaxAuctionPeriod::get()
+                    && T::MaxAuctionPeriod::get() <= T::MaxAuctionDuration::get(),
+                "`MinAuctionPeriod` must not exceed `MaxAuctionPeriod`, nor it `MaxAuctionDuration`"
+            );
+            assert!(
+                T::BlockTimeMillis::get() > 0,
+                "`BlockTimeMillis` must be greater than zero"
+            );
+            assert!(
+                T::MaxExecutionsPerBlock::get() > 0,
+                "`MaxExecutionsPerBlock` must be greater than zero, auctions would never execute"
+            );
+            assert!(
+                T::MaxAuctionsPerEndBlock::get() > 0,
+                "`MaxAuctionsPerEndBlock` must be greater than zero, no auction could be listed"
+            );
+            assert!(
+                T::MaxSellOrders::get() > 0 && T::MaxBuyOrders::get() > 0,
+                "`MaxSellOrders` and `MaxBuyOrders` must be greater than zero"
+            );
+            assert!(
+                T::MaxOpenAuctionsPerSeller::get() > 0 && T::MaxOpenBidsPerBuyer::get() > 0,
+                "`MaxOpenAuctionsPerSeller` and `MaxOpenBidsPerBuyer` must be greater than zero"
+            );
+            assert!(
+                T::MaxAuctionsPerTier::get() > 0,
+                "`MaxAuctionsPerTier` must be greater than zero, no auction could be listed"
+            );
+            assert!(
+                T::MaxMatchesPerClearing::get() > 0,
+                "`MaxMatchesPerClearing` must be greater than zero, no clearing could sell"
+            );
+            assert!(
+                T::MaxBidsPerAuction::get() > 0,
+                "`MaxBidsPerAuction` must be greater than zero, auctions would take no bids"
+            );
+            assert!(
+                T::MaxBidHistory::get() > 0,
+                "`MaxBidHistory` must be greater than zero"
+            );
+            assert!(
+                T::MaxTiers::get() > 0,
+                "`MaxTiers` must be greater than zero"
+            );
+            assert!(
+                !T::EraLength::get().is_zero(),
+                "`EraLength` must be greater than zero"
+            );
+            assert!(
+                !T::ClearingInterval::get().is_zero(),
+                "`ClearingInterval` must be greater than zero"
+            );
+            assert!(
+                T::MaxHistogramBuckets::get() > 0 && !T::HistogramBucketWidth::get().is_zero(),
+                "bid histograms need at least one bucket of non-zero width"
+            );
+            assert!(
+                T::FeeRate::get() < Permill::one()
+                    && T::CommissionRate::get() < Perbill::one()
+                    && T::ReferralShare::get() < Perbill::one(),
+                "`FeeRate`, `CommissionRate` and `ReferralShare` must be below 100%"
+            );
+            // tiers start at zero quantity, so the threshold of the second must be above it
+            assert!(
+                !T::TierThreshold::get().is_zero(),
+                "tier thresholds must be strictly increasing, `TierThreshold` must be greater than zero"
+            );
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+            Self::do_try_state()
+        }
+    }
+
+    //////////////////////
+    // Runtime events  //
+    /////////////////////
+    // runtime event for important runtime actions
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config<I>, I: 'static = ()> {
+        AuctionCreated {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+            metadata: Option<MetadataOf<T, I>>,
+            auction_category: Tier,
+            end_at: BlockNumberFor<T>,
+        },
+
+        AuctionBidAdded {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            bid: BidOf<T, I>,
+            auction_category: Tier,
+            bid_count: u32,
+            end_at: BlockNumberFor<T>,
+        },
+
+        AuctionMatched {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+            highest_bid: BidOf<T, I>,
+            matched_at: BlockNumberFor<T>,
+        },
+
+        AuctionExecuted {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            buyer_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+            highest_bid: T::Price,
+            clearing_price: T::Price,
+            executed_at: BlockNumberFor<T>,
+            auction_category: Tier,
+            bid_count: u32,
+            end_at: BlockNumberFor<T>,
+            price_basis: PriceBasis,
+        },
+
+        AuctionCanceled {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+        },
+
+        AuctionExtended {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            previous_end_at: BlockNumberFor<T>,
+            end_at: BlockNumberFor<T>,
+        },
+
+        AuctionExecutionDeferred {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            reason: DeferralReason,
+            execute_at: BlockNumberFor<T>,
+        },
+
+        AuctionExpiredUnsold {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+            expired_at: BlockNumberFor<T>,
+        },
+
+        AuctionInsufficientBidders {
+            auction_id: T::AuctionId,
+            bidders: u32,
+            min_bidders: u32,
+        },
+
+        AuctionUnsold {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            highest_bid: T::Price,
+            reserve_price: T::Price,
+            relisted_as: Option<T::AuctionId>,
+        },
+
+        AuctionRelisted {
+            previous_auction_id: T::AuctionId,
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+        },
+
+        TierBoundariesSet {
+            boundaries: TierBoundariesOf<T, I>,
+        },
+
+        TierDefaultPeriodSet {
+            tier: u32,
+            period: Option<BlockNumberFor<T>>,
+        },
+
+        TierMinBiddersSet {
+            tier: u32,
+            min_bidders: u32,
+        },
+
+        BlockExecutionSummary {
+            block: BlockNumberFor<T>,
+            executed: u32,
+            deferred: u32,
+            unmatched: u32,
+            energy_cleared: T::Quantity,
+        },
+
+        MigrationStepped {
+            weight: Weight,
+        },
+
+        MigrationCompleted,
+
+        MigrationFailed,
+
+        BidRefunded {
+            auction_id: T::AuctionId,
+            bidder: T::AccountId,
+            amount: T::Price,
+        },
+
+        CommissionCharged {
+            auction_id: T::AuctionId,
+            payer: T::AccountId,
+            amount: BalanceOf<T, I>,
+        },
+
+        FeeCharged {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            amount: BalanceOf<T, I>,
+        },
+
+        ReferrerRegistered {
+            account: T::AccountId,
+            referrer: T::AccountId,
+        },
+
+        ReferralRewardAccrued {
+            referrer: T::AccountId,
+            account: T::AccountId,
+            amount: BalanceOf<T, I>,
+        },
+
+        ReferralRewardsClaimed {
+            referrer: T::AccountId,
+            amount: BalanceOf<T, I>,
+        },
+
+        AccountSponsored {
+            sponsor: T::AccountId,
+            account: T::AccountId,
+            deposit_limit: BalanceOf<T, I>,
+            call_limit: u32,
+        },
+
+        SponsorshipEnded {
+            sponsor: T::AccountId,
+            account: T::AccountId,
+        },
+
+        EraEnded {
+            era: u32,
+        },
+
+        TierHaltSet {
+            tier: u32,
+            halted: bool,
+        },
+
+        AuctionTransferred {
+            auction_id: T::AuctionId,
+            previous_seller_id: T::AccountId,
+            seller_id: T::AccountId,
+        },
+
+        BidToppedUp {
+            auction_id: T::AuctionId,
+            bidder: T::AccountId,
+            bid: T::Price,
+        },
+
+        MarketBuyExecuted {
+            buyer_id: T::AccountId,
+            tier: u32,
+            energy_quantity: T::Quantity,
+            total_price: T::Price,
+            fills: u32,
+        },
+
+        MarketSellExecuted {
+            seller_id: T::AccountId,
+            tier: u32,
+            energy_quantity: T::Quantity,
+            total_price: T::Price,
+            fills: u32,
+        },
+
+        ForceExecuted {
+            auction_id: T::AuctionId,
+            matched: bool,
+        },
+
+        AuctionAwaitingAcceptance {
+            auction_id: T::AuctionId,
+            buyer_id: T::AccountId,
+            accept_by: BlockNumberFor<T>,
+        },
+
+        AcceptanceLapsed {
+            auction_id: T::AuctionId,
+            buyer_id: T::AccountId,
+        },
+
+        WinnerSkipped {
+            auction_id: T::AuctionId,
+            buyer_id: T::AccountId,
+        },
+
+        OrganizationCreated {
+            organization: u32,
+            admin: T::AccountId,
+        },
+
+        OrganizationMemberSet {
+            organization: u32,
+            member: T::AccountId,
+            is_member: bool,
+        },
+
+        OrganizationLimitsSet {
+            organization: u32,
+            bid_limit: T::Price,
+            budget: T::Price,
+        },
+
+        OrganizationBidPlaced {
+            organization: u32,
+            member: T::AccountId,
+            auction_id: T::AuctionId,
+            bid: T::Price,
+        },
+
+        OrganizationBudgetSpent {
+            organization: u32,
+            member: T::AccountId,
+            auction_id: T::AuctionId,
+            amount: T::Price,
+        },
+
+        ListingCooldownStarted {
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            until: BlockNumberFor<T>,
+        },
+
+        TierSubscribed {
+            tier: u32,
+            account: T::AccountId,
+        },
+
+        TierUnsubscribed {
+            tier: u32,
+            account: T::AccountId,
+        },
+
+        ParameterSet {
+            parameter: MarketParameter<T::Price, BlockNumberFor<T>>,
+        },
+
+        SettlementScheduled {
+            auction_id: T::AuctionId,
+            buyer_id: T::AccountId,
+            settle_at: BlockNumberFor<T>,
+        },
+
+        OrderPairPlaced {
+            owner: T::AccountId,
+            buy_auction_id: T::AuctionId,
+            sell_auction_id: T::AuctionId,
+        },
+
+        OrderPairSettled {
+            owner: T::AccountId,
+            buy_auction_id: T::AuctionId,
+            sell_auction_id: T::AuctionId,
+        },
+
+        OrderPairRejected {
+            owner: T::AccountId,
+            buy_auction_id: T::AuctionId,
+            sell_auction_id: T::AuctionId,
+        },
+
+        BuyOrderPlaced {
+            order_id: u64,
+            buyer_id: T::AccountId,
+            tier: u32,
+            energy_quantity: T::Quantity,
+            max_unit_price: T::Price,
+        },
+
+        BuyOrderFilled {
+            order_id: u64,
+            buyer_id: T::AccountId,
+            auction_id: T::AuctionId,
+            energy_quantity: T::Quantity,
+            price: T::Price,
+        },
+
+        BuyOrderCancelled {
+            order_id: u64,
+            buyer_id: T::AccountId,
+        },
+
+        TierCleared {
+            tier: u32,
+            zone: ZoneId,
+            clearing_price: T::Price, // per unit of energy
+            matched_volume: T::Quantity,
+            // auction, buyer, quantity and total price of every sale
+            results: BoundedVec<
+                (T::AuctionId, T::AccountId, T::Quantity, T::Price),
+                T::MaxMatchesPerClearing,
+            >,
+        },
+
+        ClearingPostponed {
+            tier: u32,
+            zone: ZoneId,
+        },
+
+        BidCanceled {
+            auction_id: T::AuctionId,
+            bidder: T::AccountId,
+            amount: T::Price,
+        },
+
+        BidCommitted {
+            auction_id: T::AuctionId,
+            bidder: T::AccountId,
+        },
+
+        MatchingAlgorithmScheduled {
+            tier: u32,
+            algorithm: MatchingAlgorithm,
+            effective_era: u32,
+        },
+
+        MatchingAlgorithmChanged {
+            tier: u32,
+            algorithm: MatchingAlgorithm,
+        },
+
+        AuctionDepositSlashed {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            amount: BalanceOf<T, I>,
+            unsold_streak: u32,
+        },
+
+        AuctionStatusChanged {
+            auction_id: T::AuctionId,
+            previous_status: AuctionStatus,
+            status: AuctionStatus,
+        },
+
+        RecurringAuctionCreated {
+            recurring_id: u32,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+            interval: BlockNumberFor<T>,
+        },
+
+        RecurringAuctionListed {
+            recurring_id: u32,
+            auction_id: T::AuctionId,
+        },
+
+        RecurringAuctionSkipped {
+            recurring_id: u32,
+            error: DispatchError,
+        },
+
+        RecurringAuctionCancelled {
+            recurring_id: u32,
+            seller_id: T::AccountId,
+        },
+
+        ProxyBidRaised {
+            auction_id: T::AuctionId,
+            bidder: T::AccountId,
+            bid: T::Price,
+        },
+
+        ParticipantRegistered {
+            who: T::AccountId,
+            role: PartyType,
+        },
+
+        ParticipantStatusSet {
+            who: T::AccountId,
+            kyc_verified: bool,
+            suspended: bool,
+        },
+
+        DemandCreated {
+            demand_id: T::AuctionId,
+            buyer_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            max_price: T::Price,
+            end_at: BlockNumberFor<T>,
+        },
+
+        DemandOffered {
+            demand_id: T::AuctionId,
+            seller_id: T::AccountId,
+            price: T::Price,
+        },
+
+        DemandCancelled {
+            demand_id: T::AuctionId,
+            buyer_id: T::AccountId,
+        },
+
+        DemandExecuted {
+            demand_id: T::AuctionId,
+            buyer_id: T::AccountId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            clearing_price: T::Price,
+            executed_at: BlockNumberFor<T>,
+        },
+
+        DemandExpired {
+            demand_id: T::AuctionId,
+            buyer_id: T::AccountId,
+        },
+
+        AuctionUpdated {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+            previous_end_at: BlockNumberFor<T>,
+            end_at: BlockNumberFor<T>,
+        },
+
+        BatchBidFailed {
+            buyer_id: T::AccountId,
+            auction_id: T::AuctionId,
+            error: DispatchError,
+        },
+
+        BatchBidCompleted {
+            buyer_id: T::AccountId,
+            placed: u32,
+            failed: u32,
+        },
+
+        AuctionEndAdjusted {
+            auction_id: T::AuctionId,
+            requested_end_at: BlockNumberFor<T>,
+            end_at: BlockNumberFor<T>,
+        },
+
+        AuctionClosedWithoutMatch {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            energy_quantity: T::Quantity,
+            auction_category: Tier,
+            bid_count: u32,
+            end_at: BlockNumberFor<T>,
+            closed_at: BlockNumberFor<T>,
+        },
+
+        ZoneLinkSet {
+            zone: ZoneId,
+            neighbour: ZoneId,
+            loss: Option<Permill>,
+        },
+
+        DeliveryAwaited {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            buyer_id: T::AccountId,
+            delivery_start: BlockNumberFor<T>,
+            delivery_end: BlockNumberFor<T>,
+        },
+
+        DeliveryConfirmed {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            buyer_id: T::AccountId,
+            price: T::Price,
+        },
+
+        DeliveryDisputed {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            buyer_id: T::AccountId,
+        },
+
+        DeliveryResolved {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            buyer_id: T::AccountId,
+            refunded: bool,
+        },
+
+        CapacityBonded {
+            seller_id: T::AccountId,
+            amount: BalanceOf<T, I>,
+            bonded: BalanceOf<T, I>,
+        },
+
+        SellerSlashed {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            buyer_id: T::AccountId,
+            amount: BalanceOf<T, I>,
+        },
+
+        MarketPaused,
+
+        MarketResumed,
+
+        ForceRemoved {
+            auction_id: T::AuctionId,
+            seller_id: T::AccountId,
+            status: AuctionStatus,
+        },
+    }
+
+    //////////////////////
+    // Pallet errors   //
+    /////////////////////
+    // Errors inform users that something went wrong.
+    #[pallet::error]
+    pub enum Error<T, I = ()> {
+        AuctionDoesNotExist,
+
+        AuctionIsOver,
+
+        InsuffficientAttachedDeposit,
+
+        NotAuctionOwner,
+
+        AuctionDurationTooLong,
+
+        UnsoldAuctionDoesNotExist,
+
+        NoDefaultAuctionPeriod,
+
+        SelfReferral,
+
+        ReferrerAlreadySet,
+
+        ReferralCycle,
+
+        NoReferralRewards,
+
+        SelfSponsorship,
+
+        SponsorshipDoesNotExist,
+
+        NotSponsor,
+
+        InvalidSignature,
+
+        InvalidNonce,
+
+        BidDoesNotExist,
+
+        AuctionHasBids,
+
+        TierHalted,
+
+        InvalidTier,
+
+        NoMarketLiquidity,
+
+        MatchDoesNotExist,
+
+        NotMatchedBuyer,
+
+        OrganizationDoesNotExist,
+
+        NotOrganizationAdmin,
+
+        AlreadyOrganizationMember,
+
+        NotOrganizationMember,
+
+        OrganizationBidLimitExceeded,
+
+        OrganizationBudgetExceeded,
+
+        ListingCooldown,
+
+        AlreadySubscribed,
+
+        NotSubscribed,
+
+        TooManySubscribers,
+
+        SettlementLagTooLong,
+
+        AuctionAlreadyPaired,
+
+        PairLegsOutOfOrder,
+
+        PairBidNotLeading,
+
+        TooManyBuyOrders,
+
+        TooManyBids,
+
+        BuyOrderDoesNotExist,
+
+        NotBuyOrderOwner,
+
+        NotPartialFill,
+
+        InvalidBidQuantity,
+
+        BidBelowReserve,
+
+        SealedBidAuction,
+
+        NotSealedBid,
+
+        CommitPhaseOver,
+
+        NotRevealPhase,
+
+        CommitmentDoesNotExist,
+
+        InvalidReveal,
+
+        PeriodShorterThanReveal,
+
+        InvalidPriceDecay,
+
+        BidBelowAsk,
+
+        InsufficientFunds,
+
+        UniformPriceAuction,
+
+        InvalidBuyoutPrice,
+
+        NoBuyoutPrice,
+
+        InvalidTierBoundaries,
+
+        BidTooLow,
+
+        AuctionNotStarted,
+
+        InvalidRecurrenceInterval,
+
+        TooManyRecurringAuctions,
+
+        RecurringAuctionDoesNotExist,
+
+        ProxyBidUnsupported,
+
+        NotRegistered,
+
+        ParticipantSuspended,
+
+        KycRequired,
+
+        RoleNotAllowed,
+
+        DemandDoesNotExist,
+
+        OfferTooHigh,
+
+        OfferOnOwnDemand,
+
+        AuctionPeriodTooShort,
+
+        AuctionPeriodTooLong,
+
+        InvalidQuantity,
+
+        PriceOverflow,
+
+        TooManyOpenAuctions,
+
+        TooManyOpenBids,
+
+        InvalidZoneLink,
+
+        InvalidDeliveryWindow,
+
+        DeliveryDoesNotExist,
+
+        DeliveryUnderDispute,
+
+        ReputationTooLow,
+
+        MarketPaused,
+
+        TierFull,
+
+        BidNotLeading,
+
+        AlreadySponsored,
+
+        BidPayloadExpired,
+
+        ProceedsBelowMinimum,
+
+        UnknownAsset,
+
+        AssetPaymentUnsupported,
+
+        InvalidParameter,
+    }
+
+    ///////////////////////////
+    // Pallet extrinsics    //
+    //////////////////////////
+    #[pallet::call]
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::new())]
+        pub fn new(
+            origin: OriginFor<T>,
+            energy_quantity: u128, // in KWH
+            starting_price: u128,  // in parachain native token
+            auction_period: u16,   // in minutes, 0 for the tier default
+            options: ListingOptionsOf<T, I>,
+        ) -> DispatchResultWithPostInfo {
+            // Check that the extrinsic was signed by seller or return error.
+            let seller = ensure_signed(origin)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&seller)?;
+            let ListingOptions {
+                metadata,
+                min_bidders,
+                match_rule,
+                settlement_lag,
+                reserve_price,
+                buyout_price,
+                start_at,
+                zone,
+                delivery_window,
+                payment_asset,
+            } = options;
+
+            // Calculate auction period
+            let energy_quantity = T::Quantity::from(energy_quantity);
+            let category = Self::categorize(energy_quantity);
+            let auction_period_in_block_number =
+                Self::auction_period_for(auction_period, &category)?;
+
+            // Seller may require more bidders than the tier minimum
+            let min_bidders =
+                min_bidders.unwrap_or_else(|| TierMinBidders::<T, I>::get(category.level));
+
+            // Settlement may trail bidding to give delivery logistics lead time
+            let settlement_lag = settlement_lag.unwrap_or_else(Zero::zero);
+            ensure!(
+                settlement_lag <= T::MaxAuctionDuration::get(),
+                Error::<T, I>::SettlementLagTooLong
+            );
+
+            // buying out never pays the seller less than it asks
+            if let Some(buyout_price) = buyout_price {
+                Self::ensure_price_fits(T::Price::from(buyout_price))?;
+                ensure!(
+                    buyout_price >= starting_price
+                        && reserve_price
+                            .as_ref()
+                            .map_or(true, |reserve| buyout_price >= reserve.price),
+                    Error::<T, I>::InvalidBuyoutPrice
+                );
+            }
+
+            let auction_id = Self::create_auction(
+                seller.clone(),
+                energy_quantity,
+                T::Price::from(starting_price),
+                auction_period_in_block_number,
+                metadata,
+                min_bidders,
+                match_rule,
+                settlement_lag,
+                reserve_price.map(|reserve| ReservePrice {
+                    price: T::Price::from(reserve.price),
+                    relist: reserve.relist,
+                }),
+                start_at,
+                zone.unwrap_or(DEFAULT_ZONE),
+                delivery_window,
+                payment_asset,
+            )?;
+
+            // a crossing standing buy order may have taken it already
+            if let Some(buyout_price) = buyout_price {
+                if Auctions::<T, I>::contains_key(auction_id) {
+                    BuyoutPrices::<T, I>::insert(auction_id, T::Price::from(buyout_price));
+                }
+            }
+
+            Ok(Self::sponsored_call(&seller).into())
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::cancel(T::MaxBidsPerAuction::get()))]
+        pub fn cancel(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            // Check that the extrinsic was signed by seller or return error.
+            let seller = ensure_signed(origin)?;
+
+            // Check auction is exist
+            ensure!(
+                Auctions::<T, I>::contains_key(auction_id),
+                Error::<T, I>::AuctionDoesNotExist
+            );
+
+            // Get auction from global auction
+            let auction_data = Auctions::<T, I>::get(auction_id).expect("data of auction");
+
+            ensure!(
+                auction_data.seller_id == seller,
+                Error::<T, I>::NotAuctionOwner
+            );
+
+            // Check auction is live or yet to start
+            ensure!(
+                matches!(
+                    auction_data.auction_status,
+                    AuctionStatus::Open | AuctionStatus::Pending
+                ),
+                Error::<T, I>::AuctionIsOver
+            );
+
+            // cancelling after demand was revealed delays an identical listing
+            if auction_data.bid_count > 0 {
+                Self::start_listing_cooldown(&auction_data);
+            }
+            Self::note_reputation(&seller, |stats| {
+                stats.cancellations = stats.cancellations.saturating_add(1)
+            });
+
+            Self::cancel_auction(auction_data);
+
+            Ok(())
+        }
+
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::bid(T::MaxBidsPerAuction::get()))]
+        pub fn bid(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            bid: u128,
+            memo: Option<MemoOf<T, I>>,
+        ) -> DispatchResultWithPostInfo {
+            // Check that the extrinsic was signed by buyer or return error.
+            let buyer_id = ensure_signed(origin)?;
+
+            Self::do_bid(buyer_id.clone(), auction_id, bid, memo)?;
+
+            Ok(Self::sponsored_call(&buyer_id).into())
+        }
+
+        /// Extend the end of an open auction by `extension` blocks.
+        ///
+        /// Only the seller may extend, and the total duration is bounded by `MaxAuctionDuration`.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::extend_auction())]
+        pub fn extend_auction(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            extension: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            // Check that the extrinsic was signed by seller or return error.
+            let seller = ensure_signed(origin)?;
+
+            // Get auction from global auction
+            let mut auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+
+            ensure!(
+                auction_data.seller_id == seller,
+                Error::<T, I>::NotAuctionOwner
+            );
+
+            // Check auction is live
+            ensure!(
+                matches!(auction_data.auction_status, AuctionStatus::Open),
+                Error::<T, I>::AuctionIsOver
+            );
+
+            // Re-key auction in execution queue, in the first block from the new end with room
+            let previous_end_at = auction_data.end_at;
+            let end_at = Self::requeue_auction(
+                auction_id,
+                previous_end_at,
+                previous_end_at.saturating_add(extension),
+            )?;
+
+            // Ensure total duration stays within bounds
+            ensure!(
+                end_at.saturating_sub(auction_data.start_at) <= T::MaxAuctionDuration::get(),
+                Error::<T, I>::AuctionDurationTooLong
+            );
+
+            // Update auction
+            auction_data.end_at = end_at;
+            auction_data.auction_period = end_at.saturating_sub(auction_data.start_at);
+            Self::sync_seller_auction(&auction_data);
+            Self::sync_summary(&auction_data);
+            Auctions::<T, I>::insert(auction_id, auction_data);
+            Self::push_sell_leg(auction_id, end_at);
+
+            // Emit an event that the auction was extended.
+            Self::deposit_event(Event::AuctionExtended {
+                auction_id,
+                seller_id: seller,
+                previous_end_at,
+                end_at,
+            });
+
+            Ok(())
+        }
+
+        /// Relist an auction that expired unsold with a new period and reserve.
+        ///
+        /// Quantity, category, metadata and starting price are reused from the expired auction,
+        /// `new_reserve` replaces its reserve price.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::relist())]
+        pub fn relist(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            new_period: u16,   // in minutes, 0 for the tier default
+            new_reserve: u128, // in parachain native token
+        ) -> DispatchResult {
+            // Check that the extrinsic was signed by seller or return error.
+            let seller = ensure_signed(origin)?;
+
+            // Get expired auction
+            let auction_data = UnsoldAuctions::<T, I>::get(auction_id)
+                .ok_or(Error::<T, I>::UnsoldAuctionDoesNotExist)?;
+
+            ensure!(
+                auction_data.seller_id == seller,
+                Error::<T, I>::NotAuctionOwner
+            );
+
+            UnsoldAuctions::<T, I>::remove(auction_id);
+            Self::release_storage_deposit(&auction_data);
+            Self::release_auction_deposit(&auction_data);
+
+            // Create fresh auction from archived data, the asset stays with the archived auction
+            // whose fills may still await delivery
+            let auction_period =
+                Self::auction_period_for(new_period, &auction_data.auction_category)?;
+            let delivery_window = Self::relisted_window(&auction_data, auction_period);
+            let reserve_price = ReservePrice {
+                price: T::Price::from(new_reserve),
+                relist: auction_data
+                    .reserve_price
+                    .map_or(false, |reserve| reserve.relist),
+            };
+            let new_auction_id = Self::create_auction(
+                seller.clone(),
+                auction_data.quantity,
+                auction_data.starting_bid.bid,
+                auction_period,
+                auction_data.metadata,
+                auction_data.min_bidders,
+                auction_data.match_rule,
+                auction_data.settlement_lag,
+                Some(reserve_price),
+                None,
+                auction_data.zone,
+                delivery_window,
+                AuctionAssets::<T, I>::get(auction_id),
+            )?;
+
+            // Emit an event that the auction was relisted.
+            Self::deposit_event(Event::AuctionRelisted {
+                previous_auction_id: auction_id,
+                auction_id: new_auction_id,
+                seller_id: seller,
+            });
+
+            Ok(())
+        }
+
+        /// Set or clear the default auction period in blocks of a tier.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_tier_default_period())]
+        pub fn set_tier_default_period(
+            origin: OriginFor<T>,
+            tier: u32,
+            period: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_tier(tier)?;
+
+            match period {
+                Some(period) => TierDefaultPeriods::<T, I>::insert(tier, period),
+                None => TierDefaultPeriods::<T, I>::remove(tier),
+            }
+
+            Self::deposit_event(Event::TierDefaultPeriodSet { tier, period });
+
+            Ok(())
+        }
+
+        /// Set the minimum number of distinct bidders required for auctions of a tier to clear.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::set_tier_min_bidders())]
+        pub fn set_tier_min_bidders(
+            origin: OriginFor<T>,
+            tier: u32,
+            min_bidders: u32,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_tier(tier)?;
+
+            TierMinBidders::<T, I>::insert(tier, min_bidders);
+
+            Self::deposit_event(Event::TierMinBiddersSet { tier, min_bidders });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::register_referrer())]
+        pub fn register_referrer(origin: OriginFor<T>, referrer: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            // a participant can not refer themselves, directly or through their referrer
+            ensure!(who != referrer, Error::<T, I>::SelfReferral);
+            ensure!(
+                !Referrers::<T, I>::contains_key(&who),
+                Error::<T, I>::ReferrerAlreadySet
+            );
+            ensure!(
+                Referrers::<T, I>::get(&referrer).as_ref() != Some(&who),
+                Error::<T, I>::ReferralCycle
+            );
+
+            Referrers::<T, I>::insert(&who, &referrer);
+
+            Self::deposit_event(Event::ReferrerRegistered {
+                account: who,
+                referrer,
+            });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::claim_referral_rewards())]
+        pub fn claim_referral_rewards(origin: OriginFor<T>) -> DispatchResult {
+            let referrer = ensure_signed(origin)?;
+
+            let amount = ReferralRewards::<T, I>::get(&referrer);
+            ensure!(!amount.is_zero(), Error::<T, I>::NoReferralRewards);
+
+            T::Currency::transfer(
+                &Self::account_id(),
+                &referrer,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            ReferralRewards::<T, I>::remove(&referrer);
+
+            Self::deposit_event(Event::ReferralRewardsClaimed { referrer, amount });
+
+            Ok(())
+        }
+
+        /// Cover the storage deposits and fees of `account` up to `deposit_limit` and
+        /// `call_limit` per era.
+        ///
+        /// Only participants made `Sponsor` by `register_sponsor` may sponsor, and an account has
+        /// at most one sponsor; its own sponsor may call again to change the limits.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::sponsor())]
+        pub fn sponsor(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            deposit_limit: BalanceOf<T, I>, // storage deposits covered per era
+            call_limit: u32,                // fee-less calls per era
+        ) -> DispatchResult {
+            let sponsor = ensure_signed(origin)?;
+
+            ensure!(sponsor != account, Error::<T, I>::SelfSponsorship);
+
+            // sponsors pay for others, they are registered for it whatever the market requires
+            let participant =
+                Participants::<T, I>::get(&sponsor).ok_or(Error::<T, I>::NotRegistered)?;
+            ensure!(!participant.suspended, Error::<T, I>::ParticipantSuspended);
+            ensure!(
+                participant.role.allows(PartyType::Sponsor),
+                Error::<T, I>::RoleNotAllowed
+            );
+            ensure!(
+                Sponsorships::<T, I>::get(&account)
+                    .map_or(true, |existing| existing.sponsor == sponsor),
+                Error::<T, I>::AlreadySponsored
+            );
+
+            Sponsorships::<T, I>::insert(
+                &account,
+                Sponsorship {
+                    sponsor: sponsor.clone(),
+                    deposit_limit,
+                    call_limit,
+                    era: Self::current_era(),
+                    deposit_used: Zero::zero(),
+                    calls_used: 0,
+                },
+            );
+
+            Self::deposit_event(Event::AccountSponsored {
+                sponsor,
+                account,
+                deposit_limit,
+                call_limit,
+            });
+
+            Ok(())
+        }
+
+        /// End the sponsorship of `account`, called by its sponsor or by the account itself.
+        ///
+        /// Deposits the sponsor already holds are released with their auctions.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::end_sponsorship())]
+        pub fn end_sponsorship(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let sponsorship = Sponsorships::<T, I>::get(&account)
+                .ok_or(Error::<T, I>::SponsorshipDoesNotExist)?;
+
+            // either side may end a sponsorship
+            ensure!(
+                who == sponsorship.sponsor || who == account,
+                Error::<T, I>::NotSponsor
+            );
+
+            // deposits already held from the sponsor are released with their auctions
+            Sponsorships::<T, I>::remove(&account);
+
+            Self::deposit_event(Event::SponsorshipEnded {
+                sponsor: sponsorship.sponsor,
+                account,
+            });
+
+            Ok(())
+        }
+
+        /// Relay a bid signed off-chain by `payload.bidder`, for devices without chain access.
+        ///
+        /// The bidder signs `bid_signing_payload(payload)`, binding the bid to this chain and
+        /// pallet instance. The payload carries the bidder's next nonce, so a relayed bid cannot
+        /// be replayed, and is rejected after `payload.expires_at`.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::bid_signed(T::MaxBidsPerAuction::get()))]
+        pub fn bid_signed(
+            origin: OriginFor<T>,
+            payload: BidPayloadOf<T, I>,
+            signature: T::OffchainSignature,
+        ) -> DispatchResult {
+            // Relayer pays for the bid of a device without chain access
+            ensure_signed(origin)?;
+
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() <= payload.expires_at,
+                Error::<T, I>::BidPayloadExpired
+            );
+            ensure!(
+                signature.verify(&Self::bid_signing_payload(&payload)[..], &payload.bidder),
+                Error::<T, I>::InvalidSignature
+            );
+            ensure!(
+                payload.nonce == BidNonces::<T, I>::get(&payload.bidder),
+                Error::<T, I>::InvalidNonce
+            );
+            BidNonces::<T, I>::mutate(&payload.bidder, |nonce| *nonce += 1);
+
+            Self::do_bid(
+                payload.bidder,
+                payload.auction_id,
+                payload.bid,
+                payload.memo,
+            )
+        }
+
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_tier_matching_algorithm())]
+        pub fn set_tier_matching_algorithm(
+            origin: OriginFor<T>,
+            tier: u32,
+            algorithm: MatchingAlgorithm,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_tier(tier)?;
+
+            // auctions of the running era keep clearing with the current algorithm
+            PendingMatchingAlgorithms::<T, I>::insert(tier, algorithm);
+
+            Self::deposit_event(Event::MatchingAlgorithmScheduled {
+                tier,
+                algorithm,
+                effective_era: Self::current_era().saturating_add(1),
+            });
+
+            Ok(())
+        }
+
+        /// Raise the caller's bid on `auction_id` by `amount`.
+        ///
+        /// The raised total is placed like a new bid, under the same checks and effects.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::top_up_bid(T::MaxBidsPerAuction::get()))]
+        pub fn top_up_bid(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            amount: u128, // added to the bidder's current bid
+        ) -> DispatchResult {
+            let bidder = ensure_signed(origin)?;
+
+            let record =
+                Bids::<T, I>::get(auction_id, &bidder).ok_or(Error::<T, I>::BidDoesNotExist)?;
+            let bid = record.bid.saturating_add(T::Price::from(amount));
+            Self::do_bid(
+                bidder.clone(),
+                auction_id,
+                bid.unique_saturated_into(),
+                record.memo,
+            )?;
+
+            Self::deposit_event(Event::BidToppedUp {
+                auction_id,
+                bidder,
+                bid,
+            });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::transfer_auction())]
+        pub fn transfer_auction(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            new_seller: T::AccountId,
+        ) -> DispatchResult {
+            // Check that the extrinsic was signed by seller or return error.
+            let seller = ensure_signed(origin)?;
+
+            let mut auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+
+            ensure!(
+                auction_data.seller_id == seller,
+                Error::<T, I>::NotAuctionOwner
+            );
+            ensure!(
+                matches!(auction_data.auction_status, AuctionStatus::Open),
+                Error::<T, I>::AuctionIsOver
+            );
+
+            // bidders committed to the current seller
+            ensure!(auction_data.bid_count == 0, Error::<T, I>::AuctionHasBids);
+            // the owner of a pair keeps both of its legs
+            ensure!(
+                !OrderPairs::<T, I>::contains_key(auction_id)
+                    && !PairedSellLegs::<T, I>::contains_key(auction_id),
+                Error::<T, I>::AuctionAlreadyPaired
+            );
+
+            // the new seller must be allowed to list
+            Self::ensure_participant(&new_seller, PartyType::Seller)?;
+            Self::ensure_reputation(&new_seller)?;
+            Self::ensure_market_open()?;
+
+            // deposits move to the new seller
+            Self::release_storage_deposit(&auction_data);
+            Self::release_auction_deposit(&auction_data);
+            SellOrdersOf::<T, I>::mutate_exists(&seller, |maybe_orders| {
+                Self::forget_order(maybe_orders, auction_id)
+            });
+            Self::release_auction_slot(&seller);
+            Self::open_auction_slot(&new_seller)?;
+
+            // seller holds the starting bid
+            auction_data.seller_id = new_seller.clone();
+            auction_data.starting_bid.bidder = new_seller.clone();
+            auction_data.highest_bid.bidder = new_seller.clone();
+
+            Self::update_storage_deposit(&auction_data)?;
+            Self::hold_auction_deposit(&auction_data)?;
+            SellOrdersOf::<T, I>::mutate(&new_seller, |orders| {
+                Self::track_order(orders, auction_data.auction_id)
+            });
+            Auctions::<T, I>::insert(auction_id, auction_data);
+
+            Self::deposit_event(Event::AuctionTransferred {
+                auction_id,
+                previous_seller_id: seller,
+                seller_id: new_seller,
+            });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::set_tier_halted())]
+        pub fn set_tier_halted(origin: OriginFor<T>, tier: u32, halted: bool) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_tier(tier)?;
+
+            // listings and bids are rejected and executions deferred while halted
+            if halted {
+                HaltedTiers::<T, I>::insert(tier, true);
+            } else {
+                HaltedTiers::<T, I>::remove(tier);
+            }
+
+            Self::deposit_event(Event::TierHaltSet { tier, halted });
+
+            Ok(())
+        }
+
+        /// Buy up to `quantity` immediately from the open auctions of a tier.
+        ///
+        /// Auctions are taken whole, cheapest per unit first, at the lowest bid that would lead
+        /// them, as long as the total stays within `max_total_price` and the buyer can pay it.
+        /// Each fill settles at once.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::market_buy(T::MaxAuctionsPerTier::get()))]
+        pub fn market_buy(
+            origin: OriginFor<T>,
+            tier: u32,
+            quantity: u128,        // in KWH
+            max_total_price: u128, // in parachain native token
+        ) -> DispatchResult {
+            // Check that the extrinsic was signed by buyer or return error.
+            let buyer_id = ensure_signed(origin)?;
+
+            Self::ensure_valid_tier(tier)?;
+            ensure!(!Self::tier_halted(tier), Error::<T, I>::TierHalted);
+            Self::ensure_participant(&buyer_id, PartyType::Buyer)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&buyer_id)?;
+
+            // members buy within the limits of their organization
+            let organization =
+                OrganizationOf::<T, I>::get(&buyer_id).and_then(Organizations::<T, I>::get);
+
+            // open auctions of the tier paid in `Currency` that a new leading bid may settle
+            let mut candidates: Vec<(AuctionDataOf<T, I>, T::Price)> =
+                TierAuctions::<T, I>::get(tier)
+                    .into_iter()
+                    .filter_map(Auctions::<T, I>::get)
+                    .filter(|auction| {
+                        matches!(auction.auction_status, AuctionStatus::Open)
+                            && auction.seller_id != buyer_id
+                            && !AuctionAssets::<T, I>::contains_key(auction.auction_id)
+                            && !matches!(
+                                Self::algorithm_of(auction),
+                                MatchingAlgorithm::SealedBid | MatchingAlgorithm::UniformPrice
+                            )
+                            && Self::bid_count_with(auction, &buyer_id) >= auction.min_bidders
+                    })
+                    .map(|auction| {
+                        let price = Self::cross_price(&auction);
+                        (auction, price)
+                    })
+                    .filter(|(_, price)| {
+                        organization
+                            .as_ref()
+                            .map_or(true, |org| *price <= org.bid_limit)
+                    })
+                    .collect();
+            candidates.sort_by(|(a, a_price), (b, b_price)| {
+                Self::unit_price_cmp((a.quantity, *a_price), (b.quantity, *b_price))
+                    .then(a.auction_id.cmp(&b.auction_id))
+            });
+
+            // sweep whole auctions within the requested quantity and budget
+            let mut remaining = T::Quantity::from(quantity);
+            let mut total_price = T::Price::zero();
+            let max_total_price = match &organization {
+                Some(org) => {
+                    T::Price::from(max_total_price).min(org.budget.saturating_sub(org.spent))
+                }
+                None => T::Price::from(max_total_price),
+            };
+            let mut fills: Vec<(AuctionDataOf<T, I>, T::Price)> = Vec::new();
+            for (auction, price) in candidates {
+                // every fill is paid from free funds, none is held for them
+                let total = total_price.saturating_add(price);
+                if auction.quantity > remaining
+                    || total > max_total_price
+                    || !(T::Simulation::get() || Self::can_pay(&buyer_id, total))
+                {
+                    continue;
+                }
+                remaining = remaining.saturating_sub(auction.quantity);
+                total_price = total_price.saturating_add(price);
+                fills.push((auction, price));
+            }
+            ensure!(!fills.is_empty(), Error::<T, I>::NoMarketLiquidity);
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let fill_count = fills.len() as u32;
+            for (auction, price) in fills {
+                Self::cross_auction(&buyer_id, auction, price, now)?;
+            }
+
+            Self::deposit_event(Event::MarketBuyExecuted {
+                buyer_id,
+                tier,
+                energy_quantity: T::Quantity::from(quantity).saturating_sub(remaining),
+                total_price,
+                fills: fill_count,
+            });
+
+            Ok(())
+        }
+
+        /// Execute an auction immediately, before or after its end, when automated execution
+        /// is stuck.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::force_execute(T::MaxBidsPerAuction::get()))]
+        pub fn force_execute(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+
+            // queue entry may be missing, the auction must not execute twice
+            AuctionsExecutionQueue::<T, I>::remove(auction_data.end_at, auction_id);
+
+            // settle or expire with all the normal effects
+            let matched = !Self::on_auction_ended(auction_id).is_empty();
+
+            Self::deposit_event(Event::ForceExecuted {
+                auction_id,
+                matched,
+            });
+
+            Ok(())
+        }
+
+        /// Accept a match waiting for acceptance, settling the auction.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::accept_match(T::MaxBidsPerAuction::get()))]
+        pub fn accept_match(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            // Check that the extrinsic was signed by buyer or return error.
+            let buyer_id = ensure_signed(origin)?;
+
+            let auction_data = AwaitingAcceptance::<T, I>::get(auction_id)
+                .ok_or(Error::<T, I>::MatchDoesNotExist)?;
+            ensure!(
+                auction_data.highest_bid.bidder == buyer_id,
+                Error::<T, I>::NotMatchedBuyer
+            );
+
+            Self::take_awaiting_acceptance(auction_id);
+            AcceptanceDeadlines::<T, I>::remove(auction_data.end_at, auction_id);
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            if auction_data.settlement_lag.is_zero() {
+                // a buyer unable to pay is passed over
+                if Self::complete_settlement(auction_data.clone(), now).is_err() {
+                    Self::fail_over(auction_data, now);
+                }
+            } else {
+                Self::schedule_settlement(auction_data, now);
+            }
+
+            Ok(())
+        }
+
+        /// Register an organization administered and joined by the caller.
+        ///
+        /// Members bid under the organization within `bid_limit` per bid, and their
+        /// settled purchases are counted against the shared `budget`.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::create_organization())]
+        pub fn create_organization(
+            origin: OriginFor<T>,
+            bid_limit: u128, // in parachain native token
+            budget: u128,    // in parachain native token
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+
+            ensure!(
+                !OrganizationOf::<T, I>::contains_key(&admin),
+                Error::<T, I>::AlreadyOrganizationMember
+            );
+
+            let organization = OrganizationIndex::<T, I>::get();
+            OrganizationIndex::<T, I>::put(organization.saturating_add(1));
+            Organizations::<T, I>::insert(
+                organization,
+                Organization {
+                    admin: admin.clone(),
+                    bid_limit: T::Price::from(bid_limit),
+                    budget: T::Price::from(budget),
+                    spent: Zero::zero(),
+                },
+            );
+            OrganizationOf::<T, I>::insert(&admin, organization);
+
+            Self::deposit_event(Event::OrganizationCreated {
+                organization,
+                admin,
+            });
+
+            Ok(())
+        }
+
+        /// Add or remove a member of an organization, admin only.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::set_organization_member())]
+        pub fn set_organization_member(
+            origin: OriginFor<T>,
+            organization: u32,
+            member: T::AccountId,
+            is_member: bool,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            Self::ensure_organization_admin(organization, &admin)?;
+
+            if is_member {
+                ensure!(
+                    !OrganizationOf::<T, I>::contains_key(&member),
+                    Error::<T, I>::AlreadyOrganizationMember
+                );
+                OrganizationOf::<T, I>::insert(&member, organization);
+            } else {
+                ensure!(
+                    OrganizationOf::<T, I>::get(&member) == Some(organization),
+                    Error::<T, I>::NotOrganizationMember
+                );
+                OrganizationOf::<T, I>::remove(&member);
+            }
+
+            Self::deposit_event(Event::OrganizationMemberSet {
+                organization,
+                member,
+                is_member,
+            });
+
+            Ok(())
+        }
+
+        /// Set the per-bid limit and the shared budget of an organization, admin only.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_organization_limits())]
+        pub fn set_organization_limits(
+            origin: OriginFor<T>,
+            organization: u32,
+            bid_limit: u128, // in parachain native token
+            budget: u128,    // in parachain native token
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            Self::ensure_organization_admin(organization, &admin)?;
+
+            let bid_limit = T::Price::from(bid_limit);
+            let budget = T::Price::from(budget);
+            Organizations::<T, I>::mutate(organization, |maybe_organization| {
+                if let Some(org) = maybe_organization {
+                    org.bid_limit = bid_limit;
+                    org.budget = budget;
+                }
+            });
+
+            Self::deposit_event(Event::OrganizationLimitsSet {
+                organization,
+                bid_limit,
+                budget,
+            });
+
+            Ok(())
+        }
+
+        /// Be notified of auctions listed and cleared in a tier.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::subscribe_tier(T::MaxTierSubscribers::get()))]
+        pub fn subscribe_tier(origin: OriginFor<T>, tier: u32) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            Self::ensure_valid_tier(tier)?;
+
+            TierSubscribers::<T, I>::try_mutate(tier, |subscribers| {
+                ensure!(
+                    !subscribers.contains(&account),
+                    Error::<T, I>::AlreadySubscribed
+                );
+                subscribers
+                    .try_push(account.clone())
+                    .map_err(|_| Error::<T, I>::TooManySubscribers)
+            })?;
+
+            Self::deposit_event(Event::TierSubscribed { tier, account });
+
+            Ok(())
+        }
+
+        /// Stop being notified of a tier.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::unsubscribe_tier(T::MaxTierSubscribers::get()))]
+        pub fn unsubscribe_tier(origin: OriginFor<T>, tier: u32) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+
+            TierSubscribers::<T, I>::try_mutate(tier, |subscribers| {
+                let index = subscribers
+                    .iter()
+                    .position(|subscriber| subscriber == &account)
+                    .ok_or(Error::<T, I>::NotSubscribed)?;
+                subscribers.remove(index);
+                Ok::<(), Error<T, I>>(())
+            })?;
+
+            Self::deposit_event(Event::TierUnsubscribed { tier, account });
+
+            Ok(())
+        }
+
+        /// Override a market parameter, or restore its runtime constant with `None`.
+        ///
+        /// Overrides are held to the bounds `integrity_test` holds the constants to.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::set_parameter())]
+        pub fn set_parameter(
+            origin: OriginFor<T>,
+            parameter: MarketParameter<T::Price, BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            // no auction would ever execute
+            ensure!(
+                parameter != MarketParameter::MaxExecutionsPerBlock(Some(0)),
+                Error::<T, I>::InvalidParameter
+            );
+
+            Parameters::<T, I>::mutate(|parameters| match parameter.clone() {
+                MarketParameter::MinBidIncrement(value) => parameters.min_bid_increment = value,
+                MarketParameter::CommissionRate(value) => parameters.commission_rate = value,
+                MarketParameter::MaxExecutionsPerBlock(value) => {
+                    parameters.max_executions_per_block = value
+                }
+                MarketParameter::ExtensionThreshold(value) => {
+                    parameters.extension_threshold = value
+                }
+                MarketParameter::ExtensionPeriod(value) => parameters.extension_period = value,
+            });
+
+            Self::deposit_event(Event::ParameterSet { parameter });
+
+            Ok(())
+        }
+
+        /// Bid on `buy_auction_id` and link it to the caller's later `sell_auction_id`.
+        ///
+        /// The bought energy settles only if the sell leg is matched too; if either leg fails
+        /// the other is unwound, the sell leg is cancelled and the buy leg falls to the next bidder.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::pair_orders(T::MaxBidsPerAuction::get()))]
+        pub fn pair_orders(
+            origin: OriginFor<T>,
+            buy_auction_id: T::AuctionId,
+            bid: u128, // in parachain native token
+            sell_auction_id: T::AuctionId,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+
+            let sell_leg =
+                Auctions::<T, I>::get(sell_auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            let buy_leg =
+                Auctions::<T, I>::get(buy_auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            ensure!(sell_leg.seller_id == owner, Error::<T, I>::NotAuctionOwner);
+            ensure!(
+                !OrderPairs::<T, I>::contains_key(buy_auction_id)
+                    && !PairedSellLegs::<T, I>::contains_key(buy_auction_id)
+                    && !OrderPairs::<T, I>::contains_key(sell_auction_id)
+                    && !PairedSellLegs::<T, I>::contains_key(sell_auction_id),
+                Error::<T, I>::AuctionAlreadyPaired
+            );
+
+            // energy is bought before it is sold again
+            ensure!(
+                buy_leg.end_at < sell_leg.end_at,
+                Error::<T, I>::PairLegsOutOfOrder
+            );
+
+            Self::do_bid(owner.clone(), buy_auction_id, bid, None)?;
+            ensure!(
+                Auctions::<T, I>::get(buy_auction_id)
+                    .map_or(false, |auction| auction.highest_bid.bidder == owner),
+                Error::<T, I>::PairBidNotLeading
+            );
+
+            OrderPairs::<T, I>::insert(
+                buy_auction_id,
+                OrderPair {
+                    owner: owner.clone(),
+                    buy_auction_id,
+                    sell_auction_id,
+                },
+            );
+            PairedSellLegs::<T, I>::insert(sell_auction_id, buy_auction_id);
+
+            Self::deposit_event(Event::OrderPairPlaced {
+                owner,
+                buy_auction_id,
+                sell_auction_id,
+            });
+
+            Ok(())
+        }
+
+        /// Place a standing order to buy `quantity` in `tier` at up to `max_unit_price`.
+        ///
+        /// New listings of the tier crossing the order are bought whole at the order's price
+        /// until its quantity is used up.
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::place_buy_order(T::MaxStandingBuyOrders::get()))]
+        pub fn place_buy_order(
+            origin: OriginFor<T>,
+            tier: u32,
+            quantity: u128,       // in KWH
+            max_unit_price: u128, // in parachain native token per KWH
+            zone: Option<ZoneId>, // grid zone drawn from, none for the default zone
+        ) -> DispatchResult {
+            let buyer_id = ensure_signed(origin)?;
+
+            Self::ensure_valid_tier(tier)?;
+            ensure!(!Self::tier_halted(tier), Error::<T, I>::TierHalted);
+            Self::ensure_participant(&buyer_id, PartyType::Buyer)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&buyer_id)?;
+
+            let order_id = BuyOrderIndex::<T, I>::get();
+            let order = BuyOrder {
+                order_id,
+                buyer: buyer_id.clone(),
+                quantity: T::Quantity::from(quantity),
+                max_unit_price: T::Price::from(max_unit_price),
+                placed_at: <frame_system::Pallet<T>>::block_number(),
+                zone: zone.unwrap_or(DEFAULT_ZONE),
+            };
+            BuyOrders::<T, I>::try_mutate(tier, |orders| {
+                orders
+                    .try_push(order)
+                    .map_err(|_| Error::<T, I>::TooManyBuyOrders)
+            })?;
+            BuyOrderIndex::<T, I>::put(order_id.saturating_add(1));
+
+            Self::deposit_event(Event::BuyOrderPlaced {
+                order_id,
+                buyer_id,
+                tier,
+                energy_quantity: T::Quantity::from(quantity),
+                max_unit_price: T::Price::from(max_unit_price),
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw the unfilled part of a standing buy order.
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::cancel_buy_order(T::MaxStandingBuyOrders::get()))]
+        pub fn cancel_buy_order(origin: OriginFor<T>, tier: u32, order_id: u64) -> DispatchResult {
+            let buyer_id = ensure_signed(origin)?;
+
+            BuyOrders::<T, I>::try_mutate(tier, |orders| -> DispatchResult {
+                let index = orders
+                    .iter()
+                    .position(|order| order.order_id == order_id)
+                    .ok_or(Error::<T, I>::BuyOrderDoesNotExist)?;
+                ensure!(
+                    orders[index].buyer == buyer_id,
+                    Error::<T, I>::NotBuyOrderOwner
+                );
+                orders.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::BuyOrderCancelled { order_id, buyer_id });
+
+            Ok(())
+        }
+
+        /// Bid `bid` for `quantity` of a partial-fill auction.
+        ///
+        /// When the auction ends its quantity is split across bids by price per unit, earliest
+        /// first among equals, each buyer paying its bid pro rata to the part it receives.
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::bid_quantity(T::MaxBidsPerAuction::get()))]
+        pub fn bid_quantity(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            quantity: u128, // in KWH
+            bid: u128,      // in parachain native token
+        ) -> DispatchResult {
+            let buyer_id = ensure_signed(origin)?;
+
+            Self::do_partial_bid(
+                buyer_id,
+                auction_id,
+                T::Quantity::from(quantity),
+                T::Price::from(bid),
+            )
+        }
+
+        /// Withdraw the caller's bid from a live auction.
+        ///
+        /// The next best bid leads in its place and funds held for the bid are released.
+        #[pallet::call_index(29)]
+        #[pallet::weight(T::WeightInfo::cancel_bid(T::MaxBidsPerAuction::get()))]
+        pub fn cancel_bid(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            let bidder = ensure_signed(origin)?;
+
+            Self::do_cancel_bid(bidder, auction_id)
+        }
+
+        /// Commit to a hidden bid on a sealed-bid auction.
+        ///
+        /// `commitment` is `sealed_commitment(bidder, auction_id, bid, salt)`, committing again
+        /// replaces it. Commitments are accepted until the auction's last `RevealWindow` blocks.
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::commit_bid(T::MaxBidsPerAuction::get()))]
+        pub fn commit_bid(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            commitment: T::Hash,
+        ) -> DispatchResult {
+            let bidder = ensure_signed(origin)?;
+
+            Self::do_commit_bid(bidder, auction_id, commitment)
+        }
+
+        /// Reveal a committed bid during the last `RevealWindow` blocks of a sealed-bid auction.
+        ///
+        /// Only revealed bids are matched when the auction ends.
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::reveal_bid(T::MaxBidsPerAuction::get()))]
+        pub fn reveal_bid(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            bid: u128, // in parachain native token
+            salt: [u8; 32],
+        ) -> DispatchResult {
+            let bidder = ensure_signed(origin)?;
+
+            Self::do_reveal_bid(bidder, auction_id, bid, salt)
+        }
+
+        /// List energy in a descending-price auction.
+        ///
+        /// The ask starts at `starting_price` and drops by `decrement` every `interval` blocks
+        /// down to `floor_price`. The first bid at or above the ask buys the whole quantity at
+        /// the ask; the auction expires unsold when nobody bids within its period.
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::new_dutch())]
+        pub fn new_dutch(
+            origin: OriginFor<T>,
+            energy_quantity: u128, // in KWH
+            starting_price: u128,  // in parachain native token
+            floor_price: u128,     // in parachain native token
+            decrement: u128,       // in parachain native token
+            interval: BlockNumberFor<T>,
+            auction_period: u16, // in minutes, 0 for the tier default
+        ) -> DispatchResult {
+            let seller = ensure_signed(origin)?;
+
+            ensure!(
+                floor_price <= starting_price && !interval.is_zero(),
+                Error::<T, I>::InvalidPriceDecay
+            );
+            let energy_quantity = T::Quantity::from(energy_quantity);
+            let category = Self::categorize(energy_quantity);
+
+            // a single bid sells the quantity whole whatever the tier's engine
+            let auction_id = Self::create_auction(
+                seller,
+                energy_quantity,
+                T::Price::from(starting_price),
+                Self::auction_period_for(auction_period, &category)?,
+                None,
+                1,
+                Some(MatchingAlgorithm::SingleWinner),
+                Zero::zero(),
+                None,
+                None,
+                DEFAULT_ZONE,
+                None,
+                None,
+            )?;
+
+            // a crossing standing buy order may have taken it already
+            if Auctions::<T, I>::contains_key(auction_id) {
+                DutchAuctions::<T, I>::insert(
+                    auction_id,
+                    PriceDecay {
+                        floor: T::Price::from(floor_price),
+                        decrement: T::Price::from(decrement),
+                        interval,
+                    },
+                );
+            }
+
+            Ok(())
+        }
+
+        /// Buy an auction whole at its buy-it-now price.
+        ///
+        /// The auction settles in the same block without waiting for the end of its period.
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::WeightInfo::buyout(T::MaxBidsPerAuction::get()))]
+        pub fn buyout(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            let buyer_id = ensure_signed(origin)?;
+
+            let auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            Self::ensure_open(&auction_data)?;
+            Self::ensure_participant(&buyer_id, PartyType::Buyer)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&buyer_id)?;
+            ensure!(
+                !Self::tier_halted(auction_data.auction_category.level),
+                Error::<T, I>::TierHalted
+            );
+            let price =
+                BuyoutPrices::<T, I>::get(auction_id).ok_or(Error::<T, I>::NoBuyoutPrice)?;
+
+            // members buy within the limits of their organization
+            if let Some(organization) = OrganizationOf::<T, I>::get(&buyer_id) {
+                Self::ensure_within_organization_limits(organization, price)?;
+            }
+
+            Self::take_buyout(buyer_id, auction_data, price)
+        }
+
+        /// Set the quantity bands new auctions are categorized in.
+        ///
+        /// `boundaries` holds the lowest quantity of every tier above the first, strictly
+        /// ascending, so auctions fall in up to `boundaries.len() + 1` tiers.
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::WeightInfo::set_tier_boundaries(boundaries.len() as u32))]
+        pub fn set_tier_boundaries(origin: OriginFor<T>, boundaries: Vec<u128>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                (boundaries.len() as u32) < T::MaxTiers::get()
+                    && boundaries.first().map_or(true, |lowest| *lowest > 0)
+                    && boundaries.windows(2).all(|pair| pair[0] < pair[1]),
+                Error::<T, I>::InvalidTierBoundaries
+            );
+            let boundaries: TierBoundariesOf<T, I> = boundaries
+                .into_iter()
+                .map(T::Quantity::from)
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| Error::<T, I>::InvalidTierBoundaries)?;
+
+            TierConfig::<T, I>::put(&boundaries);
+            Self::deposit_event(Event::TierBoundariesSet { boundaries });
+
+            Ok(())
+        }
+
+        /// Cancel any seller's open auction, refunding its bidders.
+        ///
+        /// Unlike `cancel`, the seller is not held to the listing cooldown.
+        #[pallet::call_index(35)]
+        #[pallet::weight(T::WeightInfo::cancel(T::MaxBidsPerAuction::get()))]
+        pub fn force_cancel(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            let auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            ensure!(
+                matches!(
+                    auction_data.auction_status,
+                    AuctionStatus::Open | AuctionStatus::Pending
+                ),
+                Error::<T, I>::AuctionIsOver
+            );
+
+            Self::cancel_auction(auction_data);
+
+            Ok(())
+        }
+
+        /// List `energy_quantity` at `starting_price` now and again every `interval` blocks.
+        ///
+        /// Each listing runs for `auction_period` minutes, which must fit in the interval,
+        /// until the seller calls `cancel_recurring`.
+        #[pallet::call_index(36)]
+        #[pallet::weight(T::WeightInfo::new())]
+        pub fn create_recurring_auction(
+            origin: OriginFor<T>,
+            energy_quantity: u128,       // in KWH
+            starting_price: u128,        // in parachain native token
+            auction_period: u16,         // in minutes, 0 for the tier default
+            interval: BlockNumberFor<T>, // blocks between two listings
+        ) -> DispatchResult {
+            let seller = ensure_signed(origin)?;
+
+            let energy_quantity = T::Quantity::from(energy_quantity);
+            let category = Self::categorize(energy_quantity);
+            let auction_period = Self::auction_period_for(auction_period, &category)?;
+            ensure!(
+                !interval.is_zero() && auction_period <= interval,
+                Error::<T, I>::InvalidRecurrenceInterval
+            );
+
+            let recurring_id = RecurringIndex::<T, I>::get();
+            RecurringOf::<T, I>::try_mutate(&seller, |templates| {
+                templates
+                    .try_push(recurring_id)
+                    .map_err(|_| Error::<T, I>::TooManyRecurringAuctions)
+            })?;
+            RecurringIndex::<T, I>::put(recurring_id.saturating_add(1));
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let template = RecurringAuction {
+                seller: seller.clone(),
+                quantity: energy_quantity,
+                starting_price: T::Price::from(starting_price),
+                auction_period,
+                interval,
+                next_at: now,
+            };
+            Self::deposit_event(Event::RecurringAuctionCreated {
+                recurring_id,
+                seller_id: seller,
+                energy_quantity,
+                starting_price: template.starting_price,
+                interval,
+            });
+
+            // the first auction is listed right away and must succeed
+            let auction_id = Self::list_recurring(&template)?;
+            Self::deposit_event(Event::RecurringAuctionListed {
+                recurring_id,
+                auction_id,
+            });
+            Self::schedule_recurring(recurring_id, template);
+
+            Ok(())
+        }
+
+        /// Stop listing a recurring auction, auctions already listed run to their end.
+        #[pallet::call_index(37)]
+        #[pallet::weight(T::WeightInfo::cancel_recurring())]
+        pub fn cancel_recurring(origin: OriginFor<T>, recurring_id: u32) -> DispatchResult {
+            let seller = ensure_signed(origin)?;
+
+            let template = RecurringAuctions::<T, I>::get(recurring_id)
+                .ok_or(Error::<T, I>::RecurringAuctionDoesNotExist)?;
+            ensure!(template.seller == seller, Error::<T, I>::NotAuctionOwner);
+
+            RecurringAuctions::<T, I>::remove(recurring_id);
+            RecurringSchedule::<T, I>::remove(template.next_at, recurring_id);
+            RecurringOf::<T, I>::mutate(&seller, |templates| {
+                templates.retain(|id| *id != recurring_id)
+            });
+
+            Self::deposit_event(Event::RecurringAuctionCancelled {
+                recurring_id,
+                seller_id: seller,
+            });
+
+            Ok(())
+        }
+
+        /// Bid by proxy up to `max_bid`.
+        ///
+        /// The bid opens just high enough to lead and is raised by the minimum increment over
+        /// every later challenge until `max_bid` is reached.
+        #[pallet::call_index(38)]
+        #[pallet::weight(T::WeightInfo::bid(T::MaxBidsPerAuction::get()))]
+        pub fn bid_proxy(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            max_bid: u128,
+        ) -> DispatchResultWithPostInfo {
+            let buyer_id = ensure_signed(origin)?;
+
+            let auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            ensure!(
+                !DutchAuctions::<T, I>::contains_key(auction_id),
+                Error::<T, I>::ProxyBidUnsupported
+            );
+            let max_bid = T::Price::from(max_bid);
+            if let Some(organization) = OrganizationOf::<T, I>::get(&buyer_id) {
+                Self::ensure_within_organization_limits(organization, max_bid)?;
+            }
+
+            // a leader only moves its cap
+            let leader = auction_data.highest_bid.bidder.clone();
+            if leader == buyer_id {
+                // held to the gates of a bid, a raised cap answers challenges as bids
+                Self::ensure_open(&auction_data)?;
+                Self::ensure_participant(&buyer_id, PartyType::Buyer)?;
+                Self::ensure_market_open()?;
+                Self::ensure_reputation(&buyer_id)?;
+                ensure!(
+                    !Self::tier_halted(auction_data.auction_category.level),
+                    Error::<T, I>::TierHalted
+                );
+                ensure!(
+                    max_bid >= auction_data.highest_bid.bid,
+                    Error::<T, I>::BidTooLow
+                );
+                ProxyBids::<T, I>::insert(auction_id, &buyer_id, max_bid);
+                return Ok(Self::sponsored_call(&buyer_id).into());
+            }
+
+            // open at the lowest bid leading, above the leader's cap when it bids by proxy
+            let cross_price = Self::cross_price(&auction_data);
+            ensure!(max_bid >= cross_price, Error::<T, I>::BidTooLow);
+            let opening = match ProxyBids::<T, I>::get(auction_id, &leader) {
+                Some(cap) => cap
+                    .saturating_add(Self::min_bid_increment())
+                    .max(cross_price)
+                    .min(max_bid),
+                None => cross_price,
+            };
+            Self::do_bid(
+                buyer_id.clone(),
+                auction_id,
+                opening.unique_saturated_into(),
+                None,
+            )?;
+
+            // the rest of the cap answers later challenges
+            let leads = Auctions::<T, I>::get(auction_id)
+                .map_or(false, |auction| auction.highest_bid.bidder == buyer_id);
+            if leads && max_bid > opening {
+                ProxyBids::<T, I>::insert(auction_id, &buyer_id, max_bid);
+            }
+
+            Ok(Self::sponsored_call(&buyer_id).into())
+        }
+
+        /// Register as a market participant in `role`, or change the role of a registration.
+        ///
+        /// KYC verification and suspension are kept when the role changes. The `Sponsor` role
+        /// is granted by governance with `register_sponsor`.
+        #[pallet::call_index(39)]
+        #[pallet::weight(T::WeightInfo::register_participant())]
+        pub fn register_participant(origin: OriginFor<T>, role: PartyType) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(role != PartyType::Sponsor, Error::<T, I>::RoleNotAllowed);
+
+            Participants::<T, I>::mutate(&who, |participant| match participant {
+                Some(participant) => participant.role = role,
+                None => {
+                    *participant = Some(Participant {
+                        role,
+                        kyc_verified: false,
+                        suspended: false,
+                        registered_at: <frame_system::Pallet<T>>::block_number(),
+                    })
+                }
+            });
+
+            Self::deposit_event(Event::ParticipantRegistered { who, role });
+
+            Ok(())
+        }
+
+        /// Set whether a registered participant is KYC-verified and whether it is suspended.
+        ///
+        /// Suspended participants can neither list nor bid, whether or not registration is
+        /// required.
+        #[pallet::call_index(40)]
+        #[pallet::weight(T::WeightInfo::set_participant_status())]
+        pub fn set_participant_status(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            kyc_verified: bool,
+            suspended: bool,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Participants::<T, I>::try_mutate(&who, |participant| -> DispatchResult {
+                let participant = participant.as_mut().ok_or(Error::<T, I>::NotRegistered)?;
+                participant.kyc_verified = kyc_verified;
+                participant.suspended = suspended;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ParticipantStatusSet {
+                who,
+                kyc_verified,
+                suspended,
+            });
+
+            Ok(())
+        }
+
+        /// Post demand for `energy_quantity` at up to `max_price`, for which sellers offer
+        /// lower and lower prices over `auction_period`.
+        ///
+        /// The lowest offer when the period ends sells the quantity at its price.
+        #[pallet::call_index(41)]
+        #[pallet::weight(T::WeightInfo::new_demand())]
+        pub fn new_demand(
+            origin: OriginFor<T>,
+            energy_quantity: u128, // in KWH
+            max_price: u128,       // in parachain native token
+            auction_period: u16,   // in minutes, 0 for the tier default
+        ) -> DispatchResult {
+            let buyer = ensure_signed(origin)?;
+            Self::ensure_participant(&buyer, PartyType::Buyer)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&buyer)?;
+
+            let energy_quantity = T::Quantity::from(energy_quantity);
+            let category = Self::categorize(energy_quantity);
+            ensure!(
+                !Self::tier_halted(category.level),
+                Error::<T, I>::TierHalted
+            );
+            let auction_period = Self::auction_period_for(auction_period, &category)?;
+            Self::ensure_valid_listing(energy_quantity, auction_period)?;
+            Self::ensure_price_fits(T::Price::from(max_price))?;
+
+            // demand takes the next id of the auction sequence, so its escrow stays apart
+            let demand_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+            let max_price = T::Price::from(max_price);
+            Escrow::<T, I>::lock(demand_id, &buyer, max_price)?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let end_at = now.saturating_add(auction_period);
+            DemandAuctions::<T, I>::insert(
+                demand_id,
+                DemandAuction {
+                    demand_id,
+                    buyer: buyer.clone(),
+                    quantity: energy_quantity,
+                    max_price,
+                    best_offer: None,
+                    offer_count: 0,
+                    start_at: now,
+                    end_at,
+                },
+            );
+            DemandExecutionQueue::<T, I>::insert(end_at, demand_id, ());
+            AuctionIndex::<T, I>::set(Some(demand_id + T::AuctionId::from(1u64)));
+
+            Self::deposit_event(Event::DemandCreated {
+                demand_id,
+                buyer_id: buyer,
+                energy_quantity,
+                max_price,
+                end_at,
+            });
+
+            Ok(())
+        }
+
+        /// Offer to sell the quantity of a demand auction at `price`.
+        ///
+        /// An offer must be at most the buyer's maximum price and lower than the lowest offer
+        /// by `MinBidIncrement`.
+        #[pallet::call_index(42)]
+        #[pallet::weight(T::WeightInfo::offer())]
+        pub fn offer(origin: OriginFor<T>, demand_id: T::AuctionId, price: u128) -> DispatchResult {
+            let seller = ensure_signed(origin)?;
+            Self::ensure_participant(&seller, PartyType::Seller)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&seller)?;
+
+            let mut demand =
+                DemandAuctions::<T, I>::get(demand_id).ok_or(Error::<T, I>::DemandDoesNotExist)?;
+            ensure!(demand.buyer != seller, Error::<T, I>::OfferOnOwnDemand);
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() < demand.end_at,
+                Error::<T, I>::AuctionIsOver
+            );
+            ensure!(
+                !Self::tier_halted(Self::categorize(demand.quantity).level),
+                Error::<T, I>::TierHalted
+            );
+
+            let price = T::Price::from(price);
+            let ceiling = match &demand.best_offer {
+                Some((_, best)) => best.saturating_sub(Self::min_bid_increment()),
+                None => demand.max_price,
+            };
+            ensure!(price <= ceiling, Error::<T, I>::OfferTooHigh);
+
+            demand.best_offer = Some((seller.clone(), price));
+            demand.offer_count = demand.offer_count.saturating_add(1);
+            DemandAuctions::<T, I>::insert(demand_id, demand);
+
+            Self::deposit_event(Event::DemandOffered {
+                demand_id,
+                seller_id: seller,
+                price,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw a demand auction, releasing what was held for it.
+        #[pallet::call_index(43)]
+        #[pallet::weight(T::WeightInfo::cancel_demand())]
+        pub fn cancel_demand(origin: OriginFor<T>, demand_id: T::AuctionId) -> DispatchResult {
+            let buyer = ensure_signed(origin)?;
+
+            let demand =
+                DemandAuctions::<T, I>::get(demand_id).ok_or(Error::<T, I>::DemandDoesNotExist)?;
+            ensure!(demand.buyer == buyer, Error::<T, I>::NotAuctionOwner);
+
+            DemandAuctions::<T, I>::remove(demand_id);
+            DemandExecutionQueue::<T, I>::remove(demand.end_at, demand_id);
+            Escrow::<T, I>::release(demand_id, &buyer);
+
+            Self::deposit_event(Event::DemandCancelled {
+                demand_id,
+                buyer_id: buyer,
+            });
+
+            Ok(())
+        }
+
+        /// Correct the quantity, starting price and period of a listing nobody has bid on.
+        ///
+        /// The end is recomputed from the original start, moving the auction in the execution
+        /// queue.
+        #[pallet::call_index(44)]
+        #[pallet::weight(T::WeightInfo::update_auction())]
+        pub fn update_auction(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            new_quantity: u128, // in KWH
+            new_price: u128,    // in parachain native token
+            new_period: u16,    // in minutes, 0 for the tier default
+        ) -> DispatchResult {
+            // Check that the extrinsic was signed by seller or return error.
+            let seller = ensure_signed(origin)?;
+            Self::ensure_market_open()?;
+
+            let mut auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+
+            ensure!(
+                auction_data.seller_id == seller,
+                Error::<T, I>::NotAuctionOwner
+            );
+            ensure!(
+                matches!(
+                    auction_data.auction_status,
+                    AuctionStatus::Open | AuctionStatus::Pending
+                ),
+                Error::<T, I>::AuctionIsOver
+            );
+
+            // bidders committed to the listing as it stands
+            ensure!(auction_data.bid_count == 0, Error::<T, I>::AuctionHasBids);
+
+            // the listing is validated like a new one, in the tier of its new quantity
+            let energy_quantity = T::Quantity::from(new_quantity);
+            let starting_price = T::Price::from(new_price);
+            let category = Self::categorize(energy_quantity);
+            let auction_period = Self::auction_period_for(new_period, &category)?;
+            Self::ensure_valid_listing(energy_quantity, auction_period)?;
+            Self::ensure_price_fits(starting_price)?;
+            ensure!(
+                !Self::tier_halted(category.level),
+                Error::<T, I>::TierHalted
+            );
+            if let Some(buyout_price) = BuyoutPrices::<T, I>::get(auction_id) {
+                ensure!(
+                    buyout_price >= starting_price,
+                    Error::<T, I>::InvalidBuyoutPrice
+                );
+            }
+
+            // Re-key auction in execution queue, in the first block from the new end with room
+            let previous_end_at = auction_data.end_at;
+            let end_at = Self::requeue_auction(
+                auction_id,
+                previous_end_at,
+                auction_data
+                    .start_at
+                    .checked_add(&auction_period)
+                    .ok_or(Error::<T, I>::AuctionPeriodTooLong)?,
+            )?;
+
+            // a new quantity may move the listing to another tier
+            if auction_data.auction_category.level != category.level {
+                Self::unindex_from_tier(auction_data.auction_category.level, auction_id);
+                Self::index_in_tier(category.level, auction_id)?;
+            }
+
+            auction_data.quantity = energy_quantity;
+            auction_data.auction_category = category;
+            auction_data.auction_period = auction_period;
+            auction_data.end_at = end_at;
+            auction_data.starting_bid.bid = starting_price;
+            auction_data.highest_bid.bid = starting_price;
+            Self::ensure_delivery_window(auction_data.delivery_window.as_ref(), end_at)?;
+            ensure!(
+                Self::algorithm_of(&auction_data) != MatchingAlgorithm::SealedBid
+                    || auction_period > T::RevealWindow::get(),
+                Error::<T, I>::PeriodShorterThanReveal
+            );
+            ensure!(
+                Self::sell_leg_end(auction_id, end_at) == end_at,
+                Error::<T, I>::PairLegsOutOfOrder
+            );
+
+            Self::update_storage_deposit(&auction_data)?;
+            Self::sync_seller_auction(&auction_data);
+            Self::sync_summary(&auction_data);
+            Auctions::<T, I>::insert(auction_id, auction_data.clone());
+
+            // Emit an event that the auction was updated.
+            Self::deposit_event(Event::AuctionUpdated {
+                auction_id,
+                seller_id: seller,
+                energy_quantity,
+                starting_price,
+                previous_end_at,
+                end_at,
+            });
+
+            // a standing buy order may cross the corrected listing
+            if auction_data.auction_status == AuctionStatus::Open {
+                Self::match_buy_orders(auction_data);
+            }
+
+            Ok(())
+        }
+
+        /// Bid on several auctions at once, for aggregators bidding on behalf of many meters.
+        ///
+        /// `AllOrNothing` fails the whole batch on the first failing bid, `BestEffort` skips
+        /// failing bids with a `BatchBidFailed` event and keeps the others.
+        #[pallet::call_index(45)]
+        #[pallet::weight(
+            T::WeightInfo::bid(T::MaxBidsPerAuction::get()).saturating_mul(bids.len() as u64)
+        )]
+        pub fn bid_batch(
+            origin: OriginFor<T>,
+            bids: BoundedVec<(T::AuctionId, u128), T::MaxBatchBids>,
+            mode: BatchMode,
+        ) -> DispatchResultWithPostInfo {
+            // Check that the extrinsic was signed by buyer or return error.
+            let buyer_id = ensure_signed(origin)?;
+
+            let mut placed = 0u32;
+            let mut failed = 0u32;
+            for (auction_id, bid) in bids {
+                match mode {
+                    BatchMode::AllOrNothing => {
+                        Self::do_bid(buyer_id.clone(), auction_id, bid, None)?;
+                        placed += 1;
+                    }
+                    BatchMode::BestEffort => {
+                        // a failing bid leaves no trace but its event
+                        match frame_support::storage::with_storage_layer(|| {
+                            Self::do_bid(buyer_id.clone(), auction_id, bid, None)
+                        }) {
+                            Ok(()) => placed += 1,
+                            Err(error) => {
+                                failed += 1;
+                                Self::deposit_event(Event::BatchBidFailed {
+                                    buyer_id: buyer_id.clone(),
+                                    auction_id,
+                                    error,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+
+            Self::deposit_event(Event::BatchBidCompleted {
+                buyer_id: buyer_id.clone(),
+                placed,
+                failed,
+            });
+
+            Ok(Self::sponsored_call(&buyer_id).into())
+        }
+
+        /// Link two adjacent grid zones, losing `loss` of the energy flowing between them.
+        ///
+        /// Standing buy orders only match auctions of their own zone or of a linked one,
+        /// `None` removes the link.
+        #[pallet::call_index(46)]
+        #[pallet::weight(T::WeightInfo::set_zone_link())]
+        pub fn set_zone_link(
+            origin: OriginFor<T>,
+            zone: ZoneId,
+            neighbour: ZoneId,
+            loss: Option<Permill>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(zone != neighbour, Error::<T, I>::InvalidZoneLink);
+
+            // energy flows both ways over a link
+            ZoneTopology::<T, I>::set(zone, neighbour, loss);
+            ZoneTopology::<T, I>::set(neighbour, zone, loss);
+
+            Self::deposit_event(Event::ZoneLinkSet {
+                zone,
+                neighbour,
+                loss,
+            });
+
+            Ok(())
+        }
+
+        /// Confirm the energy bought on an auction was delivered, paying its seller.
+        #[pallet::call_index(47)]
+        #[pallet::weight(T::WeightInfo::confirm_delivery())]
+        pub fn confirm_delivery(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            let buyer_id = ensure_signed(origin)?;
+
+            let delivery = Deliveries::<T, I>::get(auction_id, &buyer_id)
+                .ok_or(Error::<T, I>::DeliveryDoesNotExist)?;
+            ensure!(!delivery.disputed, Error::<T, I>::DeliveryUnderDispute);
+
+            Deliveries::<T, I>::remove(auction_id, &buyer_id);
+            Escrow::<T, I>::pay(auction_id, &buyer_id, &delivery.seller, delivery.price)?;
+            Self::note_reputation(&delivery.seller, |stats| {
+                stats.confirmed_deliveries = stats.confirmed_deliveries.saturating_add(1)
+            });
+
+            Self::deposit_event(Event::DeliveryConfirmed {
+                auction_id,
+                seller_id: delivery.seller,
+                buyer_id,
+                price: delivery.price,
+            });
+
+            Ok(())
+        }
+
+        /// Dispute the delivery of the energy bought on an auction, freezing its payment
+        /// until `ArbitrationOrigin` resolves it.
+        #[pallet::call_index(48)]
+        #[pallet::weight(T::WeightInfo::dispute_delivery())]
+        pub fn dispute_delivery(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            let buyer_id = ensure_signed(origin)?;
+
+            let seller_id = Deliveries::<T, I>::try_mutate(
+                auction_id,
+                &buyer_id,
+                |maybe_delivery| -> Result<T::AccountId, DispatchError> {
+                    let delivery = maybe_delivery
+                        .as_mut()
+                        .ok_or(Error::<T, I>::DeliveryDoesNotExist)?;
+                    ensure!(!delivery.disputed, Error::<T, I>::DeliveryUnderDispute);
+                    delivery.disputed = true;
+                    Ok(delivery.seller.clone())
+                },
+            )?;
+
+            Self::deposit_event(Event::DeliveryDisputed {
+                auction_id,
+                seller_id,
+                buyer_id,
+            });
+
+            Ok(())
+        }
+
+        /// Resolve a pending or disputed delivery, refunding the buyer or paying the seller.
+        #[pallet::call_index(49)]
+        #[pallet::weight(T::WeightInfo::resolve_delivery())]
+        pub fn resolve_delivery(
+            origin: OriginFor<T>,
+            auction_id: T::AuctionId,
+            buyer_id: T::AccountId,
+            refund: bool,
+        ) -> DispatchResult {
+            T::ArbitrationOrigin::ensure_origin(origin)?;
+
+            let delivery = Deliveries::<T, I>::take(auction_id, &buyer_id)
+                .ok_or(Error::<T, I>::DeliveryDoesNotExist)?;
+            // the dispute counts against the party it is decided against
+            if refund {
+                Escrow::<T, I>::release(auction_id, &buyer_id);
+                Self::slash_for_non_delivery(auction_id, &delivery, &buyer_id);
+                Self::note_reputation(&delivery.seller, |stats| {
+                    stats.disputes = stats.disputes.saturating_add(1)
+                });
+            } else {
+                Escrow::<T, I>::pay(auction_id, &buyer_id, &delivery.seller, delivery.price)?;
+                if delivery.disputed {
+                    Self::note_reputation(&buyer_id, |stats| {
+                        stats.disputes = stats.disputes.saturating_add(1)
+                    });
+                }
+            }
+
+            Self::deposit_event(Event::DeliveryResolved {
+                auction_id,
+                seller_id: delivery.seller,
+                buyer_id,
+                refunded: refund,
+            });
+
+            Ok(())
+        }
+
+        /// Reserve `amount` from the seller as a stake backing its deliveries.
+        ///
+        /// The stake is slashed to compensate buyers whose delivery disputes are upheld.
+        #[pallet::call_index(50)]
+        #[pallet::weight(T::WeightInfo::bond_capacity())]
+        pub fn bond_capacity(origin: OriginFor<T>, amount: BalanceOf<T, I>) -> DispatchResult {
+            let seller_id = ensure_signed(origin)?;
+            Self::ensure_participant(&seller_id, PartyType::Seller)?;
+
+            T::Currency::reserve(&seller_id, amount)?;
+            let bonded = SellerBonds::<T, I>::mutate(&seller_id, |bond| {
+                *bond = bond.saturating_add(amount);
+                *bond
+            });
+
+            Self::deposit_event(Event::CapacityBonded {
+                seller_id,
+                amount,
+                bonded,
+            });
+
+            Ok(())
+        }
+
+        /// Reject new listings and bids in every tier, auctions already listed keep executing.
+        #[pallet::call_index(51)]
+        #[pallet::weight(T::WeightInfo::pause_market())]
+        pub fn pause_market(origin: OriginFor<T>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            MarketPaused::<T, I>::put(true);
+
+            Self::deposit_event(Event::MarketPaused);
+
+            Ok(())
+        }
+
+        /// Accept listings and bids again after `pause_market`.
+        #[pallet::call_index(52)]
+        #[pallet::weight(T::WeightInfo::resume_market())]
+        pub fn resume_market(origin: OriginFor<T>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            MarketPaused::<T, I>::kill();
+
+            Self::deposit_event(Event::MarketResumed);
+
+            Ok(())
+        }
+
+        /// Remove an auction in any state from storage, refunding its bidders and seller.
+        ///
+        /// For auctions left stuck by queue corruption or migration bugs, which neither
+        /// `force_cancel` nor `force_execute` can unwind. No lifecycle checks are made.
+        #[pallet::call_index(53)]
+        #[pallet::weight(T::WeightInfo::cancel(T::MaxBidsPerAuction::get()))]
+        pub fn force_remove(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            // a stuck auction may sit in any of the places auctions are kept
+            let auction_data = Self::remove_auction(auction_id)
+                .or_else(|| Self::take_awaiting_acceptance(auction_id))
+                .or_else(|| Self::take_awaiting_settlement(auction_id))
+                .or_else(|| UnsoldAuctions::<T, I>::take(auction_id))
+                .ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+
+            Self::purge_auction(&auction_data);
+
+            Self::deposit_event(Event::ForceRemoved {
+                auction_id,
+                seller_id: auction_data.seller_id,
+                status: auction_data.auction_status,
+            });
+
+            Ok(())
+        }
+
+        /// Sell up to `quantity` immediately to the standing buy orders of a tier.
+        ///
+        /// Orders are filled best price per unit first, the earliest among equals, each fill
+        /// listed as an auction of the tier and bought at once at the order's price. Fills that
+        /// would fall in another tier are not made, and the call fails unless they bring in at
+        /// least `min_total_price`.
+        #[pallet::call_index(54)]
+        #[pallet::weight(T::WeightInfo::market_sell(T::MaxStandingBuyOrders::get()))]
+        pub fn market_sell(
+            origin: OriginFor<T>,
+            tier: u32,
+            quantity: u128,        // in KWH
+            min_total_price: u128, // in parachain native token
+        ) -> DispatchResult {
+            // Check that the extrinsic was signed by seller or return error.
+            let seller = ensure_signed(origin)?;
+
+            Self::ensure_valid_tier(tier)?;
+            ensure!(!Self::tier_halted(tier), Error::<T, I>::TierHalted);
+            Self::ensure_participant(&seller, PartyType::Seller)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&seller)?;
+
+            let mut remaining = T::Quantity::from(quantity);
+            let mut total_price = T::Price::zero();
+            let mut fills = 0u32;
+            while !remaining.is_zero() {
+                // the best order per unit sets the fill, up to its quantity
+                let orders = BuyOrders::<T, I>::get(tier);
+                let mut best: Option<(T::Quantity, T::Price)> = None;
+                for order in orders.iter() {
+                    let fill = order.quantity.min(remaining);
+                    if Self::categorize(fill).level != tier {
+                        continue;
+                    }
+                    let price = match Self::buy_order_price(order, &seller, DEFAULT_ZONE, fill) {
+                        Some(price) => price,
+                        None => continue,
+                    };
+                    if best.map_or(true, |best| {
+                        Self::unit_price_cmp((fill, price), best) == core::cmp::Ordering::Greater
+                    }) {
+                        best = Some((fill, price));
+                    }
+                }
+                let fill = match best {
+                    Some((fill, _)) => fill,
+                    None => break,
+                };
+                // the listing is taken by the order paying most for the fill, at its price
+                let price = match Self::best_buy_order(&orders, &seller, DEFAULT_ZONE, fill) {
+                    Some((_, price)) => price,
+                    None => break,
+                };
+
+                // a fill whose payment fails is undone and ends the sale
+                let sold = frame_support::storage::with_storage_layer(|| -> DispatchResult {
+                    let auction_id = Self::create_auction(
+                        seller.clone(),
+                        fill,
+                        price,
+                        T::MinAuctionPeriod::get(),
+                        None,
+                        1,
+                        Some(MatchingAlgorithm::SingleWinner),
+                        Zero::zero(),
+                        None,
+                        None,
+                        DEFAULT_ZONE,
+                        None,
+                        None,
+                    )?;
+                    ensure!(
+                        !Auctions::<T, I>::contains_key(auction_id),
+                        Error::<T, I>::NoMarketLiquidity
+                    );
+                    Ok(())
+                });
+                if sold.is_err() {
+                    break;
+                }
+                remaining = remaining.saturating_sub(fill);
+                total_price = total_price.saturating_add(price);
+                fills = fills.saturating_add(1);
+            }
+            ensure!(fills > 0, Error::<T, I>::NoMarketLiquidity);
+            ensure!(
+                total_price >= T::Price::from(min_total_price),
+                Error::<T, I>::ProceedsBelowMinimum
+            );
+
+            Self::deposit_event(Event::MarketSellExecuted {
+                seller_id: seller,
+                tier,
+                energy_quantity: T::Quantity::from(quantity).saturating_sub(remaining),
+                total_price,
+                fills,
+            });
+
+            Ok(())
+        }
+
+        /// Register `who` as a `Sponsor`, or make a registered participant one.
+        ///
+        /// Sponsors pay the fees and deposits of others, so only governance grants the role.
+        /// KYC verification and suspension are kept when the role changes.
+        #[pallet::call_index(55)]
+        #[pallet::weight(T::WeightInfo::register_sponsor())]
+        pub fn register_sponsor(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let role = PartyType::Sponsor;
+            Participants::<T, I>::mutate(&who, |participant| match participant {
+                Some(participant) => participant.role = role,
+                None => {
+                    *participant = Some(Participant {
+                        role,
+                        kyc_verified: false,
+                        suspended: false,
+                        registered_at: <frame_system::Pallet<T>>::block_number(),
+                    })
+                }
+            });
+
+            Self::deposit_event(Event::ParticipantRegistered { who, role });
+
+            Ok(())
+        }
     }
 
-    impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
-        fn default() -> Self {
-            Self {
-                auction_index: Default::default(),
+    ///////////////////////
+    /// auction handler //
+    //////////////////////
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        /// Start the configured stepped migration from the beginning
+        pub fn start_stepped_migration() {
+            OngoingMigration::<T, I>::put(BoundedVec::<u8, ConstU32<MAX_CURSOR_LEN>>::default());
+        }
+
+        // Run one step of the ongoing migration within `limit`
+        fn step_migration(limit: Weight) -> Weight {
+            let db_weight = T::DbWeight::get().reads_writes(1, 1);
+            let cursor = match OngoingMigration::<T, I>::get() {
+                Some(cursor) => cursor,
+                None => return T::DbWeight::get().reads(1),
+            };
+            if limit.any_lt(db_weight) {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let cursor = if cursor.is_empty() {
+                None
+            } else {
+                Some(cursor.into_inner())
+            };
+            let (next, used) = T::SteppedMigration::step(cursor, limit.saturating_sub(db_weight));
+
+            match next.map(BoundedVec::<u8, ConstU32<MAX_CURSOR_LEN>>::try_from) {
+                Some(Ok(next)) => {
+                    OngoingMigration::<T, I>::put(next);
+                    Self::deposit_event(Event::MigrationStepped { weight: used });
+                }
+                // cursor does not fit, stop rather than restart the migration
+                Some(Err(_)) => {
+                    OngoingMigration::<T, I>::kill();
+                    Self::deposit_event(Event::MigrationFailed);
+                }
+                None => {
+                    OngoingMigration::<T, I>::kill();
+                    Self::deposit_event(Event::MigrationCompleted);
+                }
+            }
+
+            used.saturating_add(db_weight)
+        }
+
+        /// Past bids of `account` placed at or after `from_block`, oldest first
+        pub fn bid_history(
+            account: T::AccountId,
+            from_block: BlockNumberFor<T>,
+            limit: u32,
+        ) -> Vec<BidHistoryEntry<T::AuctionId, BlockNumberFor<T>, T::Price>> {
+            BidHistory::<T, I>::get(account)
+                .into_iter()
+                .filter(|entry| entry.placed_at >= from_block)
+                .take(limit as usize)
+                .collect()
+        }
+
+        /// Starting price for `quantity` in `tier`, from the volume-weighted average price of
+        /// recent clearings plus and minus `PriceSpread`
+        pub fn suggest_starting_price(
+            tier: u32,
+            quantity: T::Quantity,
+        ) -> Option<PriceSuggestion<T::Price>> {
+            Self::ensure_valid_tier(tier).ok()?;
+            let (volume, value) = RecentClearings::<T, I>::get(tier).into_iter().fold(
+                (0u128, 0u128),
+                |(volume, value), (quantity, price)| {
+                    (
+                        volume.saturating_add(quantity.unique_saturated_into()),
+                        value.saturating_add(price.unique_saturated_into()),
+                    )
+                },
+            );
+            if volume == 0 {
+                return None;
+            }
+
+            // prices are for the whole quantity of an auction
+            let quantity: u128 = quantity.unique_saturated_into();
+            let price = value.saturating_mul(quantity) / volume;
+            let spread = T::PriceSpread::get() * price;
+
+            Some(PriceSuggestion {
+                low: T::Price::from(price.saturating_sub(spread)),
+                price: T::Price::from(price),
+                high: T::Price::from(price.saturating_add(spread)),
+            })
+        }
+
+        /// Time- and volume-weighted average clearing price per unit of quantity of the trades
+        /// of `tier` over the last `OracleWindow` blocks, none without trades
+        pub fn price_oracle(tier: u32) -> Option<OraclePrice<T::Price, T::Quantity>> {
+            Self::ensure_valid_tier(tier).ok()?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            let oldest = now.saturating_sub(T::OracleWindow::get());
+            let trades: Vec<(BlockNumberFor<T>, u128, u128)> = TradeHistory::<T, I>::get(tier)
+                .into_iter()
+                .filter(|(at, quantity, _)| *at > oldest && !quantity.is_zero())
+                .map(|(at, quantity, price)| {
+                    (
+                        at,
+                        quantity.unique_saturated_into(),
+                        price.unique_saturated_into(),
+                    )
+                })
+                .collect();
+            if trades.is_empty() {
+                return None;
+            }
+
+            // a trade's price holds until the next trade, the last one until now
+            let (mut volume, mut value, mut blocks, mut weighted) = (0u128, 0u128, 0u128, 0u128);
+            for (index, (at, quantity, price)) in trades.iter().enumerate() {
+                let until = trades
+                    .get(index + 1)
+                    .map_or(now, |(next_at, _, _)| *next_at);
+                let held: u128 = until.saturating_sub(*at).unique_saturated_into();
+                let held = held.max(1);
+                volume = volume.saturating_add(*quantity);
+                value = value.saturating_add(*price);
+                blocks = blocks.saturating_add(held);
+                weighted = weighted.saturating_add((price / quantity).saturating_mul(held));
+            }
+
+            Some(OraclePrice {
+                time_weighted: T::Price::from(weighted / blocks),
+                volume_weighted: T::Price::from(value / volume),
+                volume: T::Quantity::from(volume),
+                trades: trades.len() as u32,
+            })
+        }
+
+        /// Number of bids per price bucket placed in `tier` over the last `HistogramWindow` eras
+        pub fn bid_histogram(tier: u32) -> Vec<u32> {
+            let buckets = T::MaxHistogramBuckets::get() as usize;
+            let era = Self::current_era();
+            let first = era.saturating_sub(T::HistogramWindow::get().saturating_sub(1));
+
+            let mut histogram: Vec<u32> = Vec::new();
+            histogram.resize(buckets, 0);
+            for era in first..=era {
+                for (bucket, count) in BidHistograms::<T, I>::get(tier, era)
+                    .into_iter()
+                    .enumerate()
+                {
+                    histogram[bucket] = histogram[bucket].saturating_add(count);
+                }
+            }
+            histogram
+        }
+
+        /// State, queue depths and blocks until the next clearing of every tier
+        pub fn market_status() -> Vec<MarketStatus<BlockNumberFor<T>>> {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let migrating = OngoingMigration::<T, I>::exists();
+            let mut statuses: Vec<MarketStatus<BlockNumberFor<T>>> = (1..=T::MaxTiers::get())
+                .map(|tier| MarketStatus {
+                    tier,
+                    state: if Self::tier_halted(tier) {
+                        TierState::Halted
+                    } else if Self::market_paused() {
+                        TierState::Paused
+                    } else if migrating {
+                        TierState::Migrating
+                    } else {
+                        TierState::Open
+                    },
+                    open_auctions: 0,
+                    awaiting_acceptance: 0,
+                    awaiting_settlement: 0,
+                    next_clearing_in: None,
+                })
+                .collect();
+
+            // read from the bounded tier indexes, auctions yet to start are not open
+            for status in statuses.iter_mut() {
+                for auction in TierAuctions::<T, I>::get(status.tier)
+                    .into_iter()
+                    .filter_map(Auctions::<T, I>::get)
+                    .filter(|auction| matches!(auction.auction_status, AuctionStatus::Open))
+                {
+                    status.open_auctions = status.open_auctions.saturating_add(1);
+                    let mut due_in = auction.end_at.saturating_sub(now);
+                    if Self::algorithm_of(&auction) == MatchingAlgorithm::UniformPrice {
+                        let interval = T::ClearingInterval::get();
+                        due_in = due_in.min(interval.saturating_sub(now % interval));
+                    }
+                    status.next_clearing_in = Some(match status.next_clearing_in {
+                        Some(next) => next.min(due_in),
+                        None => due_in,
+                    });
+                }
+                (status.awaiting_acceptance, status.awaiting_settlement) =
+                    TierQueueDepths::<T, I>::get(status.tier);
+            }
+            statuses
+        }
+
+        /// Live auctions of `tier`, oldest first
+        pub fn open_auctions(
+            tier: u32,
+        ) -> Vec<AuctionDetail<T::AccountId, T::AuctionId, BlockNumberFor<T>, T::Price, T::Quantity>>
+        {
+            let mut auctions: Vec<_> = TierAuctions::<T, I>::get(tier)
+                .into_iter()
+                .filter_map(Auctions::<T, I>::get)
+                .filter(|auction| matches!(auction.auction_status, AuctionStatus::Open))
+                .map(|auction| Self::detail_of(&auction))
+                .collect();
+            auctions.sort_by_key(|auction| auction.auction_id);
+            auctions
+        }
+
+        /// Detail of a live auction
+        pub fn auction_detail(
+            auction_id: T::AuctionId,
+        ) -> Option<
+            AuctionDetail<T::AccountId, T::AuctionId, BlockNumberFor<T>, T::Price, T::Quantity>,
+        > {
+            Auctions::<T, I>::get(auction_id).map(|auction| Self::detail_of(&auction))
+        }
+
+        /// Bidder and amount of the leading bid of a live auction, none before any bid leads
+        pub fn best_bid(auction_id: T::AuctionId) -> Option<(T::AccountId, T::Price)> {
+            Auctions::<T, I>::get(auction_id)
+                .filter(|auction| auction.highest_bid.bidder != auction.seller_id)
+                .map(|auction| (auction.highest_bid.bidder, auction.highest_bid.bid))
+        }
+
+        /// Lowest bid for `quantity` of a live auction that would lead it now, none if the
+        /// auction does not take bids for that quantity
+        ///
+        /// Partial-fill auctions are quoted to fill `quantity` whole against the bids already
+        /// placed, other auctions only sell their whole quantity.
+        pub fn quote_bid(auction_id: T::AuctionId, quantity: T::Quantity) -> Option<T::Price> {
+            let auction = Auctions::<T, I>::get(auction_id)
+                .filter(|auction| auction.auction_status == AuctionStatus::Open)?;
+            if quantity.is_zero() || quantity > auction.quantity {
+                return None;
+            }
+            let (total, wanted): (u128, u128) = (
+                auction.quantity.unique_saturated_into(),
+                quantity.unique_saturated_into(),
+            );
+
+            // a price for `of` units scaled to the quoted quantity, rounded up
+            let scaled = |price: T::Price, of: u128| -> u128 {
+                let price: u128 = price.unique_saturated_into();
+                price
+                    .saturating_mul(wanted)
+                    .saturating_add(of.saturating_sub(1))
+                    / of.max(1)
+            };
+            // the seller's starting price and reserve per unit are reached
+            let floor = auction
+                .reserve_price
+                .map_or(0, |reserve| scaled(reserve.price, total))
+                .max(scaled(auction.starting_bid.bid, total));
+
+            let algorithm = Self::algorithm_of(&auction);
+            if algorithm != MatchingAlgorithm::PartialFill && quantity != auction.quantity {
+                return None;
+            }
+            let price = match algorithm {
+                MatchingAlgorithm::PartialFill => {
+                    let mut bids: Vec<(T::Quantity, T::Price)> =
+                        Bids::<T, I>::iter_prefix_values(auction_id)
+                            .map(|record| (record.quantity.unwrap_or(auction.quantity), record.bid))
+                            .collect();
+                    bids.sort_by(|a, b| Self::unit_price_cmp(*b, *a));
+
+                    // the first bid leaving too little quantity or no fill behind it is beaten
+                    let max_fills = T::MaxFillsPerAuction::get() as usize;
+                    let mut ahead: u128 = 0;
+                    let marginal = bids.into_iter().enumerate().find_map(|(index, bid)| {
+                        ahead = ahead.saturating_add(bid.0.unique_saturated_into());
+                        (ahead.saturating_add(wanted) > total || index + 1 >= max_fills)
+                            .then_some(bid)
+                    });
+                    let beaten = marginal.map_or(0, |(of, price)| {
+                        let (of, price): (u128, u128) =
+                            (of.unique_saturated_into(), price.unique_saturated_into());
+                        (price.saturating_mul(wanted) / of.max(1)).saturating_add(1)
+                    });
+                    floor.max(beaten)
+                }
+                // hidden bids can not be quoted against
+                MatchingAlgorithm::SealedBid => floor,
+                // uniform-price auctions only take standing orders in their tier's clearings
+                MatchingAlgorithm::UniformPrice => return None,
+                _ => floor.max(Self::cross_price(&auction).unique_saturated_into()),
+            };
+
+            Some(T::Price::from(price))
+        }
+
+        /// Auctions tracked for `seller`, least recently active first
+        pub fn seller_auctions(seller: &T::AccountId) -> Vec<AuctionDataOf<T, I>> {
+            SellOrdersOf::<T, I>::get(seller)
+                .into_iter()
+                .filter_map(Auctions::<T, I>::get)
+                .collect()
+        }
+
+        /// Auctions tracked for `buyer`, least recently active first
+        pub fn buyer_auctions(buyer: &T::AccountId) -> Vec<AuctionDataOf<T, I>> {
+            BuyOrdersOf::<T, I>::get(buyer)
+                .into_iter()
+                .filter_map(Auctions::<T, I>::get)
+                .collect()
+        }
+
+        /// Check the invariants between auctions, their bids and the indexes pointing at them
+        ///
+        /// Every `AuctionsExecutionQueue` entry is a live auction ending in that block, the
+        /// `highest_bid` of every live auction leads its `Bids`, and the orders of participants
+        /// only track live auctions.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), &'static str> {
+            for (block, auction_id, _) in AuctionsExecutionQueue::<T, I>::iter() {
+                let auction_data = Auctions::<T, I>::get(auction_id)
+                    .ok_or("execution queue holds an auction that does not exist")?;
+                ensure!(
+                    matches!(
+                        auction_data.auction_status,
+                        AuctionStatus::Open | AuctionStatus::Pending
+                    ),
+                    "execution queue holds an auction that is not open"
+                );
+                ensure!(
+                    auction_data.end_at == block,
+                    "execution queue holds an auction under another block than its end"
+                );
+            }
+
+            for (auction_id, auction_data) in Auctions::<T, I>::iter() {
+                let quantity = auction_data.quantity;
+                let leader = Bids::<T, I>::iter_prefix(auction_id).max_by(|(_, a), (_, b)| {
+                    Self::unit_price_cmp(
+                        (a.quantity.unwrap_or(quantity), a.bid),
+                        (b.quantity.unwrap_or(quantity), b.bid),
+                    )
+                });
+                match leader {
+                    Some((_, leading)) => {
+                        let record =
+                            Bids::<T, I>::get(auction_id, &auction_data.highest_bid.bidder)
+                                .ok_or("highest bid is not among the bids of its auction")?;
+                        ensure!(
+                            record.bid == auction_data.highest_bid.bid,
+                            "highest bid differs from its bidder's bid"
+                        );
+                        ensure!(
+                            Self::unit_price_cmp(
+                                (record.quantity.unwrap_or(quantity), record.bid),
+                                (leading.quantity.unwrap_or(quantity), leading.bid),
+                            ) != core::cmp::Ordering::Less,
+                            "highest bid is not the best bid of its auction"
+                        );
+                    }
+                    None => ensure!(
+                        auction_data.highest_bid == auction_data.starting_bid,
+                        "auction without bids is led by another bid than its starting bid"
+                    ),
+                }
+            }
+
+            for orders in SellOrdersOf::<T, I>::iter_values() {
+                ensure!(
+                    orders
+                        .iter()
+                        .all(|auction_id| Auctions::<T, I>::contains_key(auction_id)),
+                    "sell orders track an auction that does not exist"
+                );
+            }
+            for orders in BuyOrdersOf::<T, I>::iter_values() {
+                ensure!(
+                    orders
+                        .iter()
+                        .all(|auction_id| Auctions::<T, I>::contains_key(auction_id)),
+                    "buy orders track an auction that does not exist"
+                );
+            }
+
+            for tier in 1..=T::MaxTiers::get() {
+                let of_tier =
+                    |auction: &AuctionDataOf<T, I>| auction.auction_category.level == tier;
+                ensure!(
+                    TierQueueDepths::<T, I>::get(tier)
+                        == (
+                            AwaitingAcceptance::<T, I>::iter_values().filter(of_tier).count()
+                                as u32,
+                            AwaitingSettlement::<T, I>::iter_values().filter(of_tier).count()
+                                as u32,
+                        ),
+                    "queue depths of a tier differ from its auctions awaiting acceptance or settlement"
+                );
+            }
+
+            Ok(())
+        }
+
+        // Flatten an auction for the runtime API
+        fn detail_of(
+            auction: &AuctionDataOf<T, I>,
+        ) -> AuctionDetail<T::AccountId, T::AuctionId, BlockNumberFor<T>, T::Price, T::Quantity>
+        {
+            AuctionDetail {
+                auction_id: auction.auction_id,
+                seller_id: auction.seller_id.clone(),
+                tier: auction.auction_category.level,
+                quantity: auction.quantity,
+                starting_price: auction.starting_bid.bid,
+                highest_bid: auction.highest_bid.bid,
+                bid_count: auction.bid_count,
+                status: auction.auction_status.clone(),
+                start_at: auction.start_at,
+                end_at: auction.end_at,
+                algorithm: Self::algorithm_of(auction),
+                reserve_price: auction.reserve_price.map(|reserve| reserve.price),
+                buyout_price: BuyoutPrices::<T, I>::get(auction.auction_id),
+            }
+        }
+
+        // Count a bid in the histogram of its tier, by price per unit of energy
+        fn record_bid_price(auction_data: &AuctionDataOf<T, I>, bid: T::Price) {
+            let quantity: u128 = auction_data.quantity.unique_saturated_into();
+            let bid: u128 = bid.unique_saturated_into();
+            let width: u128 = T::HistogramBucketWidth::get().unique_saturated_into();
+            let unit_price = bid / quantity.max(1);
+            let last = T::MaxHistogramBuckets::get().saturating_sub(1) as usize;
+            let bucket = ((unit_price / width.max(1)) as usize).min(last);
+
+            BidHistograms::<T, I>::mutate(
+                auction_data.auction_category.level,
+                Self::current_era(),
+                |histogram| {
+                    while histogram.len() <= bucket {
+                        if histogram.try_push(0).is_err() {
+                            return;
+                        }
+                    }
+                    histogram[bucket] = histogram[bucket].saturating_add(1);
+                },
+            );
+        }
+
+        // Add an event to the market ticker, dropping the oldest one when full
+        fn record_activity(
+            kind: ActivityKind,
+            auction_data: &AuctionDataOf<T, I>,
+            price: T::Price,
+        ) {
+            let activity = Activity {
+                kind,
+                auction_id: auction_data.auction_id,
+                quantity: auction_data.quantity,
+                price,
+                at: <frame_system::Pallet<T>>::block_number(),
+            };
+
+            MarketActivity::<T, I>::mutate(|feed| {
+                if feed.len() as u32 >= T::MaxMarketActivity::get() && !feed.is_empty() {
+                    feed.remove(0);
+                }
+                let _ = feed.try_push(activity);
+            });
+        }
+
+        // Keep the clearing in the tier's recent clearings, dropping the oldest one when full
+        fn record_clearing(tier: u32, quantity: T::Quantity, price: T::Price) {
+            RecentClearings::<T, I>::mutate(tier, |clearings| {
+                if clearings.len() as u32 >= T::MaxRecentClearings::get() && !clearings.is_empty() {
+                    clearings.remove(0);
+                }
+                let _ = clearings.try_push((quantity, price));
+            });
+        }
+
+        // Add an executed trade to the history of its tier, pruning trades older than
+        // `HistoryDepth` and dropping the oldest one when full
+        fn record_trade(tier: u32, now: BlockNumberFor<T>, quantity: T::Quantity, price: T::Price) {
+            let oldest = now.saturating_sub(T::HistoryDepth::get());
+            TradeHistory::<T, I>::mutate(tier, |trades| {
+                trades.retain(|(at, _, _)| *at > oldest);
+                if trades.len() as u32 >= T::MaxTradeHistory::get() && !trades.is_empty() {
+                    trades.remove(0);
+                }
+                let _ = trades.try_push((now, quantity, price));
+            });
+        }
+
+        // Add an outcome to the account's notifications, dropping the oldest one when full
+        fn notify(account: &T::AccountId, auction_id: T::AuctionId, outcome: Outcome<T::Price>) {
+            let notification = Notification {
+                auction_id,
+                outcome,
+                at: <frame_system::Pallet<T>>::block_number(),
+            };
+
+            Notifications::<T, I>::mutate(account, |notifications| {
+                if notifications.len() as u32 >= T::MaxNotifications::get()
+                    && !notifications.is_empty()
+                {
+                    notifications.remove(0);
+                }
+                let _ = notifications.try_push(notification);
+            });
+        }
+
+        // Notify the subscribers of an auction's tier, except its seller and leading bidder
+        fn notify_subscribers(auction_data: &AuctionDataOf<T, I>, outcome: Outcome<T::Price>) {
+            for subscriber in TierSubscribers::<T, I>::get(auction_data.auction_category.level) {
+                if subscriber == auction_data.seller_id
+                    || subscriber == auction_data.highest_bid.bidder
+                {
+                    continue;
+                }
+                Self::notify(&subscriber, auction_data.auction_id, outcome.clone());
+            }
+        }
+
+        // Add a pending bid to the bidder's history, dropping the oldest entry when full
+        fn record_bid(bidder: &T::AccountId, auction_id: T::AuctionId, bid: T::Price) {
+            let entry = BidHistoryEntry {
+                auction_id,
+                bid,
+                placed_at: <frame_system::Pallet<T>>::block_number(),
+                outcome: BidOutcome::Pending,
+            };
+
+            BidHistory::<T, I>::mutate(bidder, |history| {
+                if history.len() as u32 >= T::MaxBidHistory::get() && !history.is_empty() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(entry);
+            });
+        }
+
+        // Resolve the pending bids of a bidder on an auction
+        fn set_bid_outcome(bidder: &T::AccountId, auction_id: T::AuctionId, outcome: BidOutcome) {
+            BidHistory::<T, I>::mutate(bidder, |history| {
+                for entry in history.iter_mut() {
+                    if entry.auction_id == auction_id && entry.outcome == BidOutcome::Pending {
+                        entry.outcome = outcome.clone();
+                    }
+                }
+            });
+        }
+
+        // Convert an auction period in minutes to blocks of `BlockTimeMillis`
+        fn minutes_to_blocks(minutes: u16) -> BlockNumberFor<T> {
+            let millis = u64::from(minutes).saturating_mul(60_000);
+            BlockNumberFor::<T>::unique_saturated_from(millis / T::BlockTimeMillis::get().max(1))
+        }
+
+        // Ensure a listing has a quantity and a period within `MinAuctionPeriod` and
+        // `MaxAuctionPeriod`
+        fn ensure_valid_listing(
+            energy_quantity: T::Quantity,
+            auction_period: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            ensure!(!energy_quantity.is_zero(), Error::<T, I>::InvalidQuantity);
+            ensure!(
+                auction_period >= T::MinAuctionPeriod::get(),
+                Error::<T, I>::AuctionPeriodTooShort
+            );
+            ensure!(
+                auction_period <= T::MaxAuctionPeriod::get(),
+                Error::<T, I>::AuctionPeriodTooLong
+            );
+            Ok(())
+        }
+
+        // Ensure a price can be held in `Currency`
+        fn ensure_price_fits(price: T::Price) -> DispatchResult {
+            let price: u128 = price.unique_saturated_into();
+            BalanceOf::<T, I>::try_from(price)
+                .map(|_| ())
+                .map_err(|_| Error::<T, I>::PriceOverflow.into())
+        }
+
+        // Higher quantity of energy for sale leads to higher tier
+        fn categorize(energy_quantity: T::Quantity) -> Tier {
+            let tier = match TierConfig::<T, I>::get() {
+                // one tier above the first for every boundary reached
+                Some(boundaries) => Tier {
+                    level: 1 + boundaries
+                        .iter()
+                        .filter(|boundary| energy_quantity >= **boundary)
+                        .count() as u32,
+                },
+                None if energy_quantity < T::TierThreshold::get() => Tier::default(),
+                None => Tier { level: 2 },
+            };
+
+            // keep computed tiers within the serviced range
+            Tier {
+                level: tier.level.min(T::MaxTiers::get()),
+            }
+        }
+
+        // Check a supplied tier level is within the serviced range
+        fn ensure_valid_tier(tier: u32) -> DispatchResult {
+            ensure!(
+                tier >= 1 && tier <= T::MaxTiers::get(),
+                Error::<T, I>::InvalidTier
+            );
+            Ok(())
+        }
+
+        // Auction period in blocks, falling back to the tier default when no period is given
+        fn auction_period_for(
+            minutes: u16,
+            tier: &Tier,
+        ) -> Result<BlockNumberFor<T>, DispatchError> {
+            if minutes == 0 {
+                TierDefaultPeriods::<T, I>::get(tier.level)
+                    .ok_or_else(|| Error::<T, I>::NoDefaultAuctionPeriod.into())
+            } else {
+                Ok(Self::minutes_to_blocks(minutes))
+            }
+        }
+
+        // Create, queue and index a new auction for `seller`
+        #[allow(clippy::too_many_arguments)]
+        fn create_auction(
+            seller: T::AccountId,
+            energy_quantity: T::Quantity,
+            starting_price: T::Price,
+            auction_period_in_block_number: BlockNumberFor<T>,
+            metadata: Option<MetadataOf<T, I>>,
+            min_bidders: u32,
+            match_rule: Option<MatchingAlgorithm>,
+            settlement_lag: BlockNumberFor<T>,
+            reserve_price: Option<ReservePrice<T::Price>>,
+            start_at: Option<BlockNumberFor<T>>,
+            zone: ZoneId,
+            delivery_window: Option<DeliveryWindow<BlockNumberFor<T>>>,
+            payment_asset: Option<AssetIdOf<T, I>>,
+        ) -> Result<T::AuctionId, DispatchError> {
+            Self::ensure_participant(&seller, PartyType::Seller)?;
+            // every listing waits out a pause and needs a good record, relists and recurring
+            // listings included
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&seller)?;
+            Self::ensure_valid_listing(energy_quantity, auction_period_in_block_number)?;
+            Self::ensure_price_fits(starting_price)?;
+            if let Some(reserve) = &reserve_price {
+                Self::ensure_price_fits(reserve.price)?;
+            }
+            Self::open_auction_slot(&seller)?;
+
+            // get current_auction_id
+            let current_auction_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+
+            // Get current block number from the FRAME System pallet, a start in the future
+            // leaves the auction pending until then
+            let now = <frame_system::Pallet<T>>::block_number();
+            let starting_block_number = start_at.filter(|start_at| *start_at > now).unwrap_or(now);
+            let auction_status = if starting_block_number > now {
+                AuctionStatus::Pending
+            } else {
+                AuctionStatus::Open
+            };
+
+            let requested_end_at = starting_block_number
+                .checked_add(&auction_period_in_block_number)
+                .ok_or(Error::<T, I>::AuctionPeriodTooLong)?;
+            let ending_block_number = Self::free_end_block(requested_end_at)?;
+            Self::ensure_delivery_window(delivery_window.as_ref(), ending_block_number)?;
+
+            // Create starting bid
+            let starting_bid = BidOf::<T, I> {
+                bidder: seller.clone(),
+                bid: starting_price,
+                memo: None,
+            };
+
+            // Categorize auction
+            let category = Self::categorize(energy_quantity);
+            ensure!(
+                !Self::tier_halted(category.level),
+                Error::<T, I>::TierHalted
+            );
+            Self::index_in_tier(category.level, current_auction_id)?;
+
+            // sealed bids are revealed in the last `RevealWindow` blocks of the period
+            let algorithm = match_rule.unwrap_or_else(|| Self::matching_algorithm(category.level));
+            ensure!(
+                algorithm != MatchingAlgorithm::SealedBid
+                    || auction_period_in_block_number > T::RevealWindow::get(),
+                Error::<T, I>::PeriodShorterThanReveal
+            );
+
+            // an auction paid in an asset keeps its engine, uniform-price clearings sell to
+            // standing buy orders paying in `Currency`
+            let match_rule = match payment_asset {
+                Some(asset) => {
+                    ensure!(T::Assets::asset_exists(asset), Error::<T, I>::UnknownAsset);
+                    ensure!(
+                        algorithm != MatchingAlgorithm::UniformPrice,
+                        Error::<T, I>::AssetPaymentUnsupported
+                    );
+                    Some(algorithm)
+                }
+                None => match_rule,
+            };
+
+            // sellers wait out the cooldown of a cancelled listing of the same quantity
+            if let Some(until) = ListingCooldowns::<T, I>::get(&seller, energy_quantity) {
+                ensure!(
+                    starting_block_number >= until,
+                    Error::<T, I>::ListingCooldown
+                );
+                ListingCooldowns::<T, I>::remove(&seller, energy_quantity);
+            }
+
+            // Create auction data
+            let auction_data = AuctionData {
+                auction_id: current_auction_id,
+                seller_id: seller.clone(),
+                quantity: energy_quantity,
+                starting_bid: starting_bid.clone(),
+                bid_count: 0,
+                auction_period: ending_block_number.saturating_sub(starting_block_number),
+                auction_status,
+                start_at: starting_block_number,
+                end_at: ending_block_number,
+                highest_bid: starting_bid,
+                auction_category: category,
+                metadata,
+                min_bidders,
+                match_rule,
+                settlement_lag,
+                reserve_price,
+                zone,
+                delivery_window,
+            };
+
+            // Update seller's orders
+            SellOrdersOf::<T, I>::mutate(&seller, |orders| {
+                Self::track_order(orders, auction_data.auction_id)
+            });
+
+            // Add auction to execution queue
+            AuctionsExecutionQueue::<T, I>::insert(
+                auction_data.end_at,
+                auction_data.auction_id,
+                (),
+            );
+            if !ExecutionCursor::<T, I>::exists() {
+                ExecutionCursor::<T, I>::put(starting_block_number);
+            }
+
+            // Hold storage and listing deposits from seller
+            Self::update_storage_deposit(&auction_data)?;
+            Self::hold_auction_deposit(&auction_data)?;
+
+            // Store globalauction to storage
+            if let Some(asset) = payment_asset {
+                AuctionAssets::<T, I>::insert(auction_data.auction_id, asset);
+            }
+            Self::sync_summary(&auction_data);
+            Auctions::<T, I>::insert(&auction_data.auction_id, auction_data.clone());
+
+            Self::record_activity(ActivityKind::Created, &auction_data, starting_price);
+            Self::notify_subscribers(
+                &auction_data,
+                Outcome::Listed {
+                    tier: auction_data.auction_category.level,
+                    price: starting_price,
+                },
+            );
+
+            // update auction id
+            let next_id = current_auction_id + T::AuctionId::from(1u64);
+            AuctionIndex::<T, I>::set(Some(next_id));
+
+            // Emit an event that the auction was created.
+            Self::deposit_event(Event::AuctionCreated {
+                auction_id: auction_data.auction_id,
+                seller_id: seller,
+                energy_quantity: auction_data.quantity,
+                starting_price: auction_data.starting_bid.bid,
+                metadata: auction_data.metadata.clone(),
+                auction_category: auction_data.auction_category.clone(),
+                end_at: auction_data.end_at,
+            });
+            if ending_block_number != requested_end_at {
+                Self::deposit_event(Event::AuctionEndAdjusted {
+                    auction_id: auction_data.auction_id,
+                    requested_end_at,
+                    end_at: ending_block_number,
+                });
+            }
+
+            // a standing buy order crossing the listing takes it once bidding opens
+            if auction_data.auction_status == AuctionStatus::Pending {
+                PendingAuctions::<T, I>::insert(auction_data.start_at, auction_data.auction_id, ());
+            } else {
+                Self::match_buy_orders(auction_data);
+            }
+
+            Ok(current_auction_id)
+        }
+
+        // First block from `end_at` with room for another auction ending in it
+        fn free_end_block(
+            mut end_at: BlockNumberFor<T>,
+        ) -> Result<BlockNumberFor<T>, DispatchError> {
+            let limit = T::MaxAuctionsPerEndBlock::get() as usize;
+            while AuctionsExecutionQueue::<T, I>::iter_key_prefix(end_at)
+                .take(limit)
+                .count()
+                >= limit
+            {
+                end_at = end_at
+                    .checked_add(&One::one())
+                    .ok_or(Error::<T, I>::AuctionPeriodTooLong)?;
+            }
+            Ok(end_at)
+        }
+
+        // Move a queued auction from `previous_end_at` to the first block from `end_at` with room
+        // for it, and return that block
+        fn requeue_auction(
+            auction_id: T::AuctionId,
+            previous_end_at: BlockNumberFor<T>,
+            end_at: BlockNumberFor<T>,
+        ) -> Result<BlockNumberFor<T>, DispatchError> {
+            // the auction does not take room from itself
+            AuctionsExecutionQueue::<T, I>::remove(previous_end_at, auction_id);
+            let end_at = Self::free_end_block(end_at)?;
+            AuctionsExecutionQueue::<T, I>::insert(end_at, auction_id, ());
+            Ok(end_at)
+        }
+
+        // Requeue an auction from a hook, keeping the requested block when no later block is left
+        fn requeue_in_hook(
+            auction_id: T::AuctionId,
+            previous_end_at: BlockNumberFor<T>,
+            end_at: BlockNumberFor<T>,
+        ) -> BlockNumberFor<T> {
+            Self::requeue_auction(auction_id, previous_end_at, end_at).unwrap_or_else(|_| {
+                AuctionsExecutionQueue::<T, I>::insert(end_at, auction_id, ());
+                end_at
+            })
+        }
+
+        // Move a due auction to a later execution block
+        fn defer_auction(
+            auction_id: T::AuctionId,
+            execute_at: BlockNumberFor<T>,
+            reason: DeferralReason,
+        ) {
+            if let Some(mut auction_data) = Auctions::<T, I>::get(auction_id) {
+                // a sell leg stays after its buy leg
+                let execute_at = Self::requeue_in_hook(
+                    auction_id,
+                    auction_data.end_at,
+                    Self::sell_leg_end(auction_id, execute_at),
+                );
+
+                auction_data.end_at = execute_at;
+                Self::sync_seller_auction(&auction_data);
+
+                Self::deposit_event(Event::AuctionExecutionDeferred {
+                    auction_id,
+                    seller_id: auction_data.seller_id.clone(),
+                    reason,
+                    execute_at,
+                });
+                Self::sync_summary(&auction_data);
+                Auctions::<T, I>::insert(auction_id, auction_data);
+                Self::push_sell_leg(auction_id, execute_at);
+            }
+        }
+
+        // End block of a sell leg moved to `end_at`, after the end of its live buy leg
+        fn sell_leg_end(auction_id: T::AuctionId, end_at: BlockNumberFor<T>) -> BlockNumberFor<T> {
+            PairedSellLegs::<T, I>::get(auction_id)
+                .and_then(Auctions::<T, I>::get)
+                .filter(|buy_leg| buy_leg.end_at >= end_at)
+                .map_or(end_at, |buy_leg| buy_leg.end_at.saturating_add(One::one()))
+        }
+
+        // Defer the live sell leg of a buy leg moved to `end_at` when it no longer ends after it,
+        // the energy is bought before it is sold again
+        fn push_sell_leg(buy_auction_id: T::AuctionId, end_at: BlockNumberFor<T>) {
+            let sell_auction_id = match OrderPairs::<T, I>::get(buy_auction_id) {
+                Some(pair) => pair.sell_auction_id,
+                None => return,
+            };
+            let mut sell_leg = match Auctions::<T, I>::get(sell_auction_id) {
+                Some(sell_leg) if sell_leg.end_at <= end_at => sell_leg,
+                _ => return,
+            };
+
+            let execute_at = Self::requeue_in_hook(
+                sell_auction_id,
+                sell_leg.end_at,
+                end_at.saturating_add(One::one()),
+            );
+            sell_leg.end_at = execute_at;
+            Self::sync_seller_auction(&sell_leg);
+            Self::sync_summary(&sell_leg);
+
+            Self::deposit_event(Event::AuctionExecutionDeferred {
+                auction_id: sell_auction_id,
+                seller_id: sell_leg.seller_id.clone(),
+                reason: DeferralReason::PairedBuyLeg,
+                execute_at,
+            });
+            Auctions::<T, I>::insert(sell_auction_id, sell_leg);
+        }
+
+        // Execute auctions due up to `now` within `limit`, oldest block first and by priority
+        // within a block. Auctions over the budget stay queued and carry over to the next block.
+        fn execute_due_auctions(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+            let per_auction = T::WeightInfo::execute_auctions(1);
+            let mut used = T::DbWeight::get().reads_writes(2, 2);
+            let mut budget = Self::max_executions_per_block();
+            let mut block = ExecutionCursor::<T, I>::get().unwrap_or(now);
+            let (mut telemetry, mut settlements) =
+                BlockExecution::<T, I>::get().unwrap_or_default();
+            let processed = telemetry.executed + telemetry.deferred + telemetry.unmatched;
+
+            while block <= now {
+                used = used.saturating_add(T::DbWeight::get().reads(1));
+                if used.any_gt(limit) {
+                    break;
+                }
+
+                // get auctions of the block ready for execution, auctions of halted tiers wait
+                let mut due: Vec<ExecutionCandidate<T::AuctionId, T::Price, T::Quantity>> =
+                    Vec::new();
+                let mut halted: Vec<T::AuctionId> = Vec::new();
+                for auction_id in AuctionsExecutionQueue::<T, I>::iter_key_prefix(block) {
+                    match Auctions::<T, I>::get(auction_id) {
+                        Some(auction) if Self::tier_halted(auction.auction_category.level) => {
+                            halted.push(auction_id)
+                        }
+                        Some(auction) => due.push(ExecutionCandidate {
+                            auction_id,
+                            quantity: auction.quantity,
+                            highest_bid: auction.highest_bid.bid,
+                            bid_count: auction.bid_count,
+                        }),
+                        // settled or cancelled without leaving the queue
+                        None => AuctionsExecutionQueue::<T, I>::remove(block, auction_id),
+                    }
+                }
+
+                // order execution by configured priority
+                T::ExecutionPriority::sort(&mut due);
+
+                let mut carried_over = 0u32;
+                for candidate in due {
+                    if budget == 0 || used.saturating_add(per_auction).any_gt(limit) {
+                        // over budget, stays queued for the next block's idle time
+                        if let Some(auction) = Auctions::<T, I>::get(candidate.auction_id) {
+                            Self::deposit_event(Event::AuctionExecutionDeferred {
+                                auction_id: candidate.auction_id,
+                                seller_id: auction.seller_id,
+                                reason: DeferralReason::WeightCap,
+                                execute_at: now.saturating_add(One::one()),
+                            });
+                        }
+                        carried_over += 1;
+                        continue;
+                    }
+                    budget -= 1;
+                    used = used.saturating_add(per_auction);
+
+                    // an earlier execution may have cancelled it, the sell leg of a rejected pair
+                    AuctionsExecutionQueue::<T, I>::remove(block, candidate.auction_id);
+                    if !Auctions::<T, I>::contains_key(candidate.auction_id) {
+                        continue;
+                    }
+
+                    // handle auction execution
+                    let settled = Self::on_auction_ended(candidate.auction_id);
+                    if !settled.is_empty() {
+                        telemetry.executed += 1;
+                        for settlement in settled {
+                            telemetry.energy_cleared =
+                                telemetry.energy_cleared.saturating_add(settlement.quantity);
+                            settlements.push(settlement);
+                        }
+                    } else if !AwaitingAcceptance::<T, I>::contains_key(candidate.auction_id)
+                        && !AwaitingSettlement::<T, I>::contains_key(candidate.auction_id)
+                    {
+                        // matched ones settle once accepted or after the seller's lag
+                        telemetry.unmatched += 1;
+                    }
+                }
+
+                for auction_id in halted {
+                    Self::defer_auction(
+                        auction_id,
+                        now.saturating_add(One::one()),
+                        DeferralReason::TierHalted,
+                    );
+                    telemetry.deferred += 1;
+                }
+
+                if carried_over > 0 {
+                    telemetry.deferred += carried_over;
+                    break;
+                }
+                block = block.saturating_add(One::one());
+            }
+
+            ExecutionCursor::<T, I>::put(block);
+            if telemetry.executed + telemetry.deferred + telemetry.unmatched > processed {
+                BlockExecution::<T, I>::put((telemetry, settlements));
+            }
+            used
+        }
+
+        // Push the deadline of a live auction back by the extension period, never past
+        // `MaxAuctionDuration` from its start
+        fn extend_for_late_bid(auction_data: &mut AuctionDataOf<T, I>) {
+            let previous_end_at = auction_data.end_at;
+            let end_at = previous_end_at
+                .saturating_add(Self::extension_period())
+                .min(
+                    auction_data
+                        .start_at
+                        .saturating_add(T::MaxAuctionDuration::get()),
+                );
+            if end_at <= previous_end_at {
+                return;
+            }
+            let end_at = Self::requeue_in_hook(auction_data.auction_id, previous_end_at, end_at);
+            auction_data.end_at = end_at;
+            auction_data.auction_period = end_at.saturating_sub(auction_data.start_at);
+            Self::push_sell_leg(auction_data.auction_id, end_at);
+
+            Self::deposit_event(Event::AuctionExtended {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                previous_end_at,
+                end_at,
+            });
+        }
+
+        // Delay listing the quantity of a cancelled auction again
+        fn start_listing_cooldown(auction_data: &AuctionDataOf<T, I>) {
+            let cooldown = T::RelistCooldown::get();
+            if cooldown.is_zero() || T::Simulation::get() {
+                return;
+            }
+
+            let until = <frame_system::Pallet<T>>::block_number().saturating_add(cooldown);
+            ListingCooldowns::<T, I>::insert(&auction_data.seller_id, auction_data.quantity, until);
+            Self::deposit_event(Event::ListingCooldownStarted {
+                seller_id: auction_data.seller_id.clone(),
+                energy_quantity: auction_data.quantity,
+                until,
+            });
+        }
+
+        // Refresh the compact summary of a live auction
+        fn sync_summary(auction_data: &AuctionDataOf<T, I>) {
+            AuctionSummaries::<T, I>::insert(
+                auction_data.auction_id,
+                AuctionSummary {
+                    best_bid: auction_data.highest_bid.bid,
+                    quantity_remaining: auction_data.quantity,
+                    end_at: auction_data.end_at,
+                    status: auction_data.auction_status.clone(),
+                },
+            );
+        }
+
+        // Hold a deposit proportional to the encoded size of the auction from its seller
+        fn update_storage_deposit(auction_data: &AuctionDataOf<T, I>) -> DispatchResult {
+            if T::Simulation::get() {
+                return Ok(());
+            }
+
+            // bid records are charged at their maximum size
+            let bytes = (auction_data.encoded_size() as u32).saturating_add(
+                auction_data
+                    .bid_count
+                    .saturating_mul(BidRecordOf::<T, I>::max_encoded_len() as u32),
+            );
+            let required = T::DepositPerByte::get().saturating_mul(bytes.into());
+            let held = StorageDeposits::<T, I>::get(auction_data.auction_id);
+
+            // the sponsor of a seller covers new auctions within its era allowance
+            if held.is_zero() && !DepositPayers::<T, I>::contains_key(auction_data.auction_id) {
+                if let Some(sponsor) = Self::sponsor_deposit(&auction_data.seller_id, required) {
+                    DepositPayers::<T, I>::insert(auction_data.auction_id, sponsor);
+                    StorageDeposits::<T, I>::insert(auction_data.auction_id, required);
+                    return Ok(());
+                }
+            }
+
+            // later changes of a sponsored deposit stay with the sponsor
+            let payer = DepositPayers::<T, I>::get(auction_data.auction_id)
+                .unwrap_or_else(|| auction_data.seller_id.clone());
+            if required > held {
+                T::Currency::reserve(&payer, required - held)?;
+            } else {
+                T::Currency::unreserve(&payer, held - required);
+            }
+
+            StorageDeposits::<T, I>::insert(auction_data.auction_id, required);
+            Ok(())
+        }
+
+        // Place a bid of a buyer on a live auction
+        fn do_bid(
+            buyer_id: T::AccountId,
+            auction_id: T::AuctionId,
+            bid: u128,
+            memo: Option<MemoOf<T, I>>,
+        ) -> DispatchResult {
+            // Check auction is exist
+            ensure!(
+                Auctions::<T, I>::contains_key(auction_id),
+                Error::<T, I>::AuctionDoesNotExist
+            );
+
+            // Get auction from global auction
+            let mut auction_data = Auctions::<T, I>::get(auction_id).expect("data of auction");
+
+            // Check auction is live
+            Self::ensure_open(&auction_data)?;
+            Self::ensure_participant(&buyer_id, PartyType::Buyer)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&buyer_id)?;
+            ensure!(
+                !Self::tier_halted(auction_data.auction_category.level),
+                Error::<T, I>::TierHalted
+            );
+            ensure!(
+                Self::algorithm_of(&auction_data) != MatchingAlgorithm::SealedBid,
+                Error::<T, I>::SealedBidAuction
+            );
+            ensure!(
+                Self::algorithm_of(&auction_data) != MatchingAlgorithm::UniformPrice,
+                Error::<T, I>::UniformPriceAuction
+            );
+            ensure!(
+                Self::bid_count_with(&auction_data, &buyer_id) <= T::MaxBidsPerAuction::get(),
+                Error::<T, I>::TooManyBids
+            );
+            Self::ensure_bid_slot(auction_id, &buyer_id)?;
+
+            // members bid within the limits of their organization
+            let organization = OrganizationOf::<T, I>::get(&buyer_id);
+            if let Some(organization) = organization {
+                Self::ensure_within_organization_limits(organization, T::Price::from(bid))?;
+            }
+
+            // descending-price auctions go to the first bid reaching the ask
+            if DutchAuctions::<T, I>::contains_key(auction_id) {
+                return Self::take_dutch_auction(buyer_id, auction_data, T::Price::from(bid));
+            }
+
+            // a bid reaching the buy-it-now price buys the auction at that price
+            if let Some(price) = BuyoutPrices::<T, I>::get(auction_id) {
+                if T::Price::from(bid) >= price {
+                    return Self::take_buyout(buyer_id, auction_data, price);
+                }
+            }
+
+            ensure!(
+                T::Price::from(bid) >= auction_data.starting_bid.bid,
+                Error::<T, I>::BidTooLow
+            );
+
+            // Create new bid
+            let new_bid = BidOf::<T, I> {
+                bidder: buyer_id.clone(),
+                bid: bid.into(),
+                memo,
+            };
+            Self::record_bid_price(&auction_data, new_bid.bid);
+
+            // a leader bidding by proxy answers the challenge up to its cap
+            let leading_bid = auction_data.highest_bid.bid;
+            if new_bid.bid >= Self::cross_price(&auction_data)
+                && auction_data.highest_bid.bidder != buyer_id
+            {
+                Self::answer_with_proxy(&mut auction_data, new_bid.bid);
+            }
+
+            // a bid that neither leads nor raises a proxy leader is not kept
+            ensure!(
+                Self::takes_lead(&auction_data, &buyer_id, new_bid.bid)
+                    || auction_data.highest_bid.bid != leading_bid,
+                Error::<T, I>::BidNotLeading
+            );
+
+            // check if bid is highest bid by at least the minimum increment
+            if Self::takes_lead(&auction_data, &buyer_id, new_bid.bid) {
+                // previous highest bidder has been outbid, a leader raising its bid stays
+                if auction_data.highest_bid.bidder != buyer_id {
+                    Self::set_bid_outcome(
+                        &auction_data.highest_bid.bidder,
+                        auction_id,
+                        BidOutcome::Outbid,
+                    );
+                    Escrow::<T, I>::release(auction_id, &auction_data.highest_bid.bidder);
+                    if auction_data.highest_bid.bidder != auction_data.seller_id {
+                        Self::notify(
+                            &auction_data.highest_bid.bidder,
+                            auction_id,
+                            Outcome::Outbid { by: new_bid.bid },
+                        );
+                    }
+                }
+                Self::record_bid(&buyer_id, auction_id, new_bid.bid);
+
+                // keep the bidder's latest leading bid
+                Escrow::<T, I>::lock(auction_id, &buyer_id, new_bid.bid)?;
+                if !Bids::<T, I>::contains_key(auction_id, &buyer_id) {
+                    auction_data.bid_count += 1;
+                    Self::open_bid_slot(&buyer_id);
+                }
+                Bids::<T, I>::insert(
+                    auction_id,
+                    &buyer_id,
+                    BidRecord {
+                        bid: new_bid.bid,
+                        memo: new_bid.memo.clone(),
+                        placed_at: <frame_system::Pallet<T>>::block_number(),
+                        quantity: None,
+                    },
+                );
+                auction_data.highest_bid = new_bid.clone();
+            }
+
+            Self::store_bid(buyer_id, auction_data, new_bid, organization);
+
+            Ok(())
+        }
+
+        // Raise the bid of a leader bidding by proxy just enough to stay ahead of `challenge`,
+        // dropping its cap once the challenge reaches it or its bid cannot be held
+        fn answer_with_proxy(auction_data: &mut AuctionDataOf<T, I>, challenge: T::Price) {
+            let auction_id = auction_data.auction_id;
+            let leader = auction_data.highest_bid.bidder.clone();
+            let cap = match ProxyBids::<T, I>::get(auction_id, &leader) {
+                Some(cap) => cap,
+                None => return,
+            };
+
+            let increment = Self::min_bid_increment();
+            if challenge >= cap.saturating_add(increment) {
+                ProxyBids::<T, I>::remove(auction_id, &leader);
+                return;
+            }
+            let raised = challenge.saturating_add(increment).min(cap);
+            if raised <= auction_data.highest_bid.bid {
+                return;
+            }
+            let held = frame_support::storage::with_storage_layer(|| {
+                Escrow::<T, I>::lock(auction_id, &leader, raised)
+            });
+            if held.is_err() {
+                ProxyBids::<T, I>::remove(auction_id, &leader);
+                return;
+            }
+
+            Bids::<T, I>::mutate(auction_id, &leader, |maybe_record| {
+                if let Some(record) = maybe_record {
+                    record.bid = raised;
+                }
+            });
+            auction_data.highest_bid.bid = raised;
+            Self::record_bid(&leader, auction_id, raised);
+
+            Self::deposit_event(Event::ProxyBidRaised {
+                auction_id,
+                bidder: leader,
+                bid: raised,
+            });
+        }
+
+        // Bid for part of the quantity of a partial-fill auction
+        fn do_partial_bid(
+            buyer_id: T::AccountId,
+            auction_id: T::AuctionId,
+            quantity: T::Quantity,
+            bid: T::Price,
+        ) -> DispatchResult {
+            let mut auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            Self::ensure_open(&auction_data)?;
+            Self::ensure_participant(&buyer_id, PartyType::Buyer)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&buyer_id)?;
+            ensure!(
+                !Self::tier_halted(auction_data.auction_category.level),
+                Error::<T, I>::TierHalted
+            );
+            ensure!(
+                Self::algorithm_of(&auction_data) == MatchingAlgorithm::PartialFill,
+                Error::<T, I>::NotPartialFill
+            );
+            ensure!(
+                Self::bid_count_with(&auction_data, &buyer_id) <= T::MaxBidsPerAuction::get(),
+                Error::<T, I>::TooManyBids
+            );
+            Self::ensure_bid_slot(auction_id, &buyer_id)?;
+            ensure!(
+                !quantity.is_zero() && quantity <= auction_data.quantity,
+                Error::<T, I>::InvalidBidQuantity
+            );
+
+            // price per unit must reach the seller's
+            ensure!(
+                Self::unit_price_cmp(
+                    (quantity, bid),
+                    (auction_data.quantity, auction_data.starting_bid.bid)
+                ) != core::cmp::Ordering::Less,
+                Error::<T, I>::BidBelowReserve
+            );
+
+            let organization = OrganizationOf::<T, I>::get(&buyer_id);
+            if let Some(organization) = organization {
+                Self::ensure_within_organization_limits(organization, bid)?;
+            }
+
+            // histograms count prices for the whole quantity of an auction
+            let whole_price = {
+                let (bid, quantity, total): (u128, u128, u128) = (
+                    bid.unique_saturated_into(),
+                    quantity.unique_saturated_into(),
+                    auction_data.quantity.unique_saturated_into(),
+                );
+                T::Price::from(bid.saturating_mul(total) / quantity.max(1))
+            };
+            Self::record_bid_price(&auction_data, whole_price);
+            Self::record_bid(&buyer_id, auction_id, bid);
+
+            // every bid may fill, the best price per unit leads
+            let leading_quantity = Bids::<T, I>::get(auction_id, &auction_data.highest_bid.bidder)
+                .and_then(|record| record.quantity)
+                .unwrap_or(auction_data.quantity);
+            Escrow::<T, I>::lock(auction_id, &buyer_id, bid)?;
+            if !Bids::<T, I>::contains_key(auction_id, &buyer_id) {
+                auction_data.bid_count += 1;
+                Self::open_bid_slot(&buyer_id);
+            }
+            Bids::<T, I>::insert(
+                auction_id,
+                &buyer_id,
+                BidRecord {
+                    bid,
+                    memo: None,
+                    placed_at: <frame_system::Pallet<T>>::block_number(),
+                    quantity: Some(quantity),
+                },
+            );
+            let new_bid = BidOf::<T, I> {
+                bidder: buyer_id.clone(),
+                bid,
+                memo: None,
+            };
+            if auction_data.highest_bid.bidder == auction_data.seller_id
+                || Self::unit_price_cmp(
+                    (quantity, bid),
+                    (leading_quantity, auction_data.highest_bid.bid),
+                ) == core::cmp::Ordering::Greater
+            {
+                auction_data.highest_bid = new_bid.clone();
+            }
+
+            Self::store_bid(buyer_id, auction_data, new_bid, organization);
+
+            Ok(())
+        }
+
+        // Keep an auction and the orders of its parties in sync with a new bid
+        fn store_bid(
+            buyer_id: T::AccountId,
+            mut auction_data: AuctionDataOf<T, I>,
+            new_bid: BidOf<T, I>,
+            organization: Option<u32>,
+        ) {
+            // a late new leading bid leaves others time to respond, sealed reveals keep their window
+            let now = <frame_system::Pallet<T>>::block_number();
+            if auction_data.end_at.saturating_sub(now) < Self::extension_threshold()
+                && auction_data.highest_bid.bidder == buyer_id
+                && Self::algorithm_of(&auction_data) != MatchingAlgorithm::SealedBid
+            {
+                Self::extend_for_late_bid(&mut auction_data);
+            }
+
+            // Update buyer's and seller's orders
+            BuyOrdersOf::<T, I>::mutate(&buyer_id, |orders| {
+                Self::track_order(orders, auction_data.auction_id)
+            });
+            Self::sync_seller_auction(&auction_data);
+
+            // Seller's deposit follows the retained bids, kept as is if seller cannot cover it
+            let _ = Self::update_storage_deposit(&auction_data);
+
+            // Update global auction
+            Self::sync_summary(&auction_data);
+            Auctions::<T, I>::insert(&auction_data.auction_id, auction_data.clone());
+
+            if let Some(organization) = organization {
+                Self::deposit_event(Event::OrganizationBidPlaced {
+                    organization,
+                    member: buyer_id,
+                    auction_id: auction_data.auction_id,
+                    bid: new_bid.bid,
+                });
+            }
+
+            // Emit an event that the bid was created.
+            Self::deposit_event(Event::AuctionBidAdded {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id,
+                energy_quantity: auction_data.quantity,
+                bid: new_bid,
+                auction_category: auction_data.auction_category,
+                bid_count: auction_data.bid_count,
+                end_at: auction_data.end_at,
+            });
+        }
+
+        // Withdraw a bidder's record from a live auction and lead with the next best bid
+        fn do_cancel_bid(bidder: T::AccountId, auction_id: T::AuctionId) -> DispatchResult {
+            let mut auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            ensure!(
+                matches!(auction_data.auction_status, AuctionStatus::Open),
+                Error::<T, I>::AuctionIsOver
+            );
+            let record =
+                Bids::<T, I>::take(auction_id, &bidder).ok_or(Error::<T, I>::BidDoesNotExist)?;
+            ProxyBids::<T, I>::remove(auction_id, &bidder);
+            Self::release_bid_slot(&bidder);
+            Self::note_reputation(&bidder, |stats| {
+                stats.cancellations = stats.cancellations.saturating_add(1)
+            });
+            auction_data.bid_count = auction_data.bid_count.saturating_sub(1);
+
+            Escrow::<T, I>::release(auction_id, &bidder);
+            Self::set_bid_outcome(&bidder, auction_id, BidOutcome::Refunded);
+            BuyOrdersOf::<T, I>::mutate_exists(&bidder, |maybe_orders| {
+                Self::forget_order(maybe_orders, auction_id)
+            });
+
+            if auction_data.highest_bid.bidder == bidder {
+                Self::promote_held_bid(&mut auction_data);
+
+                // a buy leg whose owner withdraws no longer leads its pair
+                if let Some(pair) = OrderPairs::<T, I>::get(auction_id) {
+                    if pair.owner == bidder {
+                        Self::reject_order_pair(pair);
+                    }
+                }
+            }
+
+            Self::sync_seller_auction(&auction_data);
+            let _ = Self::update_storage_deposit(&auction_data);
+            Self::sync_summary(&auction_data);
+            Auctions::<T, I>::insert(auction_id, auction_data);
+
+            Self::deposit_event(Event::BidCanceled {
+                auction_id,
+                bidder,
+                amount: record.bid,
+            });
+
+            Ok(())
+        }
+
+        // Lead an open auction with its best remaining bid whose funds can be held again,
+        // dropping the bids whose bidders can no longer cover them
+        fn promote_held_bid(auction_data: &mut AuctionDataOf<T, I>) {
+            let auction_id = auction_data.auction_id;
+            let quantity = auction_data.quantity;
+            loop {
+                // best price per unit leads, whole-quantity bids compare by price
+                let next = Bids::<T, I>::iter_prefix(auction_id).max_by(|(_, a), (_, b)| {
+                    Self::unit_price_cmp(
+                        (a.quantity.unwrap_or(quantity), a.bid),
+                        (b.quantity.unwrap_or(quantity), b.bid),
+                    )
+                });
+                let (bidder, record) = match next {
+                    Some(next) => next,
+                    None => {
+                        auction_data.highest_bid = auction_data.starting_bid.clone();
+                        return;
+                    }
+                };
+
+                // outbid bids were released, the new leader's funds are held again
+                if Escrow::<T, I>::lock(auction_id, &bidder, record.bid).is_ok() {
+                    auction_data.highest_bid = BidOf::<T, I> {
+                        bidder,
+                        bid: record.bid,
+                        memo: record.memo,
+                    };
+                    return;
+                }
+
+                Bids::<T, I>::remove(auction_id, &bidder);
+                ProxyBids::<T, I>::remove(auction_id, &bidder);
+                Self::release_bid_slot(&bidder);
+                auction_data.bid_count = auction_data.bid_count.saturating_sub(1);
+                BuyOrdersOf::<T, I>::mutate_exists(&bidder, |maybe_orders| {
+                    Self::forget_order(maybe_orders, auction_id)
+                });
+                Self::refund_bid(auction_id, bidder, record.bid);
+            }
+        }
+
+        /// Current ask of a descending-price auction
+        pub fn dutch_price(auction_id: T::AuctionId) -> Option<T::Price> {
+            let decay = DutchAuctions::<T, I>::get(auction_id)?;
+            let auction_data = Auctions::<T, I>::get(auction_id)?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let steps: u128 = (now.saturating_sub(auction_data.start_at) / decay.interval)
+                .unique_saturated_into();
+            let drop = decay.decrement.saturating_mul(T::Price::from(steps));
+            Some(
+                auction_data
+                    .starting_bid
+                    .bid
+                    .saturating_sub(drop)
+                    .max(decay.floor),
+            )
+        }
+
+        // Cross the open uniform-price auctions of a tier with its standing buy orders
+        // at one price per unit in each zone, returning how many auctions were read and the
+        // settlements of the auctions sold
+        fn clear_tier(tier: u32, now: BlockNumberFor<T>) -> (u32, Vec<SettlementResultOf<T, I>>) {
+            // the tier's index bounds the auctions read to `MaxAuctionsPerTier`
+            let indexed = TierAuctions::<T, I>::get(tier);
+            let read = indexed.len() as u32;
+            let auctions: Vec<AuctionDataOf<T, I>> = indexed
+                .into_iter()
+                .filter_map(Auctions::<T, I>::get)
+                .filter(|auction| {
+                    matches!(auction.auction_status, AuctionStatus::Open)
+                        && Self::algorithm_of(auction) == MatchingAlgorithm::UniformPrice
+                })
+                .collect();
+            if auctions.is_empty() {
+                return (read, Vec::new());
+            }
+
+            let mut orders = BuyOrders::<T, I>::get(tier);
+            let mut zones: Vec<ZoneId> = auctions.iter().map(|auction| auction.zone).collect();
+            zones.sort();
+            zones.dedup();
+
+            let mut settlements: Vec<SettlementResultOf<T, I>> = Vec::new();
+            for zone in zones {
+                settlements.extend(Self::clear_zone(tier, zone, &auctions, &mut orders, now));
+            }
+            orders.retain(|order| !order.quantity.is_zero());
+            BuyOrders::<T, I>::insert(tier, orders);
+
+            (read, settlements)
+        }
+
+        // Clear the uniform-price auctions of a zone with the buy orders its energy reaches,
+        // limits of orders across a zone link are lowered by the energy lost on the way
+        fn clear_zone(
+            tier: u32,
+            zone: ZoneId,
+            auctions: &[AuctionDataOf<T, I>],
+            orders: &mut BoundedVec<BuyOrderOf<T, I>, T::MaxStandingBuyOrders>,
+            now: BlockNumberFor<T>,
+        ) -> Vec<SettlementResultOf<T, I>> {
+            let asks: Vec<Ask<T::AuctionId>> = auctions
+                .iter()
+                .filter(|auction| auction.zone == zone)
+                .map(|auction| Ask {
+                    id: auction.auction_id,
+                    quantity: auction.quantity.unique_saturated_into(),
+                    // sellers get at least their reserve
+                    price: auction
+                        .reserve_price
+                        .map_or(auction.starting_bid.bid, |reserve| {
+                            reserve.price.max(auction.starting_bid.bid)
+                        })
+                        .unique_saturated_into(),
+                })
+                .collect();
+            let demand: Vec<Order<u64>> = orders
+                .iter()
+                .filter_map(|order| {
+                    let loss = Self::zone_loss(zone, order.zone)?;
+                    Some(Order {
+                        id: order.order_id,
+                        quantity: order.quantity.unique_saturated_into(),
+                        unit_price: (loss.left_from_one() * order.max_unit_price)
+                            .unique_saturated_into(),
+                    })
+                })
+                .collect();
+            // too little demand for the supply would let a single lowball order set the price
+            if !clearing::meets_quorum(&asks, &demand, T::ClearingQuorum::get()) {
+                Self::deposit_event(Event::ClearingPostponed { tier, zone });
+                return Vec::new();
+            }
+
+            let max_matches = T::MaxMatchesPerClearing::get() as usize;
+            let clearing = match clearing::clear(asks, demand, max_matches) {
+                Some(clearing) => clearing,
+                None => return Vec::new(),
+            };
+
+            let unit_price = T::Price::from(clearing.unit_price);
+            let mut matched_volume = T::Quantity::zero();
+            let mut settlements: Vec<SettlementResultOf<T, I>> = Vec::new();
+            for (auction_id, order_id) in clearing.matches {
+                let auction_data = match auctions.iter().find(|a| a.auction_id == auction_id) {
+                    Some(auction_data) => auction_data.clone(),
+                    None => continue,
+                };
+                let order = match orders.iter_mut().find(|order| order.order_id == order_id) {
+                    Some(order) => order,
+                    None => continue,
+                };
+                let quantity: u128 = auction_data.quantity.unique_saturated_into();
+                let price = unit_price.saturating_mul(T::Price::from(quantity));
+
+                // a buyer selling to itself, short of funds or over its budget is passed over
+                let eligible = order.buyer != auction_data.seller_id
+                    && (T::Simulation::get() || Self::can_pay(&order.buyer, price))
+                    && OrganizationOf::<T, I>::get(&order.buyer).map_or(true, |organization| {
+                        Self::ensure_within_organization_limits(organization, price).is_ok()
+                    });
+                if !eligible {
+                    continue;
+                }
+
+                // a fill whose payment fails leaves the order and the auction as they were
+                let buyer_id = order.buyer.clone();
+                let quantity = auction_data.quantity;
+                if let Ok(settlement) = Self::settle_cleared(&buyer_id, auction_data, price, now) {
+                    order.quantity = order.quantity.saturating_sub(quantity);
+                    matched_volume = matched_volume.saturating_add(quantity);
+                    settlements.push(settlement);
+                }
+            }
+
+            // one event reports every sale, the clearing sells at most `MaxMatchesPerClearing`
+            if !settlements.is_empty() {
+                let results = settlements
+                    .iter()
+                    .map(|settlement| {
+                        (
+                            settlement.auction_id,
+                            settlement.buyer_id.clone(),
+                            settlement.quantity,
+                            settlement.price,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                Self::deposit_event(Event::TierCleared {
+                    tier,
+                    zone,
+                    clearing_price: unit_price,
+                    matched_volume,
+                    results: BoundedVec::truncate_from(results),
+                });
+            }
+            settlements
+        }
+
+        // Sell a descending-price auction whole to a bid reaching its ask, at the ask
+        fn take_dutch_auction(
+            buyer_id: T::AccountId,
+            auction_data: AuctionDataOf<T, I>,
+            bid: T::Price,
+        ) -> DispatchResult {
+            let auction_id = auction_data.auction_id;
+            let ask = Self::dutch_price(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            ensure!(bid >= ask, Error::<T, I>::BidBelowAsk);
+            ensure!(
+                T::Simulation::get() || Self::can_pay(&buyer_id, ask),
+                Error::<T, I>::InsufficientFunds
+            );
+
+            DutchAuctions::<T, I>::remove(auction_id);
+            Self::record_bid_price(&auction_data, ask);
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::cross_auction(&buyer_id, auction_data, ask, now)?;
+
+            Ok(())
+        }
+
+        // Sell an auction whole at its buy-it-now price and settle it at once
+        fn take_buyout(
+            buyer_id: T::AccountId,
+            auction_data: AuctionDataOf<T, I>,
+            price: T::Price,
+        ) -> DispatchResult {
+            // a bid the buyer holds on the auction counts towards the price
+            ensure!(
+                Self::can_settle(auction_data.auction_id, &buyer_id, price),
+                Error::<T, I>::InsufficientFunds
+            );
+
+            Self::record_bid_price(&auction_data, price);
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::cross_auction(&buyer_id, auction_data, price, now)?;
+
+            Ok(())
+        }
+
+        /// Bytes a relayed bid is signed over, tagged with the chain's genesis hash and the
+        /// name of this pallet instance so a signature only counts where it was meant to
+        pub fn bid_signing_payload(payload: &BidPayloadOf<T, I>) -> Vec<u8> {
+            let genesis_hash = <frame_system::Pallet<T>>::block_hash(BlockNumberFor::<T>::zero());
+            let instance = <Self as frame_support::traits::PalletInfoAccess>::name();
+            (BID_SIGNING_TAG, genesis_hash, instance.as_bytes(), payload).encode()
+        }
+
+        /// Commitment to a sealed bid of `bid` by `bidder` on `auction_id`, hidden by `salt`
+        pub fn sealed_commitment(
+            bidder: &T::AccountId,
+            auction_id: T::AuctionId,
+            bid: u128,
+            salt: &[u8; 32],
+        ) -> T::Hash {
+            T::Hashing::hash_of(&(bidder, auction_id, bid, salt))
+        }
+
+        // First block of a sealed-bid auction's reveal phase
+        fn reveal_starts_at(auction_data: &AuctionDataOf<T, I>) -> BlockNumberFor<T> {
+            auction_data.end_at.saturating_sub(T::RevealWindow::get())
+        }
+
+        // Record or replace a bidder's commitment to a sealed bid
+        fn do_commit_bid(
+            bidder: T::AccountId,
+            auction_id: T::AuctionId,
+            commitment: T::Hash,
+        ) -> DispatchResult {
+            let auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            Self::ensure_open(&auction_data)?;
+            Self::ensure_participant(&bidder, PartyType::Buyer)?;
+            Self::ensure_market_open()?;
+            Self::ensure_reputation(&bidder)?;
+            ensure!(
+                !Self::tier_halted(auction_data.auction_category.level),
+                Error::<T, I>::TierHalted
+            );
+            ensure!(
+                Self::algorithm_of(&auction_data) == MatchingAlgorithm::SealedBid,
+                Error::<T, I>::NotSealedBid
+            );
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() < Self::reveal_starts_at(&auction_data),
+                Error::<T, I>::CommitPhaseOver
+            );
+
+            // committers are the auction's prospective bidders
+            if !SealedBids::<T, I>::contains_key(auction_id, &bidder) {
+                let committed = SealedBids::<T, I>::iter_prefix(auction_id).count() as u32;
+                ensure!(
+                    committed < T::MaxBidsPerAuction::get(),
+                    Error::<T, I>::TooManyBids
+                );
+            }
+            Self::ensure_bid_slot(auction_id, &bidder)?;
+            SealedBids::<T, I>::insert(auction_id, &bidder, commitment);
+
+            Self::deposit_event(Event::BidCommitted { auction_id, bidder });
+
+            Ok(())
+        }
+
+        // Open a committed bid, every revealed bid is kept for matching
+        fn do_reveal_bid(
+            bidder: T::AccountId,
+            auction_id: T::AuctionId,
+            bid: u128,
+            salt: [u8; 32],
+        ) -> DispatchResult {
+            let mut auction_data =
+                Auctions::<T, I>::get(auction_id).ok_or(Error::<T, I>::AuctionDoesNotExist)?;
+            ensure!(
+                matches!(auction_data.auction_status, AuctionStatus::Open),
+                Error::<T, I>::AuctionIsOver
+            );
+            Self::ensure_reputation(&bidder)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                now >= Self::reveal_starts_at(&auction_data) && now <= auction_data.end_at,
+                Error::<T, I>::NotRevealPhase
+            );
+
+            let commitment = SealedBids::<T, I>::get(auction_id, &bidder)
+                .ok_or(Error::<T, I>::CommitmentDoesNotExist)?;
+            ensure!(
+                commitment == Self::sealed_commitment(&bidder, auction_id, bid, &salt),
+                Error::<T, I>::InvalidReveal
+            );
+
+            let bid = T::Price::from(bid);
+            ensure!(
+                bid >= auction_data.starting_bid.bid,
+                Error::<T, I>::BidBelowReserve
+            );
+            let organization = OrganizationOf::<T, I>::get(&bidder);
+            if let Some(organization) = organization {
+                Self::ensure_within_organization_limits(organization, bid)?;
+            }
+
+            SealedBids::<T, I>::remove(auction_id, &bidder);
+            Self::record_bid_price(&auction_data, bid);
+            Self::record_bid(&bidder, auction_id, bid);
+
+            let new_bid = BidOf::<T, I> {
+                bidder: bidder.clone(),
+                bid,
+                memo: None,
+            };
+
+            // earliest reveal leads among equal bids, only the leader's funds are held
+            let leads = auction_data.highest_bid.bidder == auction_data.seller_id
+                || bid > auction_data.highest_bid.bid;
+            if leads {
+                if auction_data.highest_bid.bidder != auction_data.seller_id {
+                    let leader = auction_data.highest_bid.bidder.clone();
+                    Self::set_bid_outcome(&leader, auction_id, BidOutcome::Outbid);
+                    Escrow::<T, I>::release(auction_id, &leader);
+                    Self::notify(&leader, auction_id, Outcome::Outbid { by: bid });
+                }
+                Escrow::<T, I>::lock(auction_id, &bidder, bid)?;
+                auction_data.highest_bid = new_bid.clone();
+            }
+
+            Self::ensure_bid_slot(auction_id, &bidder)?;
+            auction_data.bid_count += 1;
+            Self::open_bid_slot(&bidder);
+            Bids::<T, I>::insert(
+                auction_id,
+                &bidder,
+                BidRecord {
+                    bid,
+                    memo: None,
+                    placed_at: now,
+                    quantity: None,
+                },
+            );
+
+            Self::store_bid(bidder, auction_data, new_bid, organization);
+
+            Ok(())
+        }
+
+        // Check the caller administers an existing organization
+        fn ensure_organization_admin(organization: u32, who: &T::AccountId) -> DispatchResult {
+            let org = Organizations::<T, I>::get(organization)
+                .ok_or(Error::<T, I>::OrganizationDoesNotExist)?;
+            ensure!(&org.admin == who, Error::<T, I>::NotOrganizationAdmin);
+            Ok(())
+        }
+
+        // Check an account may act as `party` in the market
+        fn ensure_participant(who: &T::AccountId, party: PartyType) -> DispatchResult {
+            let participant = match Participants::<T, I>::get(who) {
+                Some(participant) => participant,
+                None if T::RequireRegistration::get() => {
+                    return Err(Error::<T, I>::NotRegistered.into())
+                }
+                None => return Ok(()),
+            };
+            ensure!(!participant.suspended, Error::<T, I>::ParticipantSuspended);
+            if T::RequireRegistration::get() {
+                ensure!(
+                    participant.role.allows(party),
+                    Error::<T, I>::RoleNotAllowed
+                );
+                ensure!(
+                    !T::RequireKyc::get() || participant.kyc_verified,
+                    Error::<T, I>::KycRequired
+                );
+            }
+            Ok(())
+        }
+
+        // Check a member's bid is within the per-bid limit and the remaining shared budget
+        fn ensure_within_organization_limits(organization: u32, bid: T::Price) -> DispatchResult {
+            let org = Organizations::<T, I>::get(organization)
+                .ok_or(Error::<T, I>::OrganizationDoesNotExist)?;
+            ensure!(
+                bid <= org.bid_limit,
+                Error::<T, I>::OrganizationBidLimitExceeded
+            );
+            ensure!(
+                org.spent.saturating_add(bid) <= org.budget,
+                Error::<T, I>::OrganizationBudgetExceeded
+            );
+            Ok(())
+        }
+
+        // Count a settled purchase of a member against its organization's budget
+        fn spend_organization_budget(
+            member: &T::AccountId,
+            auction_id: T::AuctionId,
+            amount: T::Price,
+        ) {
+            let organization = match OrganizationOf::<T, I>::get(member) {
+                Some(organization) => organization,
+                None => return,
+            };
+
+            Organizations::<T, I>::mutate(organization, |maybe_organization| {
+                if let Some(org) = maybe_organization {
+                    org.spent = org.spent.saturating_add(amount);
+                }
+            });
+            Self::deposit_event(Event::OrganizationBudgetSpent {
+                organization,
+                member: member.clone(),
+                auction_id,
+                amount,
+            });
+        }
+
+        // Lowest bid that would lead an auction, the first bid leads from the starting price
+        // and later ones by the minimum increment
+        fn cross_price(auction_data: &AuctionDataOf<T, I>) -> T::Price {
+            if auction_data.highest_bid.bidder == auction_data.seller_id {
+                return auction_data.starting_bid.bid;
+            }
+            auction_data
+                .highest_bid
+                .bid
+                .saturating_add(Self::min_bid_increment())
+        }
+
+        // Whether `bid` by `bidder` leads an auction, a leader raising its own bid needs no
+        // increment
+        fn takes_lead(
+            auction_data: &AuctionDataOf<T, I>,
+            bidder: &T::AccountId,
+            bid: T::Price,
+        ) -> bool {
+            if auction_data.highest_bid.bidder == *bidder {
+                return bid > auction_data.highest_bid.bid;
+            }
+            bid >= Self::cross_price(auction_data)
+        }
+
+        // Number of bidders of an auction once `bidder` has bid on it
+        fn bid_count_with(auction_data: &AuctionDataOf<T, I>, bidder: &T::AccountId) -> u32 {
+            if Bids::<T, I>::contains_key(auction_data.auction_id, bidder) {
+                auction_data.bid_count
+            } else {
+                auction_data.bid_count.saturating_add(1)
+            }
+        }
+
+        // Compare two `(quantity, price)` offers by price per unit without dividing
+        fn unit_price_cmp(
+            (a_quantity, a_price): (T::Quantity, T::Price),
+            (b_quantity, b_price): (T::Quantity, T::Price),
+        ) -> core::cmp::Ordering {
+            let (a_quantity, a_price): (u128, u128) = (
+                a_quantity.unique_saturated_into(),
+                a_price.unique_saturated_into(),
+            );
+            let (b_quantity, b_price): (u128, u128) = (
+                b_quantity.unique_saturated_into(),
+                b_price.unique_saturated_into(),
+            );
+            a_price
+                .saturating_mul(b_quantity)
+                .cmp(&b_price.saturating_mul(a_quantity))
+        }
+
+        // Sell a new listing to the best standing buy order of its tier that crosses it
+        fn match_buy_orders(auction_data: AuctionDataOf<T, I>) {
+            // a single buyer cannot meet a minimum of several bidders, sealed auctions only
+            // take revealed bids and uniform-price ones wait for their tier's clearing, standing
+            // orders pay in `Currency` only
+            if auction_data.min_bidders > 1
+                || AuctionAssets::<T, I>::contains_key(auction_data.auction_id)
+                || matches!(
+                    Self::algorithm_of(&auction_data),
+                    MatchingAlgorithm::SealedBid | MatchingAlgorithm::UniformPrice
+                )
+            {
+                return;
+            }
+
+            let tier = auction_data.auction_category.level;
+            let mut orders = BuyOrders::<T, I>::get(tier);
+
+            // a listing below the best order's price is below every other order's too
+            let (index, price) = match Self::best_buy_order(
+                &orders,
+                &auction_data.seller_id,
+                auction_data.zone,
+                auction_data.quantity,
+            ) {
+                Some((index, price))
+                    if price >= auction_data.starting_bid.bid
+                        && auction_data
+                            .reserve_price
+                            .map_or(true, |reserve| price >= reserve.price) =>
+                {
+                    (index, price)
+                }
+                _ => return,
+            };
+
+            // a fill whose payment fails leaves the order and the listing as they were
+            let now = <frame_system::Pallet<T>>::block_number();
+            let _ = frame_support::storage::with_storage_layer(|| {
+                let order = &mut orders[index];
+                order.quantity = order.quantity.saturating_sub(auction_data.quantity);
+                let order_id = order.order_id;
+                let buyer_id = order.buyer.clone();
+                if order.quantity.is_zero() {
+                    orders.remove(index);
+                }
+                BuyOrders::<T, I>::insert(tier, orders);
+
+                Self::deposit_event(Event::BuyOrderFilled {
+                    order_id,
+                    buyer_id: buyer_id.clone(),
+                    auction_id: auction_data.auction_id,
+                    energy_quantity: auction_data.quantity,
+                    price,
+                });
+                Self::cross_auction(&buyer_id, auction_data, price, now)
+            });
+        }
+
+        // Standing buy order paying most for `quantity` from `seller_id` in `zone`, with its
+        // index and price, orders are kept oldest first so ties go to the earliest
+        fn best_buy_order(
+            orders: &[BuyOrderOf<T, I>],
+            seller_id: &T::AccountId,
+            zone: ZoneId,
+            quantity: T::Quantity,
+        ) -> Option<(usize, T::Price)> {
+            let mut best: Option<(usize, T::Price)> = None;
+            for (index, order) in orders.iter().enumerate() {
+                if order.quantity < quantity {
+                    continue;
+                }
+                let price = match Self::buy_order_price(order, seller_id, zone, quantity) {
+                    Some(price) => price,
+                    None => continue,
+                };
+                if best.map_or(true, |(_, best_price)| price > best_price) {
+                    best = Some((index, price));
+                }
+            }
+            best
+        }
+
+        // Price a standing buy order pays for `quantity` from `seller_id` in `zone`, none when
+        // the energy does not reach its buyer or the buyer cannot pay it
+        fn buy_order_price(
+            order: &BuyOrderOf<T, I>,
+            seller_id: &T::AccountId,
+            zone: ZoneId,
+            quantity: T::Quantity,
+        ) -> Option<T::Price> {
+            // energy only reaches buyers of its zone or of a linked one
+            let loss = Self::zone_loss(zone, order.zone)?;
+            let quantity: u128 = quantity.unique_saturated_into();
+            let price = loss.left_from_one()
+                * order
+                    .max_unit_price
+                    .saturating_mul(T::Price::from(quantity));
+            let can_buy = &order.buyer != seller_id
+                && (T::Simulation::get() || Self::can_pay(&order.buyer, price))
+                && OrganizationOf::<T, I>::get(&order.buyer).map_or(true, |organization| {
+                    Self::ensure_within_organization_limits(organization, price).is_ok()
+                });
+            can_buy.then_some(price)
+        }
+
+        // Settle an open auction immediately with a buyer taking it at `price`, leaving it open
+        // when the buyer's payment fails
+        fn cross_auction(
+            buyer_id: &T::AccountId,
+            mut auction_data: AuctionDataOf<T, I>,
+            price: T::Price,
+            now: BlockNumberFor<T>,
+        ) -> Result<SettlementResultOf<T, I>, DispatchError> {
+            frame_support::storage::with_storage_layer(|| {
+                Self::take_off_book(buyer_id, &mut auction_data, price);
+                Self::settle_single_winner(auction_data, now)
+            })
+        }
+
+        // Settle an auction sold by a tier clearing without lifecycle events of its own, the
+        // clearing reports it in `TierCleared`, leaving it open when the buyer's payment fails
+        //
+        // `FeeCharged` and `CommissionCharged` are still emitted: they record funds moved that
+        // `TierCleared` does not carry. Subscriber notifications are kept in storage, bounded by
+        // `MaxNotifications` per account, and add no events.
+        fn settle_cleared(
+            buyer_id: &T::AccountId,
+            mut auction_data: AuctionDataOf<T, I>,
+            price: T::Price,
+            now: BlockNumberFor<T>,
+        ) -> Result<SettlementResultOf<T, I>, DispatchError> {
+            frame_support::storage::with_storage_layer(|| {
+                Self::take_off_book(buyer_id, &mut auction_data, price);
+                Self::advance(&mut auction_data, AuctionStatus::Matched);
+                Self::apply_settlement(auction_data, now, false).map(|(_, settlement)| settlement)
+            })
+        }
+
+        // Take an open auction off the book for a buyer taking it at `price`
+        fn take_off_book(
+            buyer_id: &T::AccountId,
+            auction_data: &mut AuctionDataOf<T, I>,
+            price: T::Price,
+        ) {
+            // previous leader has been outbid
+            let leader = auction_data.highest_bid.bidder.clone();
+            if leader != auction_data.seller_id && &leader != buyer_id {
+                Self::set_bid_outcome(&leader, auction_data.auction_id, BidOutcome::Outbid);
+                Self::notify(
+                    &leader,
+                    auction_data.auction_id,
+                    Outcome::Outbid { by: price },
+                );
+            }
+            Self::record_bid(buyer_id, auction_data.auction_id, price);
+
+            // Auction leaves the book and the execution queue
+            Self::remove_auction(auction_data.auction_id);
+            BuyoutPrices::<T, I>::remove(auction_data.auction_id);
+            RelistAttempts::<T, I>::remove(auction_data.auction_id);
+            AuctionSummaries::<T, I>::remove(auction_data.auction_id);
+            AuctionsExecutionQueue::<T, I>::remove(auction_data.end_at, auction_data.auction_id);
+            let _ = ProxyBids::<T, I>::clear_prefix(auction_data.auction_id, u32::MAX, None);
+            Self::untrack_auction(auction_data);
+
+            auction_data.highest_bid = BidOf::<T, I> {
+                bidder: buyer_id.clone(),
+                bid: price,
+                memo: None,
+            };
+        }
+
+        /// Amount by which a bid must exceed the highest bid to lead
+        pub fn min_bid_increment() -> T::Price {
+            Self::parameters()
+                .min_bid_increment
+                .unwrap_or_else(T::MinBidIncrement::get)
+        }
+
+        /// Commission charged to each party of a settled auction
+        pub fn commission_rate() -> Perbill {
+            Self::parameters()
+                .commission_rate
+                .unwrap_or_else(T::CommissionRate::get)
+        }
+
+        /// Maximum number of auctions executed in a block
+        pub fn max_executions_per_block() -> u32 {
+            Self::parameters()
+                .max_executions_per_block
+                .unwrap_or_else(T::MaxExecutionsPerBlock::get)
+        }
+
+        /// Last blocks of an auction in which a new leading bid extends it
+        pub fn extension_threshold() -> BlockNumberFor<T> {
+            Self::parameters()
+                .extension_threshold
+                .unwrap_or_else(T::ExtensionThreshold::get)
+        }
+
+        /// Blocks an auction is extended by when bid on late
+        pub fn extension_period() -> BlockNumberFor<T> {
+            Self::parameters()
+                .extension_period
+                .unwrap_or_else(T::ExtensionPeriod::get)
+        }
+
+        // Account holding collected commissions
+        pub fn account_id() -> T::AccountId {
+            T::PalletId::get().into_account_truncating()
+        }
+
+        // Collect the commission of a settled auction from one party and accrue the referral share
+        fn charge_commission(auction_id: T::AuctionId, payer: &T::AccountId, price: T::Price) {
+            let price: u128 = price.unique_saturated_into();
+            let amount = Self::commission_rate() * BalanceOf::<T, I>::unique_saturated_from(price);
+            if amount.is_zero() || T::Simulation::get() {
+                return;
+            }
+
+            // charged once the trade has settled, a party short of it keeps the trade uncharged
+            let asset = AuctionAssets::<T, I>::get(auction_id);
+            let charged = match asset {
+                Some(asset) => {
+                    T::Assets::transfer(asset, payer, &Self::account_id(), amount, true).map(|_| ())
+                }
+                None => T::Currency::transfer(
+                    payer,
+                    &Self::account_id(),
+                    amount,
+                    ExistenceRequirement::KeepAlive,
+                ),
+            };
+            if charged.is_err() {
+                return;
+            }
+            Self::deposit_event(Event::CommissionCharged {
+                auction_id,
+                payer: payer.clone(),
+                amount,
+            });
+
+            if let Some(referrer) = Referrers::<T, I>::get(payer) {
+                let reward = T::ReferralShare::get() * amount;
+                if reward.is_zero() {
+                    return;
+                }
+                // `claim_referral_rewards` pays out `Currency`, a share in an asset is paid at once
+                match asset {
+                    Some(asset) => {
+                        if T::Assets::transfer(asset, &Self::account_id(), &referrer, reward, false)
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                    None => ReferralRewards::<T, I>::mutate(&referrer, |rewards| {
+                        *rewards = rewards.saturating_add(reward)
+                    }),
+                }
+                Self::deposit_event(Event::ReferralRewardAccrued {
+                    referrer,
+                    account: payer.clone(),
+                    amount: reward,
+                });
+            }
+        }
+
+        // Take the market fee of an executed auction from its seller and hand it to
+        // `OnFeeCollected`
+        fn charge_fee(
+            auction_id: T::AuctionId,
+            seller: &T::AccountId,
+            quantity: T::Quantity,
+            price: T::Price,
+        ) {
+            if T::Simulation::get() || quantity < T::FeeExemptQuantity::get() {
+                return;
+            }
+            let price: u128 = price.unique_saturated_into();
+            let amount = T::FeeRate::get() * BalanceOf::<T, I>::unique_saturated_from(price);
+            if amount.is_zero() {
+                return;
+            }
+
+            // the sale stands whatever the fee, a seller who cannot cover it is not charged
+            match AuctionAssets::<T, I>::get(auction_id) {
+                // `OnFeeCollected` takes `Currency`, fees in an asset stay with the pallet account
+                Some(asset) => {
+                    if T::Assets::transfer(asset, seller, &Self::account_id(), amount, true)
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                None => {
+                    let fee = match T::Currency::withdraw(
+                        seller,
+                        amount,
+                        WithdrawReasons::FEE,
+                        ExistenceRequirement::KeepAlive,
+                    ) {
+                        Ok(fee) => fee,
+                        Err(_) => return,
+                    };
+                    T::OnFeeCollected::on_unbalanced(fee);
+                }
+            }
+            Self::deposit_event(Event::FeeCharged {
+                auction_id,
+                seller_id: seller.clone(),
+                amount,
+            });
+        }
+
+        // Return the storage deposit of an auction to its seller
+        fn release_storage_deposit(auction_data: &AuctionDataOf<T, I>) {
+            let held = StorageDeposits::<T, I>::take(auction_data.auction_id);
+            let payer = DepositPayers::<T, I>::take(auction_data.auction_id)
+                .unwrap_or_else(|| auction_data.seller_id.clone());
+            T::Currency::unreserve(&payer, held);
+        }
+
+        // Ensure an auction accepts bids
+        fn ensure_open(auction_data: &AuctionDataOf<T, I>) -> DispatchResult {
+            match auction_data.auction_status {
+                AuctionStatus::Open => Ok(()),
+                AuctionStatus::Pending => Err(Error::<T, I>::AuctionNotStarted.into()),
+                _ => Err(Error::<T, I>::AuctionIsOver.into()),
+            }
+        }
+
+        // List the auction of a recurring template with the tier defaults of its quantity
+        fn list_recurring(
+            template: &RecurringAuctionOf<T, I>,
+        ) -> Result<T::AuctionId, DispatchError> {
+            let category = Self::categorize(template.quantity);
+            Self::create_auction(
+                template.seller.clone(),
+                template.quantity,
+                template.starting_price,
+                template.auction_period,
+                None,
+                TierMinBidders::<T, I>::get(category.level),
+                None,
+                Zero::zero(),
+                None,
+                None,
+                DEFAULT_ZONE,
+                None,
+                None,
+            )
+        }
+
+        // Keep a recurring template until its next listing, an interval after the last one
+        fn schedule_recurring(recurring_id: u32, mut template: RecurringAuctionOf<T, I>) {
+            template.next_at = template.next_at.saturating_add(template.interval);
+            RecurringSchedule::<T, I>::insert(template.next_at, recurring_id, ());
+            RecurringAuctions::<T, I>::insert(recurring_id, template);
+        }
+
+        // List the recurring auctions due at `now`, returning how many were attempted;
+        // a listing that fails is skipped until the next interval
+        fn list_recurring_auctions(now: BlockNumberFor<T>) -> u32 {
+            let mut listed = 0u32;
+            let due: Vec<u32> = RecurringSchedule::<T, I>::drain_prefix(now)
+                .map(|(recurring_id, _)| recurring_id)
+                .collect();
+            for recurring_id in due {
+                let template = match RecurringAuctions::<T, I>::get(recurring_id) {
+                    Some(template) => template,
+                    None => continue,
+                };
+                listed += 1;
+
+                match frame_support::storage::with_storage_layer(|| Self::list_recurring(&template))
+                {
+                    Ok(auction_id) => Self::deposit_event(Event::RecurringAuctionListed {
+                        recurring_id,
+                        auction_id,
+                    }),
+                    Err(error) => Self::deposit_event(Event::RecurringAuctionSkipped {
+                        recurring_id,
+                        error,
+                    }),
+                }
+                Self::schedule_recurring(recurring_id, template);
+            }
+            listed
+        }
+
+        // Sell the demand auctions ending at `now` to their lowest offer, or let them expire
+        // without one, returning how many ended
+        fn execute_demand_auctions(now: BlockNumberFor<T>) -> u32 {
+            let mut ended = 0u32;
+            let due: Vec<T::AuctionId> = DemandExecutionQueue::<T, I>::drain_prefix(now)
+                .map(|(demand_id, _)| demand_id)
+                .collect();
+            for demand_id in due {
+                let demand = match DemandAuctions::<T, I>::take(demand_id) {
+                    Some(demand) => demand,
+                    None => continue,
+                };
+                ended += 1;
+
+                let (seller, price) = match demand.best_offer {
+                    Some(offer) => offer,
+                    None => {
+                        Escrow::<T, I>::release(demand_id, &demand.buyer);
+                        Self::deposit_event(Event::DemandExpired {
+                            demand_id,
+                            buyer_id: demand.buyer,
+                        });
+                        continue;
+                    }
+                };
+
+                // the buyer pays the offer out of its held maximum, like a winning bid, and a
+                // demand it cannot pay expires
+                if Escrow::<T, I>::pay(demand_id, &demand.buyer, &seller, price).is_err() {
+                    Escrow::<T, I>::release(demand_id, &demand.buyer);
+                    Self::deposit_event(Event::DemandExpired {
+                        demand_id,
+                        buyer_id: demand.buyer,
+                    });
+                    continue;
+                }
+                Self::charge_fee(demand_id, &seller, demand.quantity, price);
+                Self::charge_commission(demand_id, &seller, price);
+                Self::charge_commission(demand_id, &demand.buyer, price);
+                Self::record_settled_volume(&seller, &demand.buyer, demand.quantity);
+                Self::record_trade(
+                    Self::categorize(demand.quantity).level,
+                    now,
+                    demand.quantity,
+                    price,
+                );
+                T::OnAuctionExecuted::on_auction_executed(
+                    demand_id,
+                    &seller,
+                    &demand.buyer,
+                    demand.quantity,
+                    price,
+                );
+
+                Self::deposit_event(Event::DemandExecuted {
+                    demand_id,
+                    buyer_id: demand.buyer,
+                    seller_id: seller,
+                    energy_quantity: demand.quantity,
+                    clearing_price: price,
+                    executed_at: now,
+                });
+            }
+            ended
+        }
+
+        // Open the auctions scheduled to start at `now` for bids, returning how many started
+        fn start_pending_auctions(now: BlockNumberFor<T>) -> u32 {
+            let mut started = 0u32;
+            let due: Vec<T::AuctionId> = PendingAuctions::<T, I>::drain_prefix(now)
+                .map(|(auction_id, _)| auction_id)
+                .collect();
+            for auction_id in due {
+                let mut auction_data = match Auctions::<T, I>::get(auction_id) {
+                    Some(auction_data) => auction_data,
+                    None => continue,
+                };
+                Self::transition(&mut auction_data, AuctionStatus::Open);
+                Self::sync_seller_auction(&auction_data);
+                Self::sync_summary(&auction_data);
+                Auctions::<T, I>::insert(auction_id, auction_data.clone());
+                started += 1;
+
+                // a standing buy order crossing the listing takes it right away
+                Self::match_buy_orders(auction_data);
+            }
+            started
+        }
+
+        // Move an auction to the next state of its lifecycle and announce it, a move the
+        // lifecycle does not allow is a bug and leaves the auction as it is
+        fn transition(auction_data: &mut AuctionDataOf<T, I>, status: AuctionStatus) {
+            if let Some(previous_status) = Self::advance(auction_data, status) {
+                Self::deposit_event(Event::AuctionStatusChanged {
+                    auction_id: auction_data.auction_id,
+                    previous_status,
+                    status: auction_data.auction_status.clone(),
+                });
+            }
+        }
+
+        // Move an auction to the next state of its lifecycle without announcing it, returning
+        // the state it left
+        fn advance(
+            auction_data: &mut AuctionDataOf<T, I>,
+            status: AuctionStatus,
+        ) -> Option<AuctionStatus> {
+            if auction_data.auction_status == status {
+                return None;
+            }
+            if !auction_data.auction_status.can_transition_to(&status) {
+                frame_support::defensive!("auction moved outside of its lifecycle");
+                return None;
+            }
+
+            Some(core::mem::replace(&mut auction_data.auction_status, status))
+        }
+
+        // Hold the listing deposit of an auction from its seller
+        fn hold_auction_deposit(auction_data: &AuctionDataOf<T, I>) -> DispatchResult {
+            let deposit = T::AuctionDeposit::get();
+            if T::Simulation::get() || deposit.is_zero() {
+                return Ok(());
+            }
+
+            T::Currency::reserve(&auction_data.seller_id, deposit)?;
+            AuctionDeposits::<T, I>::insert(auction_data.auction_id, deposit);
+            Ok(())
+        }
+
+        // Return the listing deposit of an auction to its seller
+        fn release_auction_deposit(auction_data: &AuctionDataOf<T, I>) {
+            let held = AuctionDeposits::<T, I>::take(auction_data.auction_id);
+            T::Currency::unreserve(&auction_data.seller_id, held);
+        }
+
+        // Compensate the buyer of an undelivered sale from its seller's bond, by
+        // `NonDeliverySlash` of the sale's price at most
+        fn slash_for_non_delivery(
+            auction_id: T::AuctionId,
+            delivery: &DeliveryOf<T, I>,
+            buyer_id: &T::AccountId,
+        ) {
+            if T::Simulation::get() {
+                return;
+            }
+
+            let price: u128 = delivery.price.unique_saturated_into();
+            let bond = SellerBonds::<T, I>::get(&delivery.seller);
+            let amount = (T::NonDeliverySlash::get()
+                * BalanceOf::<T, I>::unique_saturated_from(price))
+            .min(bond);
+            if amount.is_zero() {
+                return;
+            }
+
+            let unmoved = T::Currency::repatriate_reserved(
+                &delivery.seller,
+                buyer_id,
+                amount,
+                BalanceStatus::Free,
+            )
+            .unwrap_or(amount);
+            let slashed = amount.saturating_sub(unmoved);
+            SellerBonds::<T, I>::mutate_exists(&delivery.seller, |maybe_bond| {
+                *maybe_bond = maybe_bond
+                    .map(|bond| bond.saturating_sub(slashed))
+                    .filter(|bond| !bond.is_zero());
+            });
+
+            Self::deposit_event(Event::SellerSlashed {
+                auction_id,
+                seller_id: delivery.seller.clone(),
+                buyer_id: buyer_id.clone(),
+                amount: slashed,
+            });
+        }
+
+        // Count an unsold auction against its seller, slashing its listing deposit once the
+        // seller has left more than `MaxUnsoldStreak` auctions unsold in a row
+        fn note_unsold(auction_data: &AuctionDataOf<T, I>) {
+            let unsold_streak = UnsoldStreaks::<T, I>::mutate(&auction_data.seller_id, |streak| {
+                *streak = streak.saturating_add(1);
+                *streak
+            });
+            if unsold_streak <= T::MaxUnsoldStreak::get() {
+                return;
+            }
+
+            let held = AuctionDeposits::<T, I>::take(auction_data.auction_id);
+            if held.is_zero() {
+                return;
             }
+            let (_, unslashed) = T::Currency::slash_reserved(&auction_data.seller_id, held);
+            Self::deposit_event(Event::AuctionDepositSlashed {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                amount: held.saturating_sub(unslashed),
+                unsold_streak,
+            });
         }
-    }
 
-    #[pallet::genesis_build]
-    impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
-        fn build(&self) {
-            let initial_id = self.auction_index;
-            <AuctionIndex<T, I>>::put(initial_id);
+        /// Market era of the current block
+        pub fn current_era() -> u32 {
+            let now = <frame_system::Pallet<T>>::block_number();
+            (now / T::EraLength::get()).unique_saturated_into()
         }
-    }
 
-    ///////////////////
-    // Pallet hooks //
-    //////////////////
-    #[pallet::hooks]
-    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
-        fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
-            // T::WeightInfo::on_finalize(AuctionsExecutionQueue::<T>::iter_prefix(now).count() as
-            // u32)
-            Weight::from_all(100_000_000u64)
+        // Switch tiers to their scheduled matching algorithms
+        fn apply_pending_matching_algorithms() {
+            for (tier, algorithm) in PendingMatchingAlgorithms::<T, I>::drain() {
+                TierMatchingAlgorithms::<T, I>::insert(tier, algorithm);
+                Self::deposit_event(Event::MatchingAlgorithmChanged { tier, algorithm });
+            }
         }
 
-        fn on_finalize(now: BlockNumberFor<T>) {
-            // get auction ready for execution
-            for (auction_id, _) in AuctionsExecutionQueue::<T, I>::drain_prefix(now) {
-                if let Some(auction) = Auctions::<T, I>::get(auction_id) {
-                    // handle auction execution
-                    Self::on_auction_ended(auction.auction_id);
+        // Close the era before `era`, dropping its volumes and leaderboards past retention
+        fn end_era(era: u32) {
+            let ended = match era.checked_sub(1) {
+                Some(ended) => ended,
+                None => return,
+            };
+
+            let _ = EraVolumes::<T, I>::clear_prefix(ended, u32::MAX, None);
+            if let Some(expired) = ended.checked_sub(T::LeaderboardRetention::get()) {
+                Leaderboards::<T, I>::remove(expired);
+            }
+            if let Some(expired) = era.checked_sub(T::HistogramWindow::get()) {
+                for tier in 1..=T::MaxTiers::get() {
+                    BidHistograms::<T, I>::remove(tier, expired);
                 }
             }
+
+            Self::deposit_event(Event::EraEnded { era: ended });
         }
-    }
 
-    //////////////////////
-    // Runtime events  //
-    /////////////////////
-    // runtime event for important runtime actions
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config<I>, I: 'static = ()> {
-        AuctionCreated {
-            auction_id: T::AuctionId,
-            seller_id: T::AccountId,
-            energy_quantity: T::Quantity,
-            starting_price: T::Price,
-        },
+        // Add settled energy to the era volumes of both parties and rank them
+        fn record_settled_volume(
+            seller: &T::AccountId,
+            buyer: &T::AccountId,
+            quantity: T::Quantity,
+        ) {
+            let era = Self::current_era();
 
-        AuctionBidAdded {
-            auction_id: T::AuctionId,
-            seller_id: T::AccountId,
-            energy_quantity: T::Quantity,
-            bid: Bid<T::AccountId, T::Price>,
-        },
+            let sold = EraVolumes::<T, I>::mutate(era, seller, |volume| {
+                volume.sold = volume.sold.saturating_add(quantity);
+                volume.sold
+            });
+            let bought = EraVolumes::<T, I>::mutate(era, buyer, |volume| {
+                volume.bought = volume.bought.saturating_add(quantity);
+                volume.bought
+            });
 
-        AuctionMatched {
-            auction_id: T::AuctionId,
-            seller_id: T::AccountId,
-            energy_quantity: T::Quantity,
-            starting_price: T::Price,
-            highest_bid: Bid<T::AccountId, T::Price>,
-            matched_at: BlockNumberFor<T>,
-        },
+            Leaderboards::<T, I>::mutate(era, |maybe_leaderboard| {
+                let leaderboard = maybe_leaderboard.get_or_insert_with(Default::default);
+                Self::rank(&mut leaderboard.sellers, seller, sold);
+                Self::rank(&mut leaderboard.buyers, buyer, bought);
+            });
+        }
 
-        AuctionExecuted {
+        // Move an account to its place on a ranking, dropping the lowest entry when full
+        fn rank(ranking: &mut RankingOf<T, I>, who: &T::AccountId, volume: T::Quantity) {
+            ranking.retain(|(account, _)| account != who);
+            let position = ranking
+                .iter()
+                .position(|(_, ranked)| *ranked < volume)
+                .unwrap_or(ranking.len());
+            let _ = ranking.force_insert_keep_left(position, (who.clone(), volume));
+        }
+
+        // Reset the allowances of a sponsorship from a previous era
+        fn roll_sponsorship_era(sponsorship: &mut Sponsorship<T::AccountId, BalanceOf<T, I>>) {
+            let era = Self::current_era();
+            if sponsorship.era != era {
+                sponsorship.era = era;
+                sponsorship.deposit_used = Zero::zero();
+                sponsorship.calls_used = 0;
+            }
+        }
+
+        // Hold a deposit from the sponsor of an account if its allowance covers it
+        fn sponsor_deposit(
+            account: &T::AccountId,
+            amount: BalanceOf<T, I>,
+        ) -> Option<T::AccountId> {
+            Sponsorships::<T, I>::mutate(account, |maybe_sponsorship| {
+                let sponsorship = maybe_sponsorship.as_mut()?;
+                Self::roll_sponsorship_era(sponsorship);
+
+                let deposit_used = sponsorship.deposit_used.saturating_add(amount);
+                if deposit_used > sponsorship.deposit_limit {
+                    return None;
+                }
+                T::Currency::reserve(&sponsorship.sponsor, amount).ok()?;
+                sponsorship.deposit_used = deposit_used;
+
+                Some(sponsorship.sponsor.clone())
+            })
+        }
+
+        // Waive the fee of a call from a sponsored account within its era allowance
+        fn sponsored_call(account: &T::AccountId) -> Pays {
+            Sponsorships::<T, I>::mutate(account, |maybe_sponsorship| {
+                let sponsorship = match maybe_sponsorship.as_mut() {
+                    Some(sponsorship) => sponsorship,
+                    None => return Pays::Yes,
+                };
+                Self::roll_sponsorship_era(sponsorship);
+
+                if sponsorship.calls_used < sponsorship.call_limit {
+                    sponsorship.calls_used += 1;
+                    Pays::No
+                } else {
+                    Pays::Yes
+                }
+            })
+        }
+
+        // Mark an auction as the seller's most recently active one
+        fn sync_seller_auction(auction_data: &AuctionDataOf<T, I>) {
+            if !SellOrdersOf::<T, I>::contains_key(&auction_data.seller_id) {
+                return;
+            }
+
+            SellOrdersOf::<T, I>::mutate(&auction_data.seller_id, |orders| {
+                if orders.contains(&auction_data.auction_id) {
+                    Self::track_order(orders, auction_data.auction_id);
+                }
+            });
+        }
+
+        // Track an auction as the most recently active of a participant's orders, dropping
+        // the least recently active one when full
+        fn track_order<S: Get<u32>>(
+            orders: &mut BoundedVec<T::AuctionId, S>,
             auction_id: T::AuctionId,
-            seller_id: T::AccountId,
-            buyer_id: T::AccountId,
-            energy_quantity: T::Quantity,
-            starting_price: T::Price,
-            highest_bid: T::Price,
-            executed_at: BlockNumberFor<T>,
-        },
+        ) {
+            match orders.iter().position(|order| *order == auction_id) {
+                Some(index) => {
+                    orders.remove(index);
+                }
+                None if orders.len() as u32 >= S::get() && !orders.is_empty() => {
+                    orders.remove(0);
+                }
+                None => {}
+            }
+            let _ = orders.try_push(auction_id);
+        }
 
-        AuctionCanceled {
+        // Drop an auction from a participant's orders, removing the entry once empty
+        fn forget_order<S: Get<u32>>(
+            maybe_orders: &mut Option<BoundedVec<T::AuctionId, S>>,
             auction_id: T::AuctionId,
-            seller_id: T::AccountId,
-            energy_quantity: T::Quantity,
-            starting_price: T::Price,
-        },
-    }
+        ) {
+            let is_empty = match maybe_orders {
+                Some(orders) => {
+                    orders.retain(|order| *order != auction_id);
+                    orders.is_empty()
+                }
+                None => false,
+            };
 
-    //////////////////////
-    // Pallet errors   //
-    /////////////////////
-    // Errors inform users that something went wrong.
-    #[pallet::error]
-    pub enum Error<T, I = ()> {
-        AuctionDoesNotExist,
+            if is_empty {
+                *maybe_orders = None;
+            }
+        }
 
-        AuctionIsOver,
+        // Check governance has not paused the market
+        fn ensure_market_open() -> DispatchResult {
+            ensure!(!MarketPaused::<T, I>::get(), Error::<T, I>::MarketPaused);
+            Ok(())
+        }
 
-        InsuffficientAttachedDeposit,
-    }
+        // Check an account's reputation reaches `MinReputation`
+        fn ensure_reputation(who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                Reputation::<T, I>::get(who).score() >= T::MinReputation::get(),
+                Error::<T, I>::ReputationTooLow
+            );
+            Ok(())
+        }
 
-    ///////////////////////////
-    // Pallet extrinsics    //
-    //////////////////////////
-    #[pallet::call]
-    impl<T: Config<I>, I: 'static> Pallet<T, I> {
-        #[pallet::call_index(0)]
-        #[pallet::weight(100_000_000)]
-        pub fn new(
-            origin: OriginFor<T>,
-            energy_quantity: u128, // in KWH
-            starting_price: u128,  // in parachain native token
-            auction_period: u16,   // in minutes
+        // Update the track record of an account
+        fn note_reputation(who: &T::AccountId, update: impl FnOnce(&mut ReputationStats)) {
+            Reputation::<T, I>::mutate(who, update);
+        }
+
+        // A delivery window must be ordered and may not close before bidding does
+        fn ensure_delivery_window(
+            window: Option<&DeliveryWindow<BlockNumberFor<T>>>,
+            end_at: BlockNumberFor<T>,
         ) -> DispatchResult {
-            // Check that the extrinsic was signed by seller or return error.
-            let seller = ensure_signed(origin)?;
+            if let Some(window) = window {
+                ensure!(
+                    window.delivery_start <= window.delivery_end && window.delivery_end >= end_at,
+                    Error::<T, I>::InvalidDeliveryWindow
+                );
+            }
+            Ok(())
+        }
+
+        // Delivery window of an auction relisted now for `auction_period`, moved as late as its
+        // end so delivery still follows the close of bidding
+        fn relisted_window(
+            auction_data: &AuctionDataOf<T, I>,
+            auction_period: BlockNumberFor<T>,
+        ) -> Option<DeliveryWindow<BlockNumberFor<T>>> {
+            let window = auction_data.delivery_window?;
+            let requested_end_at =
+                <frame_system::Pallet<T>>::block_number().saturating_add(auction_period);
+            let end_at = Self::free_end_block(requested_end_at).unwrap_or(requested_end_at);
+            let shift = end_at.saturating_sub(auction_data.end_at);
+
+            Some(DeliveryWindow {
+                delivery_start: window.delivery_start.saturating_add(shift),
+                delivery_end: window.delivery_end.saturating_add(shift),
+            })
+        }
+
+        // Keep the winner's payment in escrow until it confirms delivery
+        fn await_delivery(
+            auction_data: &AuctionDataOf<T, I>,
+            window: DeliveryWindow<BlockNumberFor<T>>,
+            price: T::Price,
+        ) {
+            let buyer_id = auction_data.highest_bid.bidder.clone();
+            Deliveries::<T, I>::insert(
+                auction_data.auction_id,
+                &buyer_id,
+                Delivery {
+                    seller: auction_data.seller_id.clone(),
+                    quantity: auction_data.quantity,
+                    price,
+                    window,
+                    disputed: false,
+                },
+            );
+
+            Self::deposit_event(Event::DeliveryAwaited {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                buyer_id,
+                delivery_start: window.delivery_start,
+                delivery_end: window.delivery_end,
+            });
+        }
+
+        // Share of energy lost flowing from one zone to another, `None` when they are not linked
+        fn zone_loss(from: ZoneId, to: ZoneId) -> Option<Permill> {
+            if from == to {
+                return Some(Permill::zero());
+            }
+            ZoneTopology::<T, I>::get(from, to)
+        }
+
+        // Take an auction off the book, freeing its seller's open auction slot
+        fn remove_auction(auction_id: T::AuctionId) -> Option<AuctionDataOf<T, I>> {
+            let auction_data = Auctions::<T, I>::take(auction_id)?;
+            Self::release_auction_slot(&auction_data.seller_id);
+            Self::unindex_from_tier(auction_data.auction_category.level, auction_id);
+            Some(auction_data)
+        }
+
+        // Count a new listing against its seller's `MaxOpenAuctionsPerSeller`
+        fn open_auction_slot(seller: &T::AccountId) -> DispatchResult {
+            OpenAuctionCounts::<T, I>::try_mutate(seller, |count| {
+                ensure!(
+                    *count < T::MaxOpenAuctionsPerSeller::get(),
+                    Error::<T, I>::TooManyOpenAuctions
+                );
+                *count += 1;
+                Ok(())
+            })
+        }
+
+        // Index a new listing in its tier, failing once the tier holds `MaxAuctionsPerTier`
+        fn index_in_tier(tier: u32, auction_id: T::AuctionId) -> DispatchResult {
+            TierAuctions::<T, I>::try_mutate(tier, |auctions| {
+                auctions
+                    .try_push(auction_id)
+                    .map_err(|_| Error::<T, I>::TierFull.into())
+            })
+        }
+
+        // Drop an auction from the index of its tier, removing the entry once empty
+        fn unindex_from_tier(tier: u32, auction_id: T::AuctionId) {
+            TierAuctions::<T, I>::mutate_exists(tier, |maybe_auctions| {
+                if let Some(auctions) = maybe_auctions {
+                    auctions.retain(|id| *id != auction_id);
+                    if auctions.is_empty() {
+                        *maybe_auctions = None;
+                    }
+                }
+            });
+        }
+
+        // Free an open auction slot of a seller, removing the entry once empty
+        fn release_auction_slot(seller: &T::AccountId) {
+            OpenAuctionCounts::<T, I>::mutate_exists(seller, |maybe_count| {
+                *maybe_count = maybe_count
+                    .map(|count| count.saturating_sub(1))
+                    .filter(|count| *count > 0);
+            });
+        }
+
+        // Bidders already holding a bid on the auction keep their slot, new ones need a free one
+        fn ensure_bid_slot(auction_id: T::AuctionId, buyer: &T::AccountId) -> DispatchResult {
+            ensure!(
+                Bids::<T, I>::contains_key(auction_id, buyer)
+                    || OpenBidCounts::<T, I>::get(buyer) < T::MaxOpenBidsPerBuyer::get(),
+                Error::<T, I>::TooManyOpenBids
+            );
+            Ok(())
+        }
+
+        // Count a new bid record of a buyer against `MaxOpenBidsPerBuyer`
+        fn open_bid_slot(buyer: &T::AccountId) {
+            OpenBidCounts::<T, I>::mutate(buyer, |count| *count = count.saturating_add(1));
+        }
+
+        // Free an open bid slot of a buyer, removing the entry once empty
+        fn release_bid_slot(buyer: &T::AccountId) {
+            OpenBidCounts::<T, I>::mutate_exists(buyer, |maybe_count| {
+                *maybe_count = maybe_count
+                    .map(|count| count.saturating_sub(1))
+                    .filter(|count| *count > 0);
+            });
+        }
+
+        // Remove a closed auction from the orders of its seller and bidders
+        fn untrack_auction(auction_data: &AuctionDataOf<T, I>) {
+            let auction_id = auction_data.auction_id;
+
+            SellOrdersOf::<T, I>::mutate_exists(&auction_data.seller_id, |maybe_orders| {
+                Self::forget_order(maybe_orders, auction_id)
+            });
+
+            for bidder in Bids::<T, I>::iter_key_prefix(auction_id) {
+                BuyOrdersOf::<T, I>::mutate_exists(&bidder, |maybe_orders| {
+                    Self::forget_order(maybe_orders, auction_id)
+                });
+            }
+        }
+
+        // Close a live auction without a match, unwinding its seller's and bidders' state
+        fn cancel_auction(mut auction_data: AuctionDataOf<T, I>) {
+            // Close auction
+            Self::transition(&mut auction_data, AuctionStatus::Cancelled);
+
+            // Remove auction from global auctions
+            Self::remove_auction(auction_data.auction_id);
+            AuctionSummaries::<T, I>::remove(auction_data.auction_id);
+            Self::record_activity(
+                ActivityKind::Canceled,
+                &auction_data,
+                auction_data.highest_bid.bid,
+            );
+
+            // Remove auction from seller's and bidders' orders
+            Self::untrack_auction(&auction_data);
+            Self::release_storage_deposit(&auction_data);
+            Self::release_auction_deposit(&auction_data);
+
+            // Remove auction from execution queue
+            AuctionsExecutionQueue::<T, I>::remove(auction_data.end_at, auction_data.auction_id);
+            PendingAuctions::<T, I>::remove(auction_data.start_at, auction_data.auction_id);
+
+            // Unwind every bidder's state
+            Self::refund_bidders(&auction_data);
+            let _ = SealedBids::<T, I>::clear_prefix(auction_data.auction_id, u32::MAX, None);
+            let _ = ProxyBids::<T, I>::clear_prefix(auction_data.auction_id, u32::MAX, None);
+            DutchAuctions::<T, I>::remove(auction_data.auction_id);
+            BuyoutPrices::<T, I>::remove(auction_data.auction_id);
+            AuctionAssets::<T, I>::remove(auction_data.auction_id);
+            RelistAttempts::<T, I>::remove(auction_data.auction_id);
+
+            // a cancelled buy leg rejects its order pair
+            if let Some(pair) = OrderPairs::<T, I>::get(auction_data.auction_id) {
+                Self::reject_order_pair(pair);
+            }
+
+            // Emit an event that the auction was canceled.
+            Self::deposit_event(Event::AuctionCanceled {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id,
+                energy_quantity: auction_data.quantity,
+                starting_price: auction_data.starting_bid.bid,
+            });
+        }
+
+        // Drop every trace of an auction taken out of storage, refunding its bidders and seller
+        fn purge_auction(auction_data: &AuctionDataOf<T, I>) {
+            let auction_id = auction_data.auction_id;
+            AuctionSummaries::<T, I>::remove(auction_id);
+            Self::untrack_auction(auction_data);
+            Self::release_storage_deposit(auction_data);
+            Self::release_auction_deposit(auction_data);
+
+            // whichever queue still points at it
+            AuctionsExecutionQueue::<T, I>::remove(auction_data.end_at, auction_id);
+            PendingAuctions::<T, I>::remove(auction_data.start_at, auction_id);
+            AcceptanceDeadlines::<T, I>::remove(auction_data.end_at, auction_id);
+            SettlementQueue::<T, I>::remove(auction_data.end_at, auction_id);
+
+            Self::refund_bidders(auction_data);
+            let _ = SealedBids::<T, I>::clear_prefix(auction_id, u32::MAX, None);
+            let _ = ProxyBids::<T, I>::clear_prefix(auction_id, u32::MAX, None);
+            DutchAuctions::<T, I>::remove(auction_id);
+            BuyoutPrices::<T, I>::remove(auction_id);
+            AuctionAssets::<T, I>::remove(auction_id);
+            RelistAttempts::<T, I>::remove(auction_id);
+
+            // either leg of an order pair rejects it
+            let pair = OrderPairs::<T, I>::get(auction_id).or_else(|| {
+                PairedSellLegs::<T, I>::get(auction_id).and_then(OrderPairs::<T, I>::get)
+            });
+            if let Some(pair) = pair {
+                Self::reject_order_pair(pair);
+            }
+        }
+
+        // Refund the bids of every bidder of a closed auction
+        fn refund_bidders(auction_data: &AuctionDataOf<T, I>) {
+            // records hold the best bid of each bidder
+            for (bidder, record) in Bids::<T, I>::drain_prefix(auction_data.auction_id) {
+                Self::release_bid_slot(&bidder);
+                Self::refund_bid(auction_data.auction_id, bidder, record.bid);
+            }
+        }
+
+        // Record that a bid is refunded to its bidder
+        fn refund_bid(auction_id: T::AuctionId, bidder: T::AccountId, amount: T::Price) {
+            Escrow::<T, I>::release(auction_id, &bidder);
+            Self::set_bid_outcome(&bidder, auction_id, BidOutcome::Refunded);
+            Self::notify(&bidder, auction_id, Outcome::RefundReady { amount });
+
+            Self::deposit_event(Event::BidRefunded {
+                auction_id,
+                bidder,
+                amount,
+            });
+        }
+
+        // Drop the leading bid of an ended auction and lead with the next highest one,
+        // returns false when no bid is left
+        fn drop_leading_bid(auction_data: &mut AuctionDataOf<T, I>) -> bool {
+            let leading = auction_data.highest_bid.clone();
+            Bids::<T, I>::remove(auction_data.auction_id, &leading.bidder);
+            Self::release_bid_slot(&leading.bidder);
+            auction_data.bid_count = auction_data.bid_count.saturating_sub(1);
+            Self::refund_bid(auction_data.auction_id, leading.bidder, leading.bid);
+
+            // remaining records are leading bids, each qualifies
+            let next = Bids::<T, I>::iter_prefix(auction_data.auction_id)
+                .max_by_key(|(_, record)| record.bid);
+            match next {
+                Some((bidder, record)) => {
+                    auction_data.highest_bid = BidOf::<T, I> {
+                        bidder,
+                        bid: record.bid,
+                        memo: record.memo,
+                    };
+                    true
+                }
+                None => {
+                    auction_data.highest_bid = auction_data.starting_bid.clone();
+                    false
+                }
+            }
+        }
+
+        // Whether a winner can pay a clearing price from its escrowed bid and free funds
+        fn can_settle(auction_id: T::AuctionId, buyer: &T::AccountId, price: T::Price) -> bool {
+            // paper markets hold no funds to check
+            if T::Simulation::get() {
+                return true;
+            }
+
+            let held = Escrow::<T, I>::held(auction_id, buyer);
+            let price: u128 = price.unique_saturated_into();
+            let owed = T::Price::from(price.saturating_sub(held));
+            held >= price
+                || match AuctionAssets::<T, I>::get(auction_id) {
+                    Some(asset) => Self::can_pay_in(asset, buyer, owed),
+                    None => Self::can_pay(buyer, owed),
+                }
+        }
+
+        // Whether a buyer has a transferable balance of an asset for a clearing price
+        fn can_pay_in(asset: AssetIdOf<T, I>, buyer: &T::AccountId, price: T::Price) -> bool {
+            let price: u128 = price.unique_saturated_into();
+            T::Assets::reducible_balance(asset, buyer, false)
+                >= BalanceOf::<T, I>::unique_saturated_from(price)
+        }
+
+        // Whether a buyer has free, withdrawable funds for a clearing price
+        fn can_pay(buyer: &T::AccountId, price: T::Price) -> bool {
+            let price: u128 = price.unique_saturated_into();
+            let amount = BalanceOf::<T, I>::unique_saturated_from(price);
+            let free = T::Currency::free_balance(buyer);
+
+            free >= amount
+                && T::Currency::ensure_can_withdraw(
+                    buyer,
+                    amount,
+                    WithdrawReasons::TRANSFER,
+                    free.saturating_sub(amount),
+                )
+                .is_ok()
+        }
+
+        // Archive an auction that ended without a match so the seller can relist it
+        fn expire_unsold(mut auction_data: AuctionDataOf<T, I>, now: BlockNumberFor<T>) {
+            Self::transition(&mut auction_data, AuctionStatus::Expired);
+            Self::refund_bidders(&auction_data);
+            Self::note_unsold(&auction_data);
+            Self::notify(
+                &auction_data.seller_id,
+                auction_data.auction_id,
+                Outcome::ExpiredUnsold,
+            );
+            Self::deposit_event(Event::AuctionExpiredUnsold {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                energy_quantity: auction_data.quantity,
+                starting_price: auction_data.starting_bid.bid,
+                expired_at: now,
+            });
+            Self::note_closed_without_match(&auction_data, now);
+
+            UnsoldAuctions::<T, I>::insert(auction_data.auction_id, auction_data);
+        }
+
+        // Report an auction that closed without a match to indexers
+        fn note_closed_without_match(auction_data: &AuctionDataOf<T, I>, now: BlockNumberFor<T>) {
+            Self::deposit_event(Event::AuctionClosedWithoutMatch {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                energy_quantity: auction_data.quantity,
+                auction_category: auction_data.auction_category.clone(),
+                bid_count: auction_data.bid_count,
+                end_at: auction_data.end_at,
+                closed_at: now,
+            });
+        }
+
+        // End an auction nobody bid on, listing it again at `RelistDiscount` off its starting
+        // price until it was relisted `MaxRelistAttempts` times and archiving it otherwise
+        fn end_without_bids(
+            mut auction_data: AuctionDataOf<T, I>,
+            relist_attempts: u32,
+            now: BlockNumberFor<T>,
+        ) {
+            if relist_attempts >= T::MaxRelistAttempts::get() {
+                Self::expire_unsold(auction_data, now);
+                return;
+            }
 
-            // get current_auction_id
-            let current_auction_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+            // a relist that cannot be created leaves the auction archived for a manual relist
+            let starting_price: u128 = auction_data.starting_bid.bid.unique_saturated_into();
+            let starting_price =
+                starting_price.saturating_sub(T::RelistDiscount::get() * starting_price);
+            let relisted = frame_support::storage::with_storage_layer(|| {
+                Self::release_storage_deposit(&auction_data);
+                Self::release_auction_deposit(&auction_data);
+                Self::create_auction(
+                    auction_data.seller_id.clone(),
+                    auction_data.quantity,
+                    T::Price::from(starting_price),
+                    auction_data.auction_period,
+                    auction_data.metadata.clone(),
+                    auction_data.min_bidders,
+                    auction_data.match_rule,
+                    auction_data.settlement_lag,
+                    auction_data.reserve_price,
+                    None,
+                    auction_data.zone,
+                    Self::relisted_window(&auction_data, auction_data.auction_period),
+                    AuctionAssets::<T, I>::take(auction_data.auction_id),
+                )
+            });
+            let auction_id = match relisted {
+                Ok(auction_id) => auction_id,
+                Err(_) => {
+                    Self::expire_unsold(auction_data, now);
+                    return;
+                }
+            };
+            RelistAttempts::<T, I>::insert(auction_id, relist_attempts.saturating_add(1));
 
-            // Calculate auction period
-            // convert minutes to seconds and
-            // divide by 6 (assumming each blocktime is 6 seconds)
-            let auction_period_in_block_number = (auction_period.checked_mul(60).unwrap())
-                .checked_div(6)
-                .unwrap()
-                .into();
+            Self::transition(&mut auction_data, AuctionStatus::Expired);
+            Self::deposit_event(Event::AuctionExpiredUnsold {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                energy_quantity: auction_data.quantity,
+                starting_price: auction_data.starting_bid.bid,
+                expired_at: now,
+            });
+            Self::note_closed_without_match(&auction_data, now);
+            Self::deposit_event(Event::AuctionRelisted {
+                previous_auction_id: auction_data.auction_id,
+                auction_id,
+                seller_id: auction_data.seller_id,
+            });
+        }
 
-            // Get current block number from the FRAME System pallet.
-            let starting_block_number = <frame_system::Pallet<T>>::block_number();
+        // Whether the leading bid of an auction is below its reserve, by price per unit
+        fn misses_reserve(auction_data: &AuctionDataOf<T, I>) -> bool {
+            let reserve = match auction_data.reserve_price {
+                Some(reserve) => reserve.price,
+                None => return false,
+            };
+            let quantity =
+                Bids::<T, I>::get(auction_data.auction_id, &auction_data.highest_bid.bidder)
+                    .and_then(|record| record.quantity)
+                    .unwrap_or(auction_data.quantity);
 
-            let ending_block_number = starting_block_number + auction_period_in_block_number;
+            Self::unit_price_cmp(
+                (quantity, auction_data.highest_bid.bid),
+                (auction_data.quantity, reserve),
+            ) == core::cmp::Ordering::Less
+        }
 
-            // Create starting bid
-            let starting_bid = Bid::<T::AccountId, T::Price> {
-                bidder: seller.clone(),
-                bid: T::Price::from(starting_price),
+        // End an auction whose reserve was not met, listing it again for another period
+        // when the seller asked for it and archiving it otherwise
+        fn end_below_reserve(mut auction_data: AuctionDataOf<T, I>, now: BlockNumberFor<T>) {
+            let reserve = match auction_data.reserve_price {
+                Some(reserve) => reserve,
+                None => return,
             };
+            Self::transition(&mut auction_data, AuctionStatus::Expired);
+            Self::refund_bidders(&auction_data);
 
-            // Categorize auction
-            let category;
-            if energy_quantity < 5 {
-                category = Tier::default()
+            // a relist that cannot be created leaves the auction archived for a manual relist
+            let relisted_as = if reserve.relist {
+                frame_support::storage::with_storage_layer(|| {
+                    Self::release_storage_deposit(&auction_data);
+                    Self::release_auction_deposit(&auction_data);
+                    Self::create_auction(
+                        auction_data.seller_id.clone(),
+                        auction_data.quantity,
+                        auction_data.starting_bid.bid,
+                        auction_data.auction_period,
+                        auction_data.metadata.clone(),
+                        auction_data.min_bidders,
+                        auction_data.match_rule,
+                        auction_data.settlement_lag,
+                        auction_data.reserve_price,
+                        None,
+                        auction_data.zone,
+                        Self::relisted_window(&auction_data, auction_data.auction_period),
+                        AuctionAssets::<T, I>::take(auction_data.auction_id),
+                    )
+                })
+                .ok()
             } else {
-                category = Tier { level: 2 }
-            }
-
-            // Create auction data
-            let auction_data = AuctionData {
-                auction_id: current_auction_id,
-                seller_id: seller.clone(),
-                quantity: T::Quantity::from(energy_quantity),
-                starting_bid: starting_bid.clone(),
-                bids: vec![starting_bid.clone()],
-                auction_period: auction_period_in_block_number,
-                auction_status: AuctionStatus::default(),
-                start_at: starting_block_number,
-                end_at: ending_block_number,
-                highest_bid: starting_bid,
-                auction_category: category,
+                None
             };
 
-            // Get seller's auction information
-            let mut seller_auction_info =
-                AuctionsOf::<T, I>::get(seller.clone()).unwrap_or(AuctionInfo {
-                    participant_id: None,
-                    party_type: PartyType::Seller,
-                    auctions: vec![],
-                });
+            Self::deposit_event(Event::AuctionUnsold {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                highest_bid: auction_data.highest_bid.bid,
+                reserve_price: reserve.price,
+                relisted_as,
+            });
 
-            // Ensure cached autions are less than 5
-            // remove oldest auction
-            if seller_auction_info.auctions.len() > 5 {
-                seller_auction_info.auctions.pop();
+            if relisted_as.is_none() {
+                Self::expire_unsold(auction_data, now);
+            } else {
+                Self::note_closed_without_match(&auction_data, now);
             }
+        }
+
+        // Match or expire an auction and resolve the order pair it is a leg of,
+        // returning the settlements when it was matched
+        fn on_auction_ended(auction_id: T::AuctionId) -> Vec<SettlementResultOf<T, I>> {
+            let settlements = Self::match_or_expire(auction_id);
+            Self::resolve_order_pair(auction_id, <frame_system::Pallet<T>>::block_number());
+            settlements
+        }
 
-            // Update seller's auctions
-            seller_auction_info.auctions.push(auction_data.clone());
+        // Settle or unwind the order pair an ended auction is a leg of
+        fn resolve_order_pair(auction_id: T::AuctionId, now: BlockNumberFor<T>) {
+            // buy leg ended, it waits for the sell leg only when bought by the pair's owner
+            if let Some(pair) = OrderPairs::<T, I>::get(auction_id) {
+                let bought = AwaitingSettlement::<T, I>::get(auction_id)
+                    .map_or(false, |auction| auction.highest_bid.bidder == pair.owner);
+                if !bought || !Auctions::<T, I>::contains_key(pair.sell_auction_id) {
+                    Self::reject_order_pair(pair);
+                }
+                return;
+            }
 
-            // Store seller's auction into storage
-            seller_auction_info = AuctionInfo {
-                participant_id: Some(seller.clone()),
-                party_type: PartyType::Seller,
-                auctions: seller_auction_info.auctions,
+            // sell leg ended, the buy leg settles only when the sell leg was matched
+            let buy_auction_id = match PairedSellLegs::<T, I>::take(auction_id) {
+                Some(buy_auction_id) => buy_auction_id,
+                None => return,
+            };
+            let pair = match OrderPairs::<T, I>::take(buy_auction_id) {
+                Some(pair) => pair,
+                None => return,
+            };
+            let buy_leg = match Self::take_awaiting_settlement(buy_auction_id) {
+                Some(buy_leg) => buy_leg,
+                None => return,
             };
-            AuctionsOf::<T, I>::insert(&seller, seller_auction_info);
 
-            // Add auction to execution queue
-            AuctionsExecutionQueue::<T, I>::insert(
-                auction_data.end_at,
-                auction_data.auction_id,
-                (),
-            );
+            if UnsoldAuctions::<T, I>::contains_key(auction_id) {
+                Self::fail_buy_leg(buy_leg, now);
+                Self::deposit_event(Event::OrderPairRejected {
+                    owner: pair.owner,
+                    buy_auction_id,
+                    sell_auction_id: auction_id,
+                });
+            } else if Self::complete_settlement(buy_leg.clone(), now).is_err() {
+                // the owner could not pay for its purchase
+                Self::fail_over(buy_leg, now);
+                Self::deposit_event(Event::OrderPairRejected {
+                    owner: pair.owner,
+                    buy_auction_id,
+                    sell_auction_id: auction_id,
+                });
+            } else {
+                Self::deposit_event(Event::OrderPairSettled {
+                    owner: pair.owner,
+                    buy_auction_id,
+                    sell_auction_id: auction_id,
+                });
+            }
+        }
 
-            // Store globalauction to storage
-            Auctions::<T, I>::insert(&auction_data.auction_id, auction_data.clone());
+        // Unwind both legs of an order pair
+        fn reject_order_pair(pair: OrderPair<T::AccountId, T::AuctionId>) {
+            OrderPairs::<T, I>::remove(pair.buy_auction_id);
+            PairedSellLegs::<T, I>::remove(pair.sell_auction_id);
 
-            // update auction id
-            let next_id = current_auction_id + T::AuctionId::from(1u64);
-            AuctionIndex::<T, I>::set(Some(next_id));
+            // the owner's purchase waiting for the sell leg falls to the next bidder
+            if let Some(buy_leg) = AwaitingSettlement::<T, I>::get(pair.buy_auction_id) {
+                if buy_leg.highest_bid.bidder == pair.owner {
+                    Self::take_awaiting_settlement(pair.buy_auction_id);
+                    Self::fail_buy_leg(buy_leg, <frame_system::Pallet<T>>::block_number());
+                }
+            }
+            if let Some(sell_leg) = Auctions::<T, I>::get(pair.sell_auction_id) {
+                Self::cancel_auction(sell_leg);
+            }
 
-            // Emit an event that the auction was created.
-            Self::deposit_event(Event::AuctionCreated {
-                auction_id: auction_data.auction_id,
-                seller_id: seller,
-                energy_quantity: auction_data.quantity,
-                starting_price: auction_data.starting_bid.bid,
+            Self::deposit_event(Event::OrderPairRejected {
+                owner: pair.owner,
+                buy_auction_id: pair.buy_auction_id,
+                sell_auction_id: pair.sell_auction_id,
             });
+        }
 
-            Ok(())
+        // Settle a failed buy leg with the next highest bidder able to pay, or expire it
+        fn fail_buy_leg(mut auction_data: AuctionDataOf<T, I>, now: BlockNumberFor<T>) {
+            if Self::drop_leading_bid(&mut auction_data) {
+                Self::settle_solvent_winner(auction_data, now);
+            } else {
+                Self::expire_unsold(auction_data, now);
+            }
         }
 
-        #[pallet::call_index(1)]
-        #[pallet::weight(100_000_000)]
-        pub fn cancel(origin: OriginFor<T>, auction_id: T::AuctionId) -> DispatchResult {
-            // Check that the extrinsic was signed by seller or return error.
-            let _signer = ensure_signed(origin)?;
+        // Match or expire an auction, returning the settlement when it was matched
+        fn match_or_expire(auction_id: T::AuctionId) -> Vec<SettlementResultOf<T, I>> {
+            // Get auction data, an auction cancelled by an earlier execution has nothing to match
+            let auction_data = match Self::remove_auction(auction_id) {
+                Some(auction_data) => auction_data,
+                None => return Vec::new(),
+            };
+            AuctionSummaries::<T, I>::remove(auction_id);
+            let now = <frame_system::Pallet<T>>::block_number();
 
-            // Check auction is exist
-            ensure!(
-                Auctions::<T, I>::contains_key(auction_id),
-                Error::<T, I>::AuctionDoesNotExist
-            );
+            // Remove auction from seller's and bidders' orders
+            Self::untrack_auction(&auction_data);
 
-            // Get auction from global auction
-            let mut auction_data = Auctions::<T, I>::get(auction_id).expect("data of auction");
+            // sealed bids left unrevealed are not matched, a Dutch ask or buyout nobody took ends
+            let _ = SealedBids::<T, I>::clear_prefix(auction_id, u32::MAX, None);
+            let _ = ProxyBids::<T, I>::clear_prefix(auction_id, u32::MAX, None);
+            DutchAuctions::<T, I>::remove(auction_id);
+            BuyoutPrices::<T, I>::remove(auction_id);
+            let relist_attempts = RelistAttempts::<T, I>::take(auction_id);
 
-            // Check auction is live
-            ensure!(
-                matches!(auction_data.auction_status, AuctionStatus::Open),
-                Error::<T, I>::AuctionIsOver
-            );
+            // seller still holds the highest bid, nothing to match
+            if auction_data.highest_bid.bidder == auction_data.seller_id {
+                Self::end_without_bids(auction_data, relist_attempts, now);
+                return Vec::new();
+            }
 
-            // Close auction
-            auction_data.auction_status = AuctionStatus::Closed;
+            // clearing against too few bidders is not valid
+            let bidders = auction_data.bid_count;
+            if bidders < auction_data.min_bidders {
+                Self::deposit_event(Event::AuctionInsufficientBidders {
+                    auction_id: auction_data.auction_id,
+                    bidders,
+                    min_bidders: auction_data.min_bidders,
+                });
 
-            // Remove auction from global auctions
-            Auctions::<T, I>::remove(auction_data.auction_id);
+                Self::refund_bidders(&auction_data);
+                Self::expire_unsold(auction_data, now);
+                return Vec::new();
+            }
 
-            // Get seller's auction info
-            let mut sellers_auction_info =
-                AuctionsOf::<T, I>::get(auction_data.seller_id.clone()).expect("info of seller");
+            // leading bid below the seller's reserve does not sell
+            if Self::misses_reserve(&auction_data) {
+                Self::end_below_reserve(auction_data, now);
+                return Vec::new();
+            }
 
-            // get matching auction(s)
-            let index = sellers_auction_info
-                .auctions
-                .iter()
-                .position(|x| x.auction_id.clone() == auction_id)
-                .expect("index of auction");
+            // clear with the seller's rule or the engine selected for the auction's tier
+            match Self::algorithm_of(&auction_data) {
+                // a single seller's quantity goes whole to one bidder paying its own bid
+                MatchingAlgorithm::SingleWinner
+                | MatchingAlgorithm::PayAsBid
+                | MatchingAlgorithm::AllOrNothing
+                | MatchingAlgorithm::Vickrey => Self::settle_solvent_winner(auction_data, now),
+                // quantity is split across bids in price-time priority
+                MatchingAlgorithm::PartialFill => Self::settle_partial_fills(auction_data, now),
+                // only revealed bids lead, the highest one pays its own bid
+                MatchingAlgorithm::SealedBid => Self::settle_solvent_winner(auction_data, now),
+                // uniform-price auctions only sell in their tier's clearings
+                MatchingAlgorithm::UniformPrice => {
+                    Self::expire_unsold(auction_data, now);
+                    Vec::new()
+                }
+            }
+        }
 
-            // Remove auction from seller's auctions
-            sellers_auction_info.auctions.remove(index);
-            AuctionsOf::<T, I>::insert(auction_data.seller_id.clone(), sellers_auction_info);
+        // Settle with the highest bidder able to pay, skipping those who cannot
+        fn settle_solvent_winner(
+            mut auction_data: AuctionDataOf<T, I>,
+            now: BlockNumberFor<T>,
+        ) -> Vec<SettlementResultOf<T, I>> {
+            while !Self::can_settle(
+                auction_data.auction_id,
+                &auction_data.highest_bid.bidder,
+                auction_data.highest_bid.bid,
+            ) {
+                let skipped = auction_data.highest_bid.bidder.clone();
+                Self::skip_winner(auction_data.auction_id, skipped);
 
-            // Remove auction from execution queue
-            AuctionsExecutionQueue::<T, I>::remove(auction_data.end_at, auction_data.auction_id);
+                if !Self::drop_leading_bid(&mut auction_data) {
+                    Self::expire_unsold(auction_data, now);
+                    return Vec::new();
+                }
+            }
 
-            // Emit an event that the auction was canceled.
-            Self::deposit_event(Event::AuctionCanceled {
-                auction_id: auction_data.auction_id,
-                seller_id: auction_data.seller_id,
-                energy_quantity: auction_data.quantity,
-                starting_price: auction_data.starting_bid.bid,
-            });
+            // the next solvent bid may be below the reserve
+            if Self::misses_reserve(&auction_data) {
+                Self::end_below_reserve(auction_data, now);
+                return Vec::new();
+            }
 
-            Ok(())
+            Self::settle_or_await(auction_data, now)
         }
 
-        #[pallet::call_index(2)]
-        #[pallet::weight(100_000_000)]
-        pub fn bid(origin: OriginFor<T>, auction_id: T::AuctionId, bid: u128) -> DispatchResult {
-            // Check that the extrinsic was signed by buyer or return error.
-            let buyer_id = ensure_signed(origin)?;
-
-            // Check auction is exist
-            ensure!(
-                Auctions::<T, I>::contains_key(auction_id),
-                Error::<T, I>::AuctionDoesNotExist
-            );
+        // Pass over a matched buyer whose payment failed, settling with the next bidder able
+        // to pay or expiring the auction once none is left
+        fn fail_over(
+            mut auction_data: AuctionDataOf<T, I>,
+            now: BlockNumberFor<T>,
+        ) -> Vec<SettlementResultOf<T, I>> {
+            let skipped = auction_data.highest_bid.bidder.clone();
+            Self::skip_winner(auction_data.auction_id, skipped);
 
-            // Get auction from global auction
-            let mut auction_data = Auctions::<T, I>::get(auction_id).expect("data of auction");
+            if Self::drop_leading_bid(&mut auction_data) {
+                Self::settle_solvent_winner(auction_data, now)
+            } else {
+                Self::expire_unsold(auction_data, now);
+                Vec::new()
+            }
+        }
 
-            // Check auction is live
-            ensure!(
-                matches!(auction_data.auction_status, AuctionStatus::Open),
-                Error::<T, I>::AuctionIsOver
-            );
+        // Matching algorithm an auction clears with, its seller's rule or its tier's engine
+        fn algorithm_of(auction_data: &AuctionDataOf<T, I>) -> MatchingAlgorithm {
+            auction_data
+                .match_rule
+                .unwrap_or_else(|| Self::matching_algorithm(auction_data.auction_category.level))
+        }
 
-            // Create new bid
-            let new_bid = Bid::<T::AccountId, T::Price> {
-                bidder: buyer_id.clone(),
-                bid: bid.into(),
-            };
+        // Count and announce a winner skipped for being unable to pay
+        fn skip_winner(auction_id: T::AuctionId, buyer_id: T::AccountId) {
+            SkippedWinners::<T, I>::mutate(&buyer_id, |skips| *skips = skips.saturating_add(1));
+            Self::deposit_event(Event::WinnerSkipped {
+                auction_id,
+                buyer_id,
+            });
+        }
 
-            // check if bid is highest bid
-            if new_bid.bid > auction_data.bids[0].bid {
-                // add to top of auction bids
-                auction_data.bids.insert(0, new_bid.clone());
-                auction_data.highest_bid = new_bid.clone();
-                Auctions::<T, I>::insert(auction_data.auction_id, auction_data.clone());
+        // Split the quantity of an ended auction across its bids in price-time priority,
+        // each buyer paying its bid pro rata to the part it receives
+        fn settle_partial_fills(
+            auction_data: AuctionDataOf<T, I>,
+            now: BlockNumberFor<T>,
+        ) -> Vec<SettlementResultOf<T, I>> {
+            let auction_id = auction_data.auction_id;
+            let mut bids: Vec<(T::AccountId, BidRecordOf<T, I>)> =
+                Bids::<T, I>::drain_prefix(auction_id).collect();
+            for (bidder, _) in bids.iter() {
+                Self::release_bid_slot(bidder);
             }
 
-            // get buyer's auction information
-            let buyer_auction_info = AuctionsOf::<T, I>::get(buyer_id.clone());
+            // best price per unit first, earliest first among equals
+            bids.sort_by(|(_, a), (_, b)| {
+                Self::unit_price_cmp(
+                    (b.quantity.unwrap_or(auction_data.quantity), b.bid),
+                    (a.quantity.unwrap_or(auction_data.quantity), a.bid),
+                )
+                .then(a.placed_at.cmp(&b.placed_at))
+            });
+
+            let mut remaining = auction_data.quantity;
+            let mut fills: Vec<Fill<T::AccountId, T::Price, T::Quantity>> = Vec::new();
+            let mut settlements: Vec<SettlementResultOf<T, I>> = Vec::new();
+            for (bidder, record) in bids {
+                let wanted = record.quantity.unwrap_or(auction_data.quantity);
+                let filled = wanted.min(remaining);
+                let price = {
+                    let (bid, filled, wanted): (u128, u128, u128) = (
+                        record.bid.unique_saturated_into(),
+                        filled.unique_saturated_into(),
+                        wanted.unique_saturated_into(),
+                    );
+                    T::Price::from(bid.saturating_mul(filled) / wanted.max(1))
+                };
 
-            match buyer_auction_info {
-                // if buyer info already initialized, update info
-                Some(mut auction_info) => {
-                    for (index, auction) in auction_info.auctions.clone().into_iter().enumerate() {
-                        // Ensure auction is within limit
-                        if auction_info.auctions.len() >= 5 {
-                            auction_info.auctions.pop();
-                        }
+                // bids below the seller's reserve per unit do not fill
+                let below_reserve = auction_data.reserve_price.map_or(false, |reserve| {
+                    Self::unit_price_cmp(
+                        (wanted, record.bid),
+                        (auction_data.quantity, reserve.price),
+                    ) == core::cmp::Ordering::Less
+                });
+                if filled.is_zero()
+                    || below_reserve
+                    || fills.len() >= T::MaxFillsPerAuction::get() as usize
+                {
+                    Self::refund_bid(auction_id, bidder, record.bid);
+                    continue;
+                }
+                if !Self::can_settle(auction_id, &bidder, price) {
+                    Self::skip_winner(auction_id, bidder.clone());
+                    Self::refund_bid(auction_id, bidder, record.bid);
+                    continue;
+                }
 
-                        // get matching auction
-                        if auction.auction_id == auction_id {
-                            // insert new auction
-                            auction_info.auctions.insert(index, auction_data.clone());
-
-                            // update runtime storage
-                            AuctionsOf::<T, I>::insert(
-                                &buyer_id,
-                                AuctionInfo {
-                                    participant_id: Some(buyer_id.clone()),
-                                    party_type: PartyType::Seller,
-                                    auctions: auction_info.auctions.clone(),
-                                },
-                            )
-                        }
+                let mut fill = auction_data.clone();
+                fill.quantity = filled;
+                fill.highest_bid = BidOf::<T, I> {
+                    bidder: bidder.clone(),
+                    bid: price,
+                    memo: record.memo,
+                };
+                // a bidder whose payment fails leaves its part to the bids after it
+                match Self::settle_single_winner(fill, now) {
+                    Ok(settlement) => settlements.push(settlement),
+                    Err(_) => {
+                        Self::skip_winner(auction_id, bidder.clone());
+                        Self::refund_bid(auction_id, bidder, record.bid);
+                        continue;
                     }
                 }
-                // initialized and update information
-                None => {
-                    // Assign default information
-                    let mut auction_info =
-                        AuctionsOf::<T, I>::get(buyer_id.clone()).unwrap_or(AuctionInfo {
-                            participant_id: None,
-                            party_type: PartyType::Seller,
-                            auctions: vec![],
-                        });
 
-                    // Add auction to buyers information
-                    auction_info.auctions.push(auction_data.clone());
-
-                    // update runtime storage
-                    AuctionsOf::<T, I>::insert(
-                        &buyer_id,
-                        AuctionInfo {
-                            participant_id: Some(buyer_id.clone()),
-                            party_type: PartyType::Seller,
-                            auctions: auction_info.auctions.clone(),
-                        },
-                    )
-                }
+                remaining = remaining.saturating_sub(filled);
+                fills.push(Fill {
+                    buyer: bidder,
+                    quantity: filled,
+                    price,
+                });
             }
 
-            // Get seller's auction information
-            let mut seller_auction_info =
-                AuctionsOf::<T, I>::get(auction_data.clone().seller_id).expect("info of seller");
+            // the unfilled rest can be relisted by the seller
+            if !remaining.is_zero() {
+                let mut rest = auction_data;
+                rest.quantity = remaining;
+                Self::expire_unsold(rest, now);
+            }
+            if !fills.is_empty() {
+                AuctionFills::<T, I>::insert(auction_id, BoundedVec::truncate_from(fills));
+            }
+            settlements
+        }
 
-            // Update seller's auction information
-            for (index, auction) in seller_auction_info.auctions.clone().into_iter().enumerate() {
-                // Ensure auction is within limit
-                if seller_auction_info.auctions.len() >= 5 {
-                    seller_auction_info.auctions.pop();
-                }
+        // Settle a matched auction, or wait for its buyer to accept when a window is set
+        fn settle_or_await(
+            mut auction_data: AuctionDataOf<T, I>,
+            now: BlockNumberFor<T>,
+        ) -> Vec<SettlementResultOf<T, I>> {
+            // a paired buy leg bought by its owner waits for the sell leg to clear
+            let paired = OrderPairs::<T, I>::get(auction_data.auction_id)
+                .map_or(false, |pair| pair.owner == auction_data.highest_bid.bidder);
+            if paired {
+                Self::deposit_matched(&mut auction_data, now);
+                Self::insert_awaiting_settlement(auction_data);
+                return Vec::new();
+            }
 
-                // get matching auction
-                if auction.auction_id == auction_id {
-                    // insert new auction
-                    seller_auction_info
-                        .auctions
-                        .insert(index, auction_data.clone());
+            if T::AcceptanceWindow::get().is_zero() && auction_data.settlement_lag.is_zero() {
+                return match Self::settle_single_winner(auction_data.clone(), now) {
+                    Ok(settlement) => Vec::from([settlement]),
+                    Err(_) => Self::fail_over(auction_data, now),
+                };
+            }
 
-                    // update runtime storage
-                    AuctionsOf::<T, I>::insert(
-                        &buyer_id,
-                        AuctionInfo {
-                            participant_id: Some(buyer_id.clone()),
-                            party_type: PartyType::Seller,
-                            auctions: seller_auction_info.auctions.clone(),
-                        },
-                    )
-                }
+            Self::deposit_matched(&mut auction_data, now);
+            if T::AcceptanceWindow::get().is_zero() {
+                Self::schedule_settlement(auction_data, now);
+            } else {
+                Self::await_acceptance(auction_data, now);
             }
+            Vec::new()
+        }
 
-            // Update global auction
-            Auctions::<T, I>::insert(&auction_data.auction_id, auction_data.clone());
+        // Park a matched auction until its seller's settlement lag has passed
+        fn schedule_settlement(mut auction_data: AuctionDataOf<T, I>, now: BlockNumberFor<T>) {
+            let settle_at = now.saturating_add(auction_data.settlement_lag);
+            auction_data.end_at = settle_at;
 
-            // Emit an event that the bid was created.
-            Self::deposit_event(Event::AuctionBidAdded {
+            SettlementQueue::<T, I>::insert(settle_at, auction_data.auction_id, ());
+            Self::deposit_event(Event::SettlementScheduled {
                 auction_id: auction_data.auction_id,
-                seller_id: auction_data.seller_id,
-                energy_quantity: auction_data.quantity,
-                bid: new_bid,
+                buyer_id: auction_data.highest_bid.bidder.clone(),
+                settle_at,
             });
+            Self::insert_awaiting_settlement(auction_data);
+        }
 
-            Ok(())
+        // Park a matched auction until its buyer accepts or the window lapses
+        fn await_acceptance(mut auction_data: AuctionDataOf<T, I>, now: BlockNumberFor<T>) {
+            let accept_by = now.saturating_add(T::AcceptanceWindow::get());
+            auction_data.end_at = accept_by;
+
+            AcceptanceDeadlines::<T, I>::insert(accept_by, auction_data.auction_id, ());
+            Self::deposit_event(Event::AuctionAwaitingAcceptance {
+                auction_id: auction_data.auction_id,
+                buyer_id: auction_data.highest_bid.bidder.clone(),
+                accept_by,
+            });
+            Self::insert_awaiting_acceptance(auction_data);
         }
-    }
 
-    ///////////////////////
-    /// auction handler //
-    //////////////////////
-    impl<T: Config<I>, I: 'static> Pallet<T, I> {
-        fn on_auction_ended(auction_id: T::AuctionId) {
-            // Get auction data
-            let auction_data = Auctions::<T, I>::take(auction_id).unwrap();
-            let now = <frame_system::Pallet<T>>::block_number();
+        // Keep a matched auction until its buyer accepts, counted in the depths of its tier
+        fn insert_awaiting_acceptance(auction_data: AuctionDataOf<T, I>) {
+            TierQueueDepths::<T, I>::mutate(auction_data.auction_category.level, |depths| {
+                depths.0 = depths.0.saturating_add(1)
+            });
+            AwaitingAcceptance::<T, I>::insert(auction_data.auction_id, auction_data);
+        }
+
+        // Take an auction awaiting acceptance out of the depths of its tier
+        fn take_awaiting_acceptance(auction_id: T::AuctionId) -> Option<AuctionDataOf<T, I>> {
+            let auction_data = AwaitingAcceptance::<T, I>::take(auction_id)?;
+            TierQueueDepths::<T, I>::mutate(auction_data.auction_category.level, |depths| {
+                depths.0 = depths.0.saturating_sub(1)
+            });
+            Some(auction_data)
+        }
+
+        // Keep a matched auction until it settles, counted in the depths of its tier
+        fn insert_awaiting_settlement(auction_data: AuctionDataOf<T, I>) {
+            TierQueueDepths::<T, I>::mutate(auction_data.auction_category.level, |depths| {
+                depths.1 = depths.1.saturating_add(1)
+            });
+            AwaitingSettlement::<T, I>::insert(auction_data.auction_id, auction_data);
+        }
 
-            // emit event that auction is matched
+        // Take an auction awaiting settlement out of the depths of its tier
+        fn take_awaiting_settlement(auction_id: T::AuctionId) -> Option<AuctionDataOf<T, I>> {
+            let auction_data = AwaitingSettlement::<T, I>::take(auction_id)?;
+            TierQueueDepths::<T, I>::mutate(auction_data.auction_category.level, |depths| {
+                depths.1 = depths.1.saturating_sub(1)
+            });
+            Some(auction_data)
+        }
+
+        // Refund a buyer who did not accept in time and match the next highest bidder able
+        // to pay
+        fn lapse_acceptance(auction_id: T::AuctionId, now: BlockNumberFor<T>) {
+            let mut auction_data = match Self::take_awaiting_acceptance(auction_id) {
+                Some(auction_data) => auction_data,
+                None => return,
+            };
+
+            Self::deposit_event(Event::AcceptanceLapsed {
+                auction_id,
+                buyer_id: auction_data.highest_bid.bidder.clone(),
+            });
+
+            if Self::drop_leading_bid(&mut auction_data) {
+                Self::settle_solvent_winner(auction_data, now);
+            } else {
+                Self::expire_unsold(auction_data, now);
+            }
+        }
+
+        // Mark an auction matched with its highest bidder
+        fn deposit_matched(auction_data: &mut AuctionDataOf<T, I>, now: BlockNumberFor<T>) {
+            Self::transition(auction_data, AuctionStatus::Matched);
             Self::deposit_event(Event::AuctionMatched {
                 auction_id: auction_data.auction_id,
                 seller_id: auction_data.seller_id.clone(),
@@ -701,19 +8761,194 @@ pub mod pallet {
                 highest_bid: auction_data.highest_bid.clone(),
                 matched_at: now,
             });
+        }
+
+        // Settle an auction with its highest bidder, nothing is matched when it cannot pay
+        fn settle_single_winner(
+            mut auction_data: AuctionDataOf<T, I>,
+            now: BlockNumberFor<T>,
+        ) -> Result<SettlementResultOf<T, I>, DispatchError> {
+            frame_support::storage::with_storage_layer(|| {
+                // emit event that auction is matched
+                Self::deposit_matched(&mut auction_data, now);
+
+                Self::complete_settlement(auction_data, now)
+            })
+        }
+
+        // Price a matched buyer pays, the second-highest bid of a Vickrey auction and its own
+        // bid otherwise
+        fn clearing_price(auction_data: &AuctionDataOf<T, I>) -> T::Price {
+            let winning_bid = auction_data.highest_bid.bid;
+            if Self::algorithm_of(auction_data) != MatchingAlgorithm::Vickrey {
+                return winning_bid;
+            }
+
+            // records hold the best bid of each bidder, the seller's price is the floor
+            Bids::<T, I>::iter_prefix(auction_data.auction_id)
+                .filter(|(bidder, _)| *bidder != auction_data.highest_bid.bidder)
+                .map(|(_, record)| record.bid)
+                .fold(auction_data.starting_bid.bid, |second, bid| second.max(bid))
+                .min(winning_bid)
+        }
+
+        // What set the clearing price of an auction
+        fn price_basis(auction_data: &AuctionDataOf<T, I>) -> PriceBasis {
+            match Self::algorithm_of(auction_data) {
+                MatchingAlgorithm::Vickrey => PriceBasis::SecondPrice,
+                MatchingAlgorithm::UniformPrice => PriceBasis::UniformPrice,
+                _ => PriceBasis::HighestBid,
+            }
+        }
 
-            // -------------More logic can be added here
+        // Apply the effects of a match to both parties and report it, failing without any when
+        // the winner cannot pay
+        fn complete_settlement(
+            auction_data: AuctionDataOf<T, I>,
+            now: BlockNumberFor<T>,
+        ) -> Result<SettlementResultOf<T, I>, DispatchError> {
+            let (auction_data, settlement) = Self::apply_settlement(auction_data, now, true)?;
 
             // emit evnt that auction has be executed
             Self::deposit_event(Event::AuctionExecuted {
                 auction_id: auction_data.auction_id,
-                seller_id: auction_data.seller_id,
-                buyer_id: auction_data.highest_bid.bidder,
+                seller_id: auction_data.seller_id.clone(),
+                buyer_id: auction_data.highest_bid.bidder.clone(),
                 energy_quantity: auction_data.quantity,
                 starting_price: auction_data.starting_bid.bid,
                 highest_bid: auction_data.highest_bid.bid,
+                clearing_price: settlement.price,
                 executed_at: now,
+                auction_category: auction_data.auction_category.clone(),
+                bid_count: auction_data.bid_count,
+                end_at: auction_data.end_at,
+                price_basis: Self::price_basis(&auction_data),
             });
+
+            Ok(settlement)
+        }
+
+        // Apply the effects of a match to both parties, failing without any when the winner
+        // cannot pay, `announce` reports the settled auction's change of state
+        fn apply_settlement(
+            mut auction_data: AuctionDataOf<T, I>,
+            now: BlockNumberFor<T>,
+            announce: bool,
+        ) -> Result<(AuctionDataOf<T, I>, SettlementResultOf<T, I>), DispatchError> {
+            // the winner pays before anything settles, a delivery window defers the payment
+            let price = Self::clearing_price(&auction_data);
+            if auction_data.delivery_window.is_none() {
+                Escrow::<T, I>::pay(
+                    auction_data.auction_id,
+                    &auction_data.highest_bid.bidder,
+                    &auction_data.seller_id,
+                    price,
+                )?;
+            }
+            if announce {
+                Self::transition(&mut auction_data, AuctionStatus::Settled);
+            } else {
+                Self::advance(&mut auction_data, AuctionStatus::Settled);
+            }
+
+            Self::set_bid_outcome(
+                &auction_data.highest_bid.bidder,
+                auction_data.auction_id,
+                BidOutcome::Won,
+            );
+            Self::notify(
+                &auction_data.highest_bid.bidder,
+                auction_data.auction_id,
+                Outcome::Won { price },
+            );
+            Self::notify(
+                &auction_data.seller_id,
+                auction_data.auction_id,
+                Outcome::Sold { price },
+            );
+
+            // Auction leaves state, a sale ends the seller's unsold streak
+            Self::release_storage_deposit(&auction_data);
+            Self::release_auction_deposit(&auction_data);
+            UnsoldStreaks::<T, I>::remove(&auction_data.seller_id);
+            for party in [&auction_data.seller_id, &auction_data.highest_bid.bidder] {
+                Self::note_reputation(party, |stats| {
+                    stats.completed_trades = stats.completed_trades.saturating_add(1)
+                });
+            }
+
+            Self::record_settled_volume(
+                &auction_data.seller_id,
+                &auction_data.highest_bid.bidder,
+                auction_data.quantity,
+            );
+            Self::record_clearing(
+                auction_data.auction_category.level,
+                auction_data.quantity,
+                price,
+            );
+            Self::record_trade(
+                auction_data.auction_category.level,
+                now,
+                auction_data.quantity,
+                price,
+            );
+            Self::record_activity(ActivityKind::Matched, &auction_data, price);
+            Self::notify_subscribers(
+                &auction_data,
+                Outcome::Cleared {
+                    tier: auction_data.auction_category.level,
+                    price,
+                },
+            );
+
+            Self::spend_organization_budget(
+                &auction_data.highest_bid.bidder,
+                auction_data.auction_id,
+                price,
+            );
+
+            // Bids leave state with the auction, held ones are released
+            for (bidder, _) in Bids::<T, I>::drain_prefix(auction_data.auction_id) {
+                Self::release_bid_slot(&bidder);
+                if bidder != auction_data.highest_bid.bidder {
+                    Escrow::<T, I>::release(auction_data.auction_id, &bidder);
+                }
+            }
+            if let Some(window) = auction_data.delivery_window {
+                Self::await_delivery(&auction_data, window, price);
+            }
+
+            // take the market fee, then charge both parties a commission on the clearing price
+            Self::charge_fee(
+                auction_data.auction_id,
+                &auction_data.seller_id,
+                auction_data.quantity,
+                price,
+            );
+            Self::charge_commission(auction_data.auction_id, &auction_data.seller_id, price);
+            Self::charge_commission(
+                auction_data.auction_id,
+                &auction_data.highest_bid.bidder,
+                price,
+            );
+
+            T::OnAuctionExecuted::on_auction_executed(
+                auction_data.auction_id,
+                &auction_data.seller_id,
+                &auction_data.highest_bid.bidder,
+                auction_data.quantity,
+                price,
+            );
+
+            let settlement = SettlementResult {
+                auction_id: auction_data.auction_id,
+                seller_id: auction_data.seller_id.clone(),
+                buyer_id: auction_data.highest_bid.bidder.clone(),
+                quantity: auction_data.quantity,
+                price,
+            };
+            Ok((auction_data, settlement))
         }
     }
 }
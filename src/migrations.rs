@@ -0,0 +1,556 @@
+//! Storage migrations of the double auction pallet.
+//!
+//! The multi-block migrations framework is not available on this Substrate branch. Heavy
+//! migrations instead implement [`SteppedMigration`] and are driven from the pallet's `on_idle`
+//! hook, resuming from a cursor kept in `OngoingMigration` until the migration reports it is done.
+//! A runtime starts one by adding [`StartSteppedMigration`] to its `OnRuntimeUpgrade` tuple and
+//! setting `Config::SteppedMigration`.
+//!
+//! Storage layout changes are migrated by the `MigrateToV*` upgrades, one per storage version. Each
+//! only runs from the version before it, so a runtime chains them in its `OnRuntimeUpgrade` tuple,
+//! and with the `try-runtime` feature each checks the storage it migrates before and after the
+//! upgrade.
+use crate::pallet::{
+    AuctionData, AuctionDataOf, AuctionStatus, Auctions, AwaitingAcceptance, AwaitingSettlement,
+    BidOf, BuyOrder, BuyOrderOf, BuyOrders, BuyOrdersOf, Config, Leaderboard, Leaderboards,
+    MarketParameters, MatchingAlgorithm, MetadataOf, Pallet, Parameters, ReservePrice,
+    SellOrdersOf, Tier, TierAuctions, TierQueueDepths, UnsoldAuctions, DEFAULT_ZONE,
+};
+use codec::Decode;
+#[cfg(feature = "try-runtime")]
+use codec::Encode;
+use core::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::{
+    traits::{Get, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+    BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::prelude::vec::Vec;
+
+/// Maximum encoded length of a migration cursor.
+pub const MAX_CURSOR_LEN: u32 = 256;
+
+/// Migration performed in bounded steps over several blocks.
+pub trait SteppedMigration {
+    /// Migrate as many items as fit in `limit`, resuming after `cursor`.
+    ///
+    /// Returns the cursor to resume from in the next block, or `None` once the migration is
+    /// complete, together with the weight consumed.
+    fn step(cursor: Option<Vec<u8>>, limit: Weight) -> (Option<Vec<u8>>, Weight);
+}
+
+/// No migration, completes immediately.
+impl SteppedMigration for () {
+    fn step(_cursor: Option<Vec<u8>>, _limit: Weight) -> (Option<Vec<u8>>, Weight) {
+        (None, Weight::zero())
+    }
+}
+
+/// Starts the configured stepped migration on runtime upgrade.
+pub struct StartSteppedMigration<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for StartSteppedMigration<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        Pallet::<T, I>::start_stepped_migration();
+        T::DbWeight::get().writes(1)
+    }
+}
+
+/// Bounds the storage of version 1 for `MaxEncodedLen`, moving it to version 2.
+///
+/// Leaderboard rankings were unbounded vectors, they are cut to `MaxLeaderboardSize` entries.
+/// Every other storage item keeps its encoding.
+pub struct MigrateToV2<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV2<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        if StorageVersion::get::<Pallet<T, I>>() != 1 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut translated = 0u64;
+        Leaderboards::<T, I>::translate::<Leaderboard<Vec<(T::AccountId, T::Quantity)>>, _>(
+            |_, leaderboard| {
+                translated += 1;
+                Some(Leaderboard {
+                    sellers: BoundedVec::truncate_from(leaderboard.sellers),
+                    buyers: BoundedVec::truncate_from(leaderboard.buyers),
+                })
+            },
+        );
+        StorageVersion::new(2).put::<Pallet<T, I>>();
+
+        T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        Ok((Leaderboards::<T, I>::iter_keys().count() as u32).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let leaderboards =
+            u32::decode(&mut &state[..]).map_err(|_| "pre-upgrade state does not decode")?;
+        ensure!(
+            StorageVersion::get::<Pallet<T, I>>() >= 2,
+            "storage version is not bumped"
+        );
+        ensure!(
+            Leaderboards::<T, I>::iter_values().count() as u32 == leaderboards,
+            "leaderboards were lost"
+        );
+        Ok(())
+    }
+}
+
+/// Keeps only auction ids in the orders of participants, moving storage version 2 to version 3.
+///
+/// `SellOrdersOf` and `BuyOrdersOf` held copies of whole auctions that went stale as the auctions
+/// changed, they now hold the ids of auctions read from `Auctions`.
+pub struct MigrateToV3<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV3<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        if StorageVersion::get::<Pallet<T, I>>() != 2 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut translated = 0u64;
+        SellOrdersOf::<T, I>::translate::<BoundedVec<AuctionDataOf<T, I>, T::MaxSellOrders>, _>(
+            |_, orders| {
+                translated += 1;
+                Some(BoundedVec::truncate_from(
+                    orders.into_iter().map(|order| order.auction_id).collect(),
+                ))
+            },
+        );
+        BuyOrdersOf::<T, I>::translate::<BoundedVec<AuctionDataOf<T, I>, T::MaxBuyOrders>, _>(
+            |_, orders| {
+                translated += 1;
+                Some(BoundedVec::truncate_from(
+                    orders.into_iter().map(|order| order.auction_id).collect(),
+                ))
+            },
+        );
+        StorageVersion::new(3).put::<Pallet<T, I>>();
+
+        T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        Ok((
+            SellOrdersOf::<T, I>::iter_keys().count() as u32,
+            BuyOrdersOf::<T, I>::iter_keys().count() as u32,
+        )
+            .encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let (sellers, buyers) = <(u32, u32)>::decode(&mut &state[..])
+            .map_err(|_| "pre-upgrade state does not decode")?;
+        ensure!(
+            StorageVersion::get::<Pallet<T, I>>() >= 3,
+            "storage version is not bumped"
+        );
+        ensure!(
+            SellOrdersOf::<T, I>::iter_values().count() as u32 == sellers,
+            "sell orders were lost"
+        );
+        ensure!(
+            BuyOrdersOf::<T, I>::iter_values().count() as u32 == buyers,
+            "buy orders were lost"
+        );
+        Ok(())
+    }
+}
+
+// Storage layout of storage version 3
+mod v3 {
+    use super::*;
+
+    // Auction before grid zones and delivery windows
+    #[derive(Decode)]
+    pub struct AuctionData<AccountId, AuctionId, Bid, BlockNumber, Quantity, Tier, Metadata, Price> {
+        pub auction_id: AuctionId,
+        pub seller_id: AccountId,
+        pub quantity: Quantity,
+        pub starting_bid: Bid,
+        pub bid_count: u32,
+        pub auction_period: BlockNumber,
+        pub auction_status: AuctionStatus,
+        pub start_at: BlockNumber,
+        pub end_at: BlockNumber,
+        pub highest_bid: Bid,
+        pub auction_category: Tier,
+        pub metadata: Option<Metadata>,
+        pub min_bidders: u32,
+        pub match_rule: Option<MatchingAlgorithm>,
+        pub settlement_lag: BlockNumber,
+        pub reserve_price: Option<ReservePrice<Price>>,
+    }
+
+    impl<AccountId, AuctionId, Bid, BlockNumber, Quantity, Tier, Metadata, Price>
+        AuctionData<AccountId, AuctionId, Bid, BlockNumber, Quantity, Tier, Metadata, Price>
+    {
+        // Auction fed into the default zone without a delivery window
+        pub fn upgrade(
+            self,
+        ) -> super::AuctionData<
+            AccountId,
+            AuctionId,
+            Bid,
+            BlockNumber,
+            Quantity,
+            Tier,
+            Metadata,
+            Price,
+        > {
+            super::AuctionData {
+                auction_id: self.auction_id,
+                seller_id: self.seller_id,
+                quantity: self.quantity,
+                starting_bid: self.starting_bid,
+                bid_count: self.bid_count,
+                auction_period: self.auction_period,
+                auction_status: self.auction_status,
+                start_at: self.start_at,
+                end_at: self.end_at,
+                highest_bid: self.highest_bid,
+                auction_category: self.auction_category,
+                metadata: self.metadata,
+                min_bidders: self.min_bidders,
+                match_rule: self.match_rule,
+                settlement_lag: self.settlement_lag,
+                reserve_price: self.reserve_price,
+                zone: DEFAULT_ZONE,
+                delivery_window: None,
+            }
+        }
+    }
+
+    // Standing buy order before grid zones
+    #[derive(Decode)]
+    pub struct BuyOrder<AccountId, BlockNumber, Price, Quantity> {
+        pub order_id: u64,
+        pub buyer: AccountId,
+        pub quantity: Quantity,
+        pub max_unit_price: Price,
+        pub placed_at: BlockNumber,
+    }
+
+    impl<AccountId, BlockNumber, Price, Quantity> BuyOrder<AccountId, BlockNumber, Price, Quantity> {
+        // Buy order drawing from the default zone
+        pub fn upgrade(self) -> super::BuyOrder<AccountId, BlockNumber, Price, Quantity> {
+            super::BuyOrder {
+                order_id: self.order_id,
+                buyer: self.buyer,
+                quantity: self.quantity,
+                max_unit_price: self.max_unit_price,
+                placed_at: self.placed_at,
+                zone: DEFAULT_ZONE,
+            }
+        }
+    }
+}
+
+// Auction of storage version 3 as stored by this pallet instance
+type V3AuctionDataOf<T, I> = v3::AuctionData<
+    <T as frame_system::Config>::AccountId,
+    <T as Config<I>>::AuctionId,
+    BidOf<T, I>,
+    BlockNumberFor<T>,
+    <T as Config<I>>::Quantity,
+    Tier,
+    MetadataOf<T, I>,
+    <T as Config<I>>::Price,
+>;
+
+// Standing buy order of storage version 3 as stored by this pallet instance
+type V3BuyOrderOf<T, I> = v3::BuyOrder<
+    <T as frame_system::Config>::AccountId,
+    BlockNumberFor<T>,
+    <T as Config<I>>::Price,
+    <T as Config<I>>::Quantity,
+>;
+
+/// Places auctions and standing buy orders in grid zones, moving storage version 3 to version 4.
+///
+/// Auctions, including those awaiting acceptance or settlement and unsold ones, are fed into
+/// `DEFAULT_ZONE` without a delivery window, and standing buy orders draw from `DEFAULT_ZONE`.
+pub struct MigrateToV4<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV4<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        if StorageVersion::get::<Pallet<T, I>>() != 3 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut translated = 0u64;
+        let mut upgrade = |_: T::AuctionId, auction: V3AuctionDataOf<T, I>| {
+            translated += 1;
+            Some(auction.upgrade())
+        };
+        Auctions::<T, I>::translate::<V3AuctionDataOf<T, I>, _>(&mut upgrade);
+        AwaitingAcceptance::<T, I>::translate::<V3AuctionDataOf<T, I>, _>(&mut upgrade);
+        AwaitingSettlement::<T, I>::translate::<V3AuctionDataOf<T, I>, _>(&mut upgrade);
+        UnsoldAuctions::<T, I>::translate::<V3AuctionDataOf<T, I>, _>(&mut upgrade);
+        BuyOrders::<T, I>::translate::<BoundedVec<V3BuyOrderOf<T, I>, T::MaxStandingBuyOrders>, _>(
+            |_, orders| {
+                translated += 1;
+                Some(BoundedVec::truncate_from(
+                    orders
+                        .into_iter()
+                        .map(|order| order.upgrade())
+                        .collect::<Vec<BuyOrderOf<T, I>>>(),
+                ))
+            },
+        );
+        StorageVersion::new(4).put::<Pallet<T, I>>();
+
+        T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        Ok((
+            Auctions::<T, I>::iter_keys().count() as u32,
+            AwaitingAcceptance::<T, I>::iter_keys().count() as u32,
+            AwaitingSettlement::<T, I>::iter_keys().count() as u32,
+            UnsoldAuctions::<T, I>::iter_keys().count() as u32,
+            BuyOrders::<T, I>::iter_keys().count() as u32,
+        )
+            .encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let (auctions, awaiting_acceptance, awaiting_settlement, unsold, tiers) =
+            <(u32, u32, u32, u32, u32)>::decode(&mut &state[..])
+                .map_err(|_| "pre-upgrade state does not decode")?;
+        ensure!(
+            StorageVersion::get::<Pallet<T, I>>() >= 4,
+            "storage version is not bumped"
+        );
+
+        let zoned = |auction: &AuctionDataOf<T, I>| {
+            auction.zone == DEFAULT_ZONE && auction.delivery_window.is_none()
+        };
+        for (migrated, count) in [
+            (
+                Auctions::<T, I>::iter_values().filter(|a| zoned(a)).count(),
+                auctions,
+            ),
+            (
+                AwaitingAcceptance::<T, I>::iter_values()
+                    .filter(|a| zoned(a))
+                    .count(),
+                awaiting_acceptance,
+            ),
+            (
+                AwaitingSettlement::<T, I>::iter_values()
+                    .filter(|a| zoned(a))
+                    .count(),
+                awaiting_settlement,
+            ),
+            (
+                UnsoldAuctions::<T, I>::iter_values()
+                    .filter(|a| zoned(a))
+                    .count(),
+                unsold,
+            ),
+        ] {
+            ensure!(migrated as u32 == count, "auctions were lost");
+        }
+        ensure!(
+            BuyOrders::<T, I>::iter_values().count() as u32 == tiers,
+            "standing buy orders were lost"
+        );
+        ensure!(
+            BuyOrders::<T, I>::iter_values()
+                .flatten()
+                .all(|order| order.zone == DEFAULT_ZONE),
+            "standing buy orders are not in the default zone"
+        );
+        Ok(())
+    }
+}
+
+/// Indexes live auctions by tier, moving storage version 4 to version 5.
+///
+/// `market_buy` and `open_auctions` read the auctions of a tier from `TierAuctions`, which is
+/// filled from `Auctions` oldest first. A tier already holding `MaxAuctionsPerTier` auctions
+/// leaves the newer ones out of the index, they still execute at their end.
+pub struct MigrateToV5<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV5<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        if StorageVersion::get::<Pallet<T, I>>() != 4 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut auctions: Vec<AuctionDataOf<T, I>> = Auctions::<T, I>::iter_values().collect();
+        auctions.sort_by_key(|auction| auction.auction_id);
+        let read = auctions.len() as u64;
+        for auction in auctions {
+            TierAuctions::<T, I>::mutate(auction.auction_category.level, |ids| {
+                let _ = ids.try_push(auction.auction_id);
+            });
+        }
+        StorageVersion::new(5).put::<Pallet<T, I>>();
+
+        T::DbWeight::get().reads_writes(read * 2 + 1, read + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        Ok((Auctions::<T, I>::iter_keys().count() as u32).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let auctions =
+            u32::decode(&mut &state[..]).map_err(|_| "pre-upgrade state does not decode")?;
+        ensure!(
+            StorageVersion::get::<Pallet<T, I>>() >= 5,
+            "storage version is not bumped"
+        );
+        let indexed: u32 = TierAuctions::<T, I>::iter_values()
+            .map(|ids| ids.len() as u32)
+            .sum();
+        ensure!(
+            indexed <= auctions,
+            "tier index holds auctions that do not exist"
+        );
+        ensure!(
+            TierAuctions::<T, I>::iter_values()
+                .flatten()
+                .all(|auction_id| Auctions::<T, I>::contains_key(auction_id)),
+            "tier index holds auctions that do not exist"
+        );
+        Ok(())
+    }
+}
+
+// Storage layout of storage version 5
+mod v5 {
+    use super::*;
+    use sp_runtime::Perbill;
+
+    // Market parameters before the extension window was tunable
+    #[derive(Decode)]
+    pub struct MarketParameters<Price> {
+        pub min_bid_increment: Option<Price>,
+        pub commission_rate: Option<Perbill>,
+        pub max_executions_per_block: Option<u32>,
+    }
+
+    impl<Price> MarketParameters<Price> {
+        // Parameters keeping the runtime extension window
+        pub fn upgrade<BlockNumber>(self) -> super::MarketParameters<Price, BlockNumber> {
+            super::MarketParameters {
+                min_bid_increment: self.min_bid_increment,
+                commission_rate: self.commission_rate,
+                max_executions_per_block: self.max_executions_per_block,
+                extension_threshold: None,
+                extension_period: None,
+            }
+        }
+    }
+}
+
+/// Makes the extension window a market parameter, moving storage version 5 to version 6.
+///
+/// Overrides set by governance are kept, the extension window keeps following the runtime
+/// constants until it is overridden.
+pub struct MigrateToV6<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV6<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        if StorageVersion::get::<Pallet<T, I>>() != 5 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let _ = Parameters::<T, I>::translate::<v5::MarketParameters<T::Price>, _>(|parameters| {
+            parameters.map(|parameters| parameters.upgrade())
+        });
+        StorageVersion::new(6).put::<Pallet<T, I>>();
+
+        T::DbWeight::get().reads_writes(2, 2)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_: Vec<u8>) -> Result<(), &'static str> {
+        ensure!(
+            StorageVersion::get::<Pallet<T, I>>() >= 6,
+            "storage version is not bumped"
+        );
+        ensure!(
+            !Parameters::<T, I>::exists() || Parameters::<T, I>::try_get().is_ok(),
+            "market parameters do not decode"
+        );
+        Ok(())
+    }
+}
+
+/// Counts matched auctions by tier, moving storage version 6 to version 7.
+///
+/// `market_status` reads the auctions of a tier awaiting acceptance and settlement from
+/// `TierQueueDepths`, which is filled from `AwaitingAcceptance` and `AwaitingSettlement`.
+pub struct MigrateToV7<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV7<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        if StorageVersion::get::<Pallet<T, I>>() != 6 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut read = 0u64;
+        for auction in AwaitingAcceptance::<T, I>::iter_values() {
+            read += 1;
+            TierQueueDepths::<T, I>::mutate(auction.auction_category.level, |depths| {
+                depths.0 = depths.0.saturating_add(1)
+            });
+        }
+        for auction in AwaitingSettlement::<T, I>::iter_values() {
+            read += 1;
+            TierQueueDepths::<T, I>::mutate(auction.auction_category.level, |depths| {
+                depths.1 = depths.1.saturating_add(1)
+            });
+        }
+        StorageVersion::new(7).put::<Pallet<T, I>>();
+
+        T::DbWeight::get().reads_writes(read * 2 + 1, read + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        Ok((
+            AwaitingAcceptance::<T, I>::iter_keys().count() as u32,
+            AwaitingSettlement::<T, I>::iter_keys().count() as u32,
+        )
+            .encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let (awaiting_acceptance, awaiting_settlement) = <(u32, u32)>::decode(&mut &state[..])
+            .map_err(|_| "pre-upgrade state does not decode")?;
+        ensure!(
+            StorageVersion::get::<Pallet<T, I>>() >= 7,
+            "storage version is not bumped"
+        );
+        let (acceptance, settlement) = TierQueueDepths::<T, I>::iter_values()
+            .fold((0u32, 0u32), |(acceptance, settlement), depths| {
+                (acceptance + depths.0, settlement + depths.1)
+            });
+        ensure!(
+            acceptance == awaiting_acceptance && settlement == awaiting_settlement,
+            "matched auctions were not counted"
+        );
+        Ok(())
+    }
+}
@@ -1,9 +1,18 @@
 use crate as pallet_double_auction;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::{
+    parameter_types,
+    traits::{
+        AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8, Currency, Hooks,
+        OnUnbalanced,
+    },
+    weights::Weight,
+    PalletId,
+};
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
+    Perbill, Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -29,6 +38,8 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system,
+        Balances: pallet_balances,
+        Assets: pallet_assets,
         DoubleAuctionModule: pallet_double_auction,
     }
 );
@@ -51,7 +62,7 @@ impl frame_system::Config for Test {
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u128>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -60,18 +71,248 @@ impl frame_system::Config for Test {
     type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u128;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = u128;
+    type RemoveItemsLimit = ConstU32<5>;
+    type AssetId = u32;
+    type AssetIdParameter = u32;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<1>;
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type ApprovalDeposit = ConstU128<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+}
+
+// Asset auctions may be paid in, held by every funded test account
+pub const STABLECOIN: u32 = 1;
+
+parameter_types! {
+    pub static MaxExecutionsPerBlock: u32 = 10;
+    pub static MaxAuctionsPerEndBlock: u32 = 100;
+    pub static MinBidIncrement: u128 = 1;
+    pub static AcceptanceWindow: u64 = 0;
+    pub static Simulation: bool = false;
+    pub static OnChainSettlement: bool = false;
+    pub static MaxBidsPerAuction: u32 = 10;
+    pub static ExtensionThreshold: u64 = 0;
+    pub static ExtensionPeriod: u64 = 0;
+    pub static AuctionDeposit: u128 = 0;
+    pub static RequireRegistration: bool = false;
+    pub static RequireKyc: bool = false;
+    pub static BlockTimeMillis: u64 = 6_000;
+    pub static FeeRate: Permill = Permill::zero();
+    pub static FeeExemptQuantity: u128 = 0;
+    pub static MaxRelistAttempts: u32 = 0;
+    pub static MaxOpenAuctionsPerSeller: u32 = 100;
+    pub static MaxOpenBidsPerBuyer: u32 = 100;
+    pub static MaxAuctionsPerTier: u32 = 100;
+    pub static MaxMatchesPerClearing: u32 = 100;
+    pub static ClearingQuorum: Perbill = Perbill::zero();
+    pub static MinReputation: u32 = 0;
+    pub static NonDeliverySlash: Permill = Permill::zero();
+    pub static RelistDiscount: Permill = Permill::zero();
+    pub static ExecutedAuctions: Vec<(u64, AccountId, AccountId, u128, u128)> = Vec::new();
+    pub const DoubleAuctionPalletId: PalletId = PalletId(*b"py/dauct");
+    pub const CommissionRate: Perbill = Perbill::from_percent(10);
+    pub const ReferralShare: Perbill = Perbill::from_percent(50);
+    pub const PriceSpread: Perbill = Perbill::from_percent(10);
+}
+
+// Account collected market fees are deposited to
+pub fn treasury_account() -> AccountId {
+    AccountId::from(*b"00000000000000000000TREASURY0000")
+}
+
+// Market fee handler depositing collected fees to the treasury account
+pub struct DepositToTreasury;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for DepositToTreasury {
+    fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+        Balances::resolve_creating(&treasury_account(), amount);
+    }
+}
+
+// Smart meter signing the relayed bids of benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MeterSigner;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_double_auction::traits::BenchmarkHelper<Signature, AccountId> for MeterSigner {
+    fn signer() -> AccountId {
+        use sp_core::Pair;
+        sp_runtime::MultiSigner::from(meter().public()).into_account()
+    }
+
+    fn sign(message: &[u8]) -> Signature {
+        use sp_core::Pair;
+        MultiSignature::from(meter().sign(message))
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+fn meter() -> sp_core::sr25519::Pair {
+    use sp_core::Pair;
+    sp_core::sr25519::Pair::from_seed(&[7u8; 32])
+}
+
+// Execution handler recording every executed auction
+pub struct RecordExecution;
+impl pallet_double_auction::traits::OnAuctionExecuted<u64, AccountId, u128, u128>
+    for RecordExecution
+{
+    fn on_auction_executed(
+        auction_id: u64,
+        seller: &AccountId,
+        buyer: &AccountId,
+        quantity: u128,
+        price: u128,
+    ) {
+        let mut executed = ExecutedAuctions::get();
+        executed.push((auction_id, seller.clone(), buyer.clone(), quantity, price));
+        ExecutedAuctions::set(executed);
+    }
+}
+
 impl pallet_double_auction::Config for Test {
     type RuntimeEvent = RuntimeEvent;
-    // type WeightInfo = ()
+    type WeightInfo = ();
     type AuctionId = u64;
     type Quantity = u128;
     type Price = u128;
+    type PriceDecimals = ConstU8<12>;
+    type MaxTiers = ConstU32<2>;
+    type TierThreshold = ConstU128<5>;
+    type MinBidIncrement = MinBidIncrement;
+    type MinReputation = MinReputation;
+    type Currency = Balances;
+    type Assets = Assets;
+    type Simulation = Simulation;
+    type OnChainSettlement = OnChainSettlement;
+    type DepositPerByte = ConstU128<1>;
+    type AuctionDeposit = AuctionDeposit;
+    type MaxUnsoldStreak = ConstU32<1>;
+    type NonDeliverySlash = NonDeliverySlash;
+    type PalletId = DoubleAuctionPalletId;
+    type CommissionRate = CommissionRate;
+    type ReferralShare = ReferralShare;
+    type FeeRate = FeeRate;
+    type FeeExemptQuantity = FeeExemptQuantity;
+    type OnFeeCollected = DepositToTreasury;
+    type EraLength = ConstU64<100>;
+    type MaxLeaderboardSize = ConstU32<2>;
+    type LeaderboardRetention = ConstU32<4>;
+    type OffchainSignature = Signature;
+    type OffchainPublic = <Signature as Verify>::Signer;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MeterSigner;
+    type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type ArbitrationOrigin = frame_system::EnsureRoot<AccountId>;
+    type RequireRegistration = RequireRegistration;
+    type RequireKyc = RequireKyc;
+    type SteppedMigration = ();
+    type ExecutionPriority = pallet_double_auction::traits::HighestValueFirst;
+    type OnAuctionExecuted = RecordExecution;
+    type MaxExecutionsPerBlock = MaxExecutionsPerBlock;
+    type MaxAuctionsPerEndBlock = MaxAuctionsPerEndBlock;
+    type MaxAuctionDuration = ConstU64<14_400>;
+    type BlockTimeMillis = BlockTimeMillis;
+    type MinAuctionPeriod = ConstU64<10>;
+    type MaxAuctionPeriod = ConstU64<14_400>;
+    type AcceptanceWindow = AcceptanceWindow;
+    type RevealWindow = ConstU64<10>;
+    type ClearingInterval = ConstU64<10>;
+    type MaxMatchesPerClearing = MaxMatchesPerClearing;
+    type ClearingQuorum = ClearingQuorum;
+    type ExtensionThreshold = ExtensionThreshold;
+    type ExtensionPeriod = ExtensionPeriod;
+    type RelistCooldown = ConstU64<20>;
+    type MaxRelistAttempts = MaxRelistAttempts;
+    type RelistDiscount = RelistDiscount;
+    type MetadataLimit = ConstU32<64>;
+    type MemoLimit = ConstU32<32>;
+    type MaxSellOrders = ConstU32<5>;
+    type MaxBuyOrders = ConstU32<5>;
+    type MaxBidsPerAuction = MaxBidsPerAuction;
+    type MaxBatchBids = ConstU32<4>;
+    type MaxOpenAuctionsPerSeller = MaxOpenAuctionsPerSeller;
+    type MaxOpenBidsPerBuyer = MaxOpenBidsPerBuyer;
+    type MaxAuctionsPerTier = MaxAuctionsPerTier;
+    type MaxStandingBuyOrders = ConstU32<3>;
+    type MaxRecurringAuctions = ConstU32<2>;
+    type MaxFillsPerAuction = ConstU32<3>;
+    type MaxBidHistory = ConstU32<10>;
+    type MaxNotifications = ConstU32<2>;
+    type MaxTierSubscribers = ConstU32<2>;
+    type MaxMarketActivity = ConstU32<3>;
+    type MaxRecentClearings = ConstU32<2>;
+    type MaxTradeHistory = ConstU32<3>;
+    type HistoryDepth = ConstU64<100>;
+    type OracleWindow = ConstU64<100>;
+    type PriceSpread = PriceSpread;
+    type MaxHistogramBuckets = ConstU32<4>;
+    type HistogramBucketWidth = ConstU128<500>;
+    type HistogramWindow = ConstU32<2>;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::default()
+    let mut storage = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
-        .unwrap()
-        .into()
+        .unwrap();
+
+    // fund accounts used in tests
+    let accounts = [
+        b"000000000000000000000ALICE000000",
+        b"000000000000000000000BOB00000000",
+        b"00000000000000000000CHARLIE00000",
+    ]
+    .map(|account| AccountId::from(*account));
+    pallet_balances::GenesisConfig::<Test> {
+        balances: accounts
+            .iter()
+            .map(|account| (account.clone(), 1_000_000_000))
+            .collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    // a sufficient asset needs no native balance to be held, the pallet account included
+    pallet_assets::GenesisConfig::<Test> {
+        assets: Vec::from([(STABLECOIN, treasury_account(), true, 1)]),
+        metadata: Vec::new(),
+        accounts: accounts
+            .iter()
+            .map(|account| (STABLECOIN, account.clone(), 1_000_000_000))
+            .collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    storage.into()
+}
+
+// Run the end-of-block hooks of block `n`, with idle time to execute every due auction
+pub fn finalize_block(n: u64) {
+    DoubleAuctionModule::on_idle(n, Weight::MAX);
+    DoubleAuctionModule::on_finalize(n);
 }
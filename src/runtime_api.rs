@@ -0,0 +1,63 @@
+//! Runtime API definition for the double auction pallet.
+use crate::{
+    AuctionDetail, BidHistoryEntry, Leaderboard, MarketStatus, OraclePrice, PriceSuggestion,
+    ReputationStats,
+};
+use codec::Codec;
+use scale_info::prelude::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait DoubleAuctionApi<AccountId, AuctionId, BlockNumber, Price, Quantity>
+    where
+        AccountId: Codec,
+        AuctionId: Codec,
+        BlockNumber: Codec,
+        Price: Codec,
+        Quantity: Codec,
+    {
+        /// Past bids of `account` placed at or after `from_block` with their outcomes.
+        fn bid_history(
+            account: AccountId,
+            from_block: BlockNumber,
+            limit: u32,
+        ) -> Vec<BidHistoryEntry<AuctionId, BlockNumber, Price>>;
+
+        /// Market era of the current block.
+        fn current_era() -> u32;
+
+        /// Top sellers and buyers of `era` by settled energy.
+        fn leaderboard(era: u32) -> Option<Leaderboard<Vec<(AccountId, Quantity)>>>;
+
+        /// Starting price for `quantity` in `tier` derived from recent clearings, if any.
+        fn suggest_starting_price(tier: u32, quantity: Quantity) -> Option<PriceSuggestion<Price>>;
+
+        /// Time- and volume-weighted average clearing price of `tier` over the recent blocks.
+        fn price_oracle(tier: u32) -> Option<OraclePrice<Price, Quantity>>;
+
+        /// Number of bids per price bucket placed in `tier` over the recent eras.
+        fn bid_histogram(tier: u32) -> Vec<u32>;
+
+        /// State, queue depths and blocks until the next clearing of every tier.
+        fn market_status() -> Vec<MarketStatus<BlockNumber>>;
+
+        /// Live auctions of `tier`, oldest first.
+        fn open_auctions(
+            tier: u32,
+        ) -> Vec<AuctionDetail<AccountId, AuctionId, BlockNumber, Price, Quantity>>;
+
+        /// Detail of a live auction.
+        fn auction_detail(
+            auction_id: AuctionId,
+        ) -> Option<AuctionDetail<AccountId, AuctionId, BlockNumber, Price, Quantity>>;
+
+        /// Bidder and amount of the leading bid of a live auction, none before any bid leads.
+        fn best_bid(auction_id: AuctionId) -> Option<(AccountId, Price)>;
+
+        /// Lowest bid for `quantity` of a live auction that would lead it against the current
+        /// bids.
+        fn quote_bid(auction_id: AuctionId, quantity: Quantity) -> Option<Price>;
+
+        /// Completed trades, cancellations, lost disputes and confirmed deliveries of `account`.
+        fn reputation(account: AccountId) -> ReputationStats;
+    }
+}
@@ -0,0 +1,145 @@
+//! Reusable fixtures for runtimes and integration tests exercising the double auction pallet.
+//!
+//! Enabled by the `test-utils` feature.
+use crate::pallet::*;
+use codec::{Decode, Encode};
+use frame_support::{
+    sp_runtime::traits::{Bounded, One, Saturating, TrailingZeroInput},
+    traits::{Currency, GenesisBuild, Hooks},
+    weights::Weight,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use scale_info::prelude::vec::Vec;
+
+/// Deterministic account derived from a name and an index.
+pub fn account<T: frame_system::Config>(name: &'static str, index: u32) -> T::AccountId {
+    let entropy = (name, index).using_encoded(sp_io::hashing::blake2_256);
+    T::AccountId::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+        .expect("infinite length input; no invalid inputs for type; qed")
+}
+
+/// Default seller fixture.
+pub fn seller<T: frame_system::Config>() -> T::AccountId {
+    account::<T>("seller", 0)
+}
+
+/// `index`-th buyer fixture.
+pub fn buyer<T: frame_system::Config>(index: u32) -> T::AccountId {
+    account::<T>("buyer", index)
+}
+
+/// Give `who` enough free balance to cover any deposit.
+pub fn fund_account<T: Config<I>, I: 'static>(who: &T::AccountId) {
+    T::Currency::make_free_balance_be(who, BalanceOf::<T, I>::max_value() / 4u32.into());
+}
+
+/// Builds test externalities with the pallet's genesis applied.
+pub struct ExtBuilder<T: Config<I>, I: 'static = ()> {
+    auction_index: T::AuctionId,
+    block_number: BlockNumberFor<T>,
+}
+
+impl<T: Config<I>, I: 'static> Default for ExtBuilder<T, I> {
+    fn default() -> Self {
+        Self {
+            auction_index: Default::default(),
+            block_number: One::one(),
+        }
+    }
+}
+
+impl<T: Config<I>, I: 'static> ExtBuilder<T, I> {
+    /// Id assigned to the first auction.
+    pub fn auction_index(mut self, auction_index: T::AuctionId) -> Self {
+        self.auction_index = auction_index;
+        self
+    }
+
+    /// Block the externalities start at.
+    pub fn block_number(mut self, block_number: BlockNumberFor<T>) -> Self {
+        self.block_number = block_number;
+        self
+    }
+
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<T>()
+            .expect("system genesis builds");
+        GenesisBuild::<T, I>::assimilate_storage(
+            &GenesisConfig::<T, I> {
+                auction_index: self.auction_index,
+                ..Default::default()
+            },
+            &mut storage,
+        )
+        .expect("pallet genesis builds");
+
+        let mut ext = sp_io::TestExternalities::new(storage);
+        ext.execute_with(|| frame_system::Pallet::<T>::set_block_number(self.block_number));
+        ext
+    }
+}
+
+/// Create an auction from `seller` and return its id.
+pub fn create_auction<T: Config<I>, I: 'static>(
+    seller: T::AccountId,
+    energy_quantity: u128,
+    starting_price: u128,
+    auction_period: u16,
+) -> T::AuctionId {
+    let auction_id = AuctionIndex::<T, I>::get().unwrap_or_default();
+    fund_account::<T, I>(&seller);
+    Pallet::<T, I>::new(
+        RawOrigin::Signed(seller).into(),
+        energy_quantity,
+        starting_price,
+        auction_period,
+        Default::default(),
+    )
+    .expect("auction is created");
+    auction_id
+}
+
+/// Place a bid of `bid` from `buyer` on `auction_id`.
+pub fn place_bid<T: Config<I>, I: 'static>(
+    buyer: T::AccountId,
+    auction_id: T::AuctionId,
+    bid: u128,
+) {
+    fund_account::<T, I>(&buyer);
+    Pallet::<T, I>::bid(RawOrigin::Signed(buyer).into(), auction_id, bid, None)
+        .expect("bid is placed");
+}
+
+/// Create an auction from the seller fixture with `n` increasing bids from distinct buyers.
+///
+/// Returns the auction id and the bidders, the last one holding the highest bid.
+pub fn create_auction_with_bids<T: Config<I>, I: 'static>(
+    n: u32,
+) -> (T::AuctionId, Vec<T::AccountId>) {
+    let starting_price = 1_000u128;
+    let auction_id = create_auction::<T, I>(seller::<T>(), 10, starting_price, 5);
+
+    let bidders: Vec<T::AccountId> = (0..n).map(buyer::<T>).collect();
+    for (index, bidder) in bidders.iter().enumerate() {
+        place_bid::<T, I>(
+            bidder.clone(),
+            auction_id,
+            starting_price + 100 * (index as u128 + 1),
+        );
+    }
+
+    (auction_id, bidders)
+}
+
+/// Advance to block `n`, running the pallet hooks of every block on the way.
+pub fn run_to_block<T: Config<I>, I: 'static>(n: BlockNumberFor<T>) {
+    let mut now = frame_system::Pallet::<T>::block_number();
+    while now < n {
+        Pallet::<T, I>::on_idle(now, Weight::MAX);
+        Pallet::<T, I>::on_finalize(now);
+        now = now.saturating_add(One::one());
+        frame_system::Pallet::<T>::set_block_number(now);
+        Pallet::<T, I>::on_initialize(now);
+    }
+}
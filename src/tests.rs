@@ -1,7 +1,25 @@
-use crate::{mock::*, Bid, Event};
+use crate::{
+    mock::*,
+    test_utils::{self, ExtBuilder},
+    ActivityKind, AuctionStatus, BatchMode, Bid, BidOutcome, BidPayload, BidRecordOf,
+    DeferralReason, DeliveryWindow, Error, Event, Fill, Leaderboard, ListingOptions,
+    MarketParameter, MarketStatus, MatchingAlgorithm, OraclePrice, Outcome, PartyType, PriceBasis,
+    ReputationStats, ReservePrice, SettlementResult, Tier, TierState, SETTLEMENT_DIGEST_ID,
+};
+use codec::{Encode, MaxEncodedLen};
+use frame_support::dispatch::Pays;
 use frame_support::pallet_prelude::Weight;
-use frame_support::{assert_ok, traits::Hooks};
-use sp_runtime::AccountId32;
+use frame_support::traits::{Currency, ReservableCurrency};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{ConstU32, GenesisBuild, Hooks, OnRuntimeUpgrade, StorageVersion},
+    BoundedVec,
+};
+use sp_core::{sr25519, Pair};
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash, IdentifyAccount},
+    AccountId32, DigestItem, MultiSignature, MultiSigner, Perbill, Permill,
+};
 
 #[test]
 fn create_new_auction_should_work() {
@@ -25,7 +43,8 @@ fn create_new_auction_should_work() {
             seller,
             energy_quantity,
             starting_price,
-            auction_period
+            auction_period,
+            Default::default()
         ));
 
         // assert that auction was added to auctions
@@ -41,15 +60,11 @@ fn create_new_auction_should_work() {
         assert_eq!(auction.starting_bid.bid, starting_price);
 
         // assert that auction was added for user
-        let seller_auction_info = DoubleAuctionModule::auctions_of(AccountId::from(
+        let seller_orders = DoubleAuctionModule::sell_orders_of(AccountId::from(
             AccountId32::from(b"000000000000000000000ALICE000000".clone()),
-        ))
-        .expect("return seller's auction information");
+        ));
 
-        assert_eq!(
-            seller_auction_info.participant_id.unwrap(),
-            AccountId32::from(b"000000000000000000000ALICE000000".clone())
-        );
+        assert_eq!(seller_orders[0], auction.auction_id);
 
         // assert that auction is in auction queue
         assert!(
@@ -63,6 +78,9 @@ fn create_new_auction_should_work() {
             seller_id: auction.seller_id,
             energy_quantity: auction.quantity,
             starting_price,
+            metadata: None,
+            auction_category: Tier { level: 1 },
+            end_at: execution_block,
         }));
     })
 }
@@ -89,7 +107,8 @@ fn cancel_auction_should_work() {
             seller.clone(),
             energy_quantity,
             starting_price,
-            auction_period
+            auction_period,
+            Default::default()
         ));
 
         // assert that auction was added to auctions
@@ -106,11 +125,9 @@ fn cancel_auction_should_work() {
 
         // assert that auction was removed from user
         assert!(
-            DoubleAuctionModule::auctions_of(AccountId::from(AccountId32::from(
+            DoubleAuctionModule::sell_orders_of(AccountId::from(AccountId32::from(
                 b"000000000000000000000ALICE000000".clone(),
             )))
-            .unwrap()
-            .auctions
             .get(auction.auction_id as usize)
             .is_none()
         );
@@ -151,7 +168,8 @@ fn bid_should_work() {
             seller.clone(),
             energy_quantity,
             starting_price,
-            auction_period
+            auction_period,
+            Default::default()
         ));
 
         // assert that auction was added to auctions
@@ -165,7 +183,12 @@ fn bid_should_work() {
         let new_bid = 10_000;
 
         // dispatch signed extrinsic for bid
-        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), auction_id, new_bid));
+        assert_ok!(DoubleAuctionModule::bid(
+            buyer.clone(),
+            auction_id,
+            new_bid,
+            None
+        ));
 
         // assert that bid was added to the auction
         auction = DoubleAuctionModule::auctions(auction_id).expect("return indexed auction");
@@ -175,13 +198,11 @@ fn bid_should_work() {
             AccountId32::from(b"000000000000000000000BOB00000000".clone())
         );
 
-        // assert that bid was added on buyer info
+        // assert that bid was added on buyer info, which reads the auction as it is now
         assert_eq!(
-            DoubleAuctionModule::auctions_of(AccountId::from(AccountId32::from(
+            DoubleAuctionModule::buyer_auctions(&AccountId::from(AccountId32::from(
                 b"000000000000000000000BOB00000000".clone(),
             )))
-            .unwrap()
-            .auctions
             .get(auction.auction_id as usize)
             .unwrap()
             .highest_bid
@@ -191,11 +212,9 @@ fn bid_should_work() {
 
         // assert that bid was added on seller info
         assert!(
-            DoubleAuctionModule::auctions_of(AccountId::from(AccountId32::from(
+            DoubleAuctionModule::sell_orders_of(AccountId::from(AccountId32::from(
                 b"000000000000000000000ALICE000000".clone(),
             )))
-            .unwrap()
-            .auctions
             .get(auction.auction_id as usize)
             .is_some()
         );
@@ -208,7 +227,11 @@ fn bid_should_work() {
             bid: Bid {
                 bidder: AccountId32::from(b"000000000000000000000BOB00000000".clone()),
                 bid: new_bid,
+                memo: None,
             },
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
         }));
     });
 }
@@ -233,7 +256,8 @@ fn on_auction_ended_should_work() {
             seller.clone(),
             energy_quantity,
             starting_price,
-            auction_period
+            auction_period,
+            Default::default()
         ));
 
         // assert that auction was added to auctions
@@ -246,7 +270,12 @@ fn on_auction_ended_should_work() {
         let auction_id = auction.auction_id;
         let new_bid = 10_000;
 
-        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), auction_id, new_bid));
+        assert_ok!(DoubleAuctionModule::bid(
+            buyer.clone(),
+            auction_id,
+            new_bid,
+            None
+        ));
         auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
 
         // fast forward block production to a block after auction execution block height
@@ -255,9 +284,9 @@ fn on_auction_ended_should_work() {
 
         assert_eq!(
             DoubleAuctionModule::on_initialize(execution_block),
-            Weight::from_all(100_000_000u64)
+            <() as crate::WeightInfo>::execute_auctions(0)
         );
-        DoubleAuctionModule::on_finalize(execution_block);
+        finalize_block(execution_block);
 
         // assert that auction is not in auction queue
         assert!(
@@ -275,6 +304,18 @@ fn on_auction_ended_should_work() {
             matched_at: System::block_number(),
         }));
 
+        // assert that the execution handler was told of the trade
+        assert_eq!(
+            ExecutedAuctions::get(),
+            vec![(
+                auction.auction_id,
+                auction.seller_id.clone(),
+                auction.highest_bid.bidder.clone(),
+                auction.quantity,
+                auction.highest_bid.bid
+            )]
+        );
+
         System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
             auction_id: auction.auction_id,
             seller_id: auction.seller_id,
@@ -282,7 +323,7291 @@ fn on_auction_ended_should_work() {
             energy_quantity: auction.quantity,
             starting_price: auction.starting_bid.bid,
             highest_bid: auction.highest_bid.bid,
+            clearing_price: auction.highest_bid.bid,
             executed_at: System::block_number(),
+            auction_category: auction.auction_category.clone(),
+            bid_count: auction.bid_count,
+            end_at: auction.end_at,
+            price_basis: PriceBasis::HighestBid,
+        }));
+    });
+}
+
+#[test]
+fn least_recently_active_auction_should_be_dropped_from_orders() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        let seller_auctions =
+            |seller: &AccountId| DoubleAuctionModule::sell_orders_of(seller).into_inner();
+
+        // fill the seller's orders up to `MaxSellOrders`
+        for _ in 0..5 {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(alice_id.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+        assert_eq!(seller_auctions(&alice_id), vec![0, 1, 2, 3, 4]);
+
+        // assert that a bid makes the oldest auction the most recently active one
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 1_000, None));
+        assert_eq!(seller_auctions(&alice_id), vec![1, 2, 3, 4, 0]);
+
+        // assert that the least recently active auction is dropped for a new one
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_eq!(seller_auctions(&alice_id), vec![2, 3, 4, 0, 5]);
+    });
+}
+
+#[test]
+fn cancel_auction_with_bids_should_refund_bidders() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // initialize new auction params
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let energy_quantity = 2; // in KWH
+        let starting_price = 1_000;
+        let auction_period = 5; // in minutes
+
+        // dispatch new auction extrinsic
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            energy_quantity,
+            starting_price,
+            auction_period,
+            Default::default()
+        ));
+
+        // place bid
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let new_bid = 10_000;
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            new_bid,
+            None
+        ));
+
+        // dispatch signed extrinsic for cancel auction
+        assert_ok!(DoubleAuctionModule::cancel(seller, 0));
+
+        // assert that auction was removed from bidder
+        assert!(!DoubleAuctionModule::buy_orders_of(bob.clone()).contains(&0));
+
+        // assert that bidder was refunded
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BidRefunded {
+            auction_id: 0,
+            bidder: bob,
+            amount: new_bid,
+        }));
+    });
+}
+
+#[test]
+fn scheduled_auction_should_open_at_its_start() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                start_at: Some(10),
+                ..Default::default()
+            }
+        ));
+
+        // assert that the auction waits for its start, its period counting from there
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.auction_status, AuctionStatus::Pending);
+        assert_eq!(auction.start_at, 10);
+        assert_eq!(auction.end_at, 60);
+        assert!(DoubleAuctionModule::pending_auction(10, 0).is_some());
+        assert_noop!(
+            DoubleAuctionModule::bid(buyer.clone(), 0, 2_000, None),
+            Error::<Test>::AuctionNotStarted
+        );
+
+        // assert that bids are accepted once the auction is activated
+        System::set_block_number(10);
+        DoubleAuctionModule::on_initialize(10);
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.auction_status, AuctionStatus::Open);
+        assert!(DoubleAuctionModule::pending_auction(10, 0).is_none());
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionStatusChanged {
+                auction_id: 0,
+                previous_status: AuctionStatus::Pending,
+                status: AuctionStatus::Open,
+            },
+        ));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 2_000, None));
+    });
+}
+
+#[test]
+fn recurring_auction_should_be_listed_every_interval() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+
+        // assert that listings may not overlap
+        assert_noop!(
+            DoubleAuctionModule::create_recurring_auction(seller.clone(), 2, 1_000, 5, 10),
+            Error::<Test>::InvalidRecurrenceInterval
+        );
+
+        // assert that the first auction is listed right away
+        assert_ok!(DoubleAuctionModule::create_recurring_auction(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            100
+        ));
+        assert!(DoubleAuctionModule::auctions(0).is_some());
+        assert_eq!(
+            DoubleAuctionModule::recurring_of(&seller_id).to_vec(),
+            vec![0]
+        );
+        assert_eq!(
+            DoubleAuctionModule::recurring_auction(0)
+                .expect("template")
+                .next_at,
+            102
+        );
+
+        // assert that a fresh auction is listed an interval later
+        System::set_block_number(102);
+        DoubleAuctionModule::on_initialize(102);
+        let auction = DoubleAuctionModule::auctions(1).expect("listed auction");
+        assert_eq!(auction.quantity, 2);
+        assert_eq!(auction.starting_bid.bid, 1_000);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::RecurringAuctionListed {
+                recurring_id: 0,
+                auction_id: 1,
+            },
+        ));
+
+        // assert that only the seller stops the template
+        assert_noop!(
+            DoubleAuctionModule::cancel_recurring(
+                RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+                    b"000000000000000000000BOB00000000".clone(),
+                ))),
+                0
+            ),
+            Error::<Test>::NotAuctionOwner
+        );
+        assert_ok!(DoubleAuctionModule::cancel_recurring(seller, 0));
+        assert!(DoubleAuctionModule::recurring_auction(0).is_none());
+        assert!(DoubleAuctionModule::recurring_schedule(202, 0).is_none());
+        System::set_block_number(202);
+        DoubleAuctionModule::on_initialize(202);
+        assert!(DoubleAuctionModule::auctions(2).is_none());
+    });
+}
+
+#[test]
+fn proxy_bid_should_answer_challenges_up_to_its_cap() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that the proxy opens at the lowest leading bid
+        assert_ok!(DoubleAuctionModule::bid_proxy(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, bob_id);
+        assert_eq!(auction.highest_bid.bid, 1_000);
+        assert_eq!(DoubleAuctionModule::proxy_bid(0, &bob_id), Some(2_000));
+
+        // assert that a challenge below the cap is answered by the increment
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            1_500,
+            None
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, bob_id);
+        assert_eq!(auction.highest_bid.bid, 1_501);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::ProxyBidRaised {
+            auction_id: 0,
+            bidder: bob_id.clone(),
+            bid: 1_501,
+        }));
+
+        // assert that a challenge beating the cap leads and drops it
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            2_001,
+            None
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, charlie_id);
+        assert_eq!(DoubleAuctionModule::proxy_bid(0, &bob_id), None);
+        assert_noop!(
+            DoubleAuctionModule::bid_proxy(RuntimeOrigin::signed(bob_id), 0, 2_001),
+            Error::<Test>::BidTooLow
+        );
+    });
+}
+
+#[test]
+fn proxy_cap_raise_should_be_gated_like_bids() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let bob = RuntimeOrigin::signed(bob_id.clone());
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            ))),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid_proxy(bob.clone(), 0, 2_000));
+
+        // assert that a leader cannot raise its cap in a halted tier
+        assert_ok!(DoubleAuctionModule::set_tier_halted(
+            RuntimeOrigin::root(),
+            1,
+            true
+        ));
+        assert_noop!(
+            DoubleAuctionModule::bid_proxy(bob.clone(), 0, 3_000),
+            Error::<Test>::TierHalted
+        );
+        assert_ok!(DoubleAuctionModule::set_tier_halted(
+            RuntimeOrigin::root(),
+            1,
+            false
+        ));
+
+        // assert that a leader cannot raise its cap while the market is paused
+        assert_ok!(DoubleAuctionModule::pause_market(RuntimeOrigin::root()));
+        assert_noop!(
+            DoubleAuctionModule::bid_proxy(bob.clone(), 0, 3_000),
+            Error::<Test>::MarketPaused
+        );
+        assert_ok!(DoubleAuctionModule::resume_market(RuntimeOrigin::root()));
+
+        // assert that a leader below the required reputation cannot raise its cap
+        MinReputation::set(1);
+        assert_noop!(
+            DoubleAuctionModule::bid_proxy(bob.clone(), 0, 3_000),
+            Error::<Test>::ReputationTooLow
+        );
+        MinReputation::set(0);
+
+        assert_ok!(DoubleAuctionModule::bid_proxy(bob, 0, 3_000));
+        assert_eq!(DoubleAuctionModule::proxy_bid(0, &bob_id), Some(3_000));
+    });
+}
+
+#[test]
+fn only_registered_participants_should_list_and_bid() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        RequireRegistration::set(true);
+        RequireKyc::set(true);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let seller = RuntimeOrigin::signed(alice_id.clone());
+        let buyer = RuntimeOrigin::signed(bob_id.clone());
+
+        // assert that unregistered accounts cannot list
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 2, 1_000, 5, Default::default()),
+            Error::<Test>::NotRegistered
+        );
+
+        // assert that registered participants wait for KYC verification
+        assert_ok!(DoubleAuctionModule::register_participant(
+            seller.clone(),
+            PartyType::Seller
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::ParticipantRegistered {
+                who: alice_id.clone(),
+                role: PartyType::Seller,
+            },
+        ));
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 2, 1_000, 5, Default::default()),
+            Error::<Test>::KycRequired
+        );
+        assert_noop!(
+            DoubleAuctionModule::set_participant_status(
+                RuntimeOrigin::signed(alice_id.clone()),
+                alice_id.clone(),
+                true,
+                false
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(DoubleAuctionModule::set_participant_status(
+            RuntimeOrigin::root(),
+            alice_id.clone(),
+            true,
+            false
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that bidding needs a buying role
+        assert_ok!(DoubleAuctionModule::register_participant(
+            buyer.clone(),
+            PartyType::Seller
+        ));
+        assert_ok!(DoubleAuctionModule::set_participant_status(
+            RuntimeOrigin::root(),
+            bob_id.clone(),
+            true,
+            false
+        ));
+        assert_noop!(
+            DoubleAuctionModule::bid(buyer.clone(), 0, 1_000, None),
+            Error::<Test>::RoleNotAllowed
+        );
+        assert_ok!(DoubleAuctionModule::register_participant(
+            buyer.clone(),
+            PartyType::Prosumer
+        ));
+        assert_eq!(
+            DoubleAuctionModule::participant(&bob_id).map(|p| p.kyc_verified),
+            Some(true)
+        );
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 1_000, None));
+
+        // assert that suspended participants can no longer bid
+        assert_ok!(DoubleAuctionModule::set_participant_status(
+            RuntimeOrigin::root(),
+            bob_id.clone(),
+            true,
+            true
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::ParticipantStatusSet {
+                who: bob_id,
+                kyc_verified: true,
+                suspended: true,
+            },
+        ));
+        assert_noop!(
+            DoubleAuctionModule::bid(buyer, 0, 1_100, None),
+            Error::<Test>::ParticipantSuspended
+        );
+    });
+}
+
+#[test]
+fn demand_auction_should_sell_to_lowest_offer() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // assert that the buyer's maximum price is held for the demand
+        assert_ok!(DoubleAuctionModule::new_demand(
+            RuntimeOrigin::signed(bob_id.clone()),
+            2,
+            5_000,
+            5
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::DemandCreated {
+            demand_id: 0,
+            buyer_id: bob_id.clone(),
+            energy_quantity: 2,
+            max_price: 5_000,
+            end_at: 52,
+        }));
+        assert_eq!(Balances::reserved_balance(&bob_id), 5_000);
+
+        // assert that offers compete downward from the maximum price
+        assert_noop!(
+            DoubleAuctionModule::offer(RuntimeOrigin::signed(charlie_id.clone()), 0, 5_001),
+            Error::<Test>::OfferTooHigh
+        );
+        assert_noop!(
+            DoubleAuctionModule::offer(RuntimeOrigin::signed(bob_id.clone()), 0, 4_000),
+            Error::<Test>::OfferOnOwnDemand
+        );
+        assert_ok!(DoubleAuctionModule::offer(
+            RuntimeOrigin::signed(charlie_id),
+            0,
+            4_000
+        ));
+        assert_noop!(
+            DoubleAuctionModule::offer(RuntimeOrigin::signed(alice_id.clone()), 0, 4_000),
+            Error::<Test>::OfferTooHigh
+        );
+        assert_ok!(DoubleAuctionModule::offer(
+            RuntimeOrigin::signed(alice_id.clone()),
+            0,
+            3_000
+        ));
+
+        // assert that only the buyer withdraws its demand
+        assert_noop!(
+            DoubleAuctionModule::cancel_demand(RuntimeOrigin::signed(alice_id.clone()), 0),
+            Error::<Test>::NotAuctionOwner
+        );
+
+        // assert that the lowest offer sells at its price when the demand ends
+        System::set_block_number(52);
+        DoubleAuctionModule::on_initialize(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::DemandExecuted {
+            demand_id: 0,
+            buyer_id: bob_id.clone(),
+            seller_id: alice_id.clone(),
+            energy_quantity: 2,
+            clearing_price: 3_000,
+            executed_at: 52,
         }));
+        assert!(DoubleAuctionModule::demand_auction(0).is_none());
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_eq!(Balances::free_balance(&bob_id), 1_000_000_000 - 3_000 - 300);
+        assert_eq!(
+            Balances::free_balance(&alice_id),
+            1_000_000_000 + 3_000 - 300
+        );
+    });
+}
+
+#[test]
+fn offer_should_fail_in_halted_tier() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let charlie = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new_demand(
+            RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+                b"000000000000000000000BOB00000000".clone(),
+            ))),
+            2,
+            5_000,
+            5
+        ));
+
+        // assert that sellers cannot offer on demand of a halted tier
+        assert_ok!(DoubleAuctionModule::set_tier_halted(
+            RuntimeOrigin::root(),
+            1,
+            true
+        ));
+        assert_noop!(
+            DoubleAuctionModule::offer(charlie.clone(), 0, 4_000),
+            Error::<Test>::TierHalted
+        );
+
+        // assert that offers resume with the tier
+        assert_ok!(DoubleAuctionModule::set_tier_halted(
+            RuntimeOrigin::root(),
+            1,
+            false
+        ));
+        assert_ok!(DoubleAuctionModule::offer(charlie, 0, 4_000));
+    });
+}
+
+#[test]
+fn auction_status_should_follow_lifecycle() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        for _ in 0..3 {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+        assert_eq!(
+            DoubleAuctionModule::auctions(0)
+                .expect("auction")
+                .auction_status,
+            AuctionStatus::Open
+        );
+
+        // assert that a cancelled auction is told apart from an executed one
+        assert_ok!(DoubleAuctionModule::cancel(
+            RuntimeOrigin::signed(seller_id.clone()),
+            0
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionStatusChanged {
+                auction_id: 0,
+                previous_status: AuctionStatus::Open,
+                status: AuctionStatus::Cancelled,
+            },
+        ));
+
+        // assert that a sold auction is matched then settled, an unsold one expires
+        assert_ok!(DoubleAuctionModule::bid(buyer, 1, 2_000, None));
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionStatusChanged {
+                auction_id: 1,
+                previous_status: AuctionStatus::Open,
+                status: AuctionStatus::Matched,
+            },
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionStatusChanged {
+                auction_id: 1,
+                previous_status: AuctionStatus::Matched,
+                status: AuctionStatus::Settled,
+            },
+        ));
+        assert_eq!(
+            DoubleAuctionModule::unsold_auctions(2)
+                .expect("archived auction")
+                .auction_status,
+            AuctionStatus::Expired
+        );
+
+        // assert that the lifecycle only moves forward
+        assert!(AuctionStatus::Pending.can_transition_to(&AuctionStatus::Open));
+        assert!(!AuctionStatus::Settled.can_transition_to(&AuctionStatus::Open));
+        assert!(!AuctionStatus::Cancelled.can_transition_to(&AuctionStatus::Matched));
+    });
+}
+
+#[test]
+fn bid_history_should_track_outcomes() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // dispatch new auction extrinsic
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // place competing bids
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            10_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            20_000,
+            None
+        ));
+
+        // assert that outbid bidder is recorded as outbid
+        let bob_history = DoubleAuctionModule::bid_history(bob, 0, 10);
+        assert_eq!(bob_history.len(), 1);
+        assert_eq!(bob_history[0].bid, 10_000);
+        assert_eq!(bob_history[0].outcome, BidOutcome::Outbid);
+
+        // execute auction
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that highest bidder won
+        let charlie_history = DoubleAuctionModule::bid_history(charlie.clone(), 0, 10);
+        assert_eq!(charlie_history[0].outcome, BidOutcome::Won);
+
+        // assert that history is filtered by block
+        assert!(DoubleAuctionModule::bid_history(charlie, execution_block, 10).is_empty());
+    });
+}
+
+#[test]
+fn bid_with_memo_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // dispatch new auction extrinsic
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // place bid with purchase-order reference
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let memo: BoundedVec<u8, ConstU32<32>> = b"PO-2024-0042".to_vec().try_into().unwrap();
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            10_000,
+            Some(memo.clone())
+        ));
+
+        // assert that memo was stored with the bid
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.memo, Some(memo.clone()));
+
+        // assert that memo was echoed in event
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionBidAdded {
+            auction_id: 0,
+            seller_id: auction.seller_id,
+            energy_quantity: auction.quantity,
+            bid: Bid {
+                bidder: bob,
+                bid: 10_000,
+                memo: Some(memo),
+            },
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+        }));
+    });
+}
+
+#[test]
+fn create_auction_with_metadata_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // dispatch new auction extrinsic with contract terms reference
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let metadata: BoundedVec<u8, ConstU32<64>> =
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+                .to_vec()
+                .try_into()
+                .unwrap();
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                metadata: Some(metadata.clone()),
+                ..Default::default()
+            }
+        ));
+
+        // assert that metadata was stored with the auction
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.metadata, Some(metadata.clone()));
+
+        // assert that metadata was included in event
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionCreated {
+            auction_id: 0,
+            seller_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            metadata: Some(metadata),
+            auction_category: Tier { level: 1 },
+            end_at: 52,
+        }));
+    });
+}
+
+#[test]
+fn extend_auction_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // dispatch new auction extrinsic
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // only seller can extend
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_noop!(
+            DoubleAuctionModule::extend_auction(RuntimeOrigin::signed(bob), 0, 10),
+            Error::<Test>::NotAuctionOwner
+        );
+
+        // extension beyond maximum duration fails
+        assert_noop!(
+            DoubleAuctionModule::extend_auction(
+                RuntimeOrigin::signed(seller_id.clone()),
+                0,
+                14_400
+            ),
+            Error::<Test>::AuctionDurationTooLong
+        );
+
+        // dispatch signed extrinsic for extend auction
+        assert_ok!(DoubleAuctionModule::extend_auction(
+            RuntimeOrigin::signed(seller_id.clone()),
+            0,
+            10
+        ));
+
+        // assert that auction end and execution queue were updated
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.end_at, 62);
+        assert!(DoubleAuctionModule::auction_execution_queue(52, 0).is_none());
+        assert!(DoubleAuctionModule::auction_execution_queue(62, 0).is_some());
+
+        // assert that correct event was emitted
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExtended {
+            auction_id: 0,
+            seller_id,
+            previous_end_at: 52,
+            end_at: 62,
+        }));
+    });
+}
+
+#[test]
+fn relist_unsold_auction_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // dispatch new auction extrinsic
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // auction expires without bids
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that auction was archived as unsold
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::unsold_auctions(0).is_some());
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionExpiredUnsold {
+                auction_id: 0,
+                seller_id: seller_id.clone(),
+                energy_quantity: 2,
+                starting_price: 1_000,
+                expired_at: execution_block,
+            },
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionClosedWithoutMatch {
+                auction_id: 0,
+                seller_id: seller_id.clone(),
+                energy_quantity: 2,
+                auction_category: Tier { level: 1 },
+                bid_count: 0,
+                end_at: execution_block,
+                closed_at: execution_block,
+            },
+        ));
+
+        // dispatch signed extrinsic for relist
+        assert_ok!(DoubleAuctionModule::relist(
+            RuntimeOrigin::signed(seller_id.clone()),
+            0,
+            10,
+            800
+        ));
+
+        // assert that a fresh auction was created
+        let auction = DoubleAuctionModule::auctions(1).expect("return indexed auction");
+        assert_eq!(auction.quantity, 2);
+        assert_eq!(auction.starting_bid.bid, 1_000);
+        assert_eq!(
+            auction.reserve_price,
+            Some(ReservePrice {
+                price: 800,
+                relist: false,
+            })
+        );
+        assert_eq!(auction.end_at, execution_block + 100);
+        assert!(DoubleAuctionModule::unsold_auctions(0).is_none());
+
+        // assert that correct event was emitted
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionRelisted {
+            previous_auction_id: 0,
+            auction_id: 1,
+            seller_id,
+        }));
+    });
+}
+
+#[test]
+fn relisted_auction_should_move_its_delivery_window() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                delivery_window: Some(DeliveryWindow {
+                    delivery_start: 60,
+                    delivery_end: 80,
+                }),
+                ..Default::default()
+            }
+        ));
+
+        // auction expires without bids
+        System::set_block_number(52);
+        finalize_block(52);
+        assert!(DoubleAuctionModule::unsold_auctions(0).is_some());
+
+        // assert that a manual relist moves the window as late as the new end
+        assert_ok!(DoubleAuctionModule::relist(
+            RuntimeOrigin::signed(seller_id.clone()),
+            0,
+            10,
+            800
+        ));
+        let auction = DoubleAuctionModule::auctions(1).expect("return relisted auction");
+        assert_eq!(auction.end_at, 152);
+        assert_eq!(
+            auction.delivery_window,
+            Some(DeliveryWindow {
+                delivery_start: 160,
+                delivery_end: 180,
+            })
+        );
+
+        // assert that an automatic relist moves it too
+        MaxRelistAttempts::set(1);
+        System::set_block_number(152);
+        finalize_block(152);
+        let auction = DoubleAuctionModule::auctions(2).expect("return relisted auction");
+        assert_eq!(auction.end_at, 252);
+        assert_eq!(
+            auction.delivery_window,
+            Some(DeliveryWindow {
+                delivery_start: 260,
+                delivery_end: 280,
+            })
+        );
+    });
+}
+
+#[test]
+fn execution_over_budget_should_follow_priority() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxExecutionsPerBlock::set(2);
+
+        // create three auctions ending in the same block
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        for _ in 0..3 {
+            assert_ok!(DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+
+        // place bids of different value
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 10_000, None));
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 1, 30_000, None));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 2, 20_000, None));
+
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that highest value auctions were executed
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+        assert!(DoubleAuctionModule::auctions(2).is_none());
+
+        // assert that lowest value auction stays queued for the next block
+        let deferred = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(deferred.end_at, execution_block);
+        assert!(DoubleAuctionModule::auction_execution_queue(execution_block, 0).is_some());
+        assert_eq!(
+            DoubleAuctionModule::execution_cursor(),
+            Some(execution_block)
+        );
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionExecutionDeferred {
+                auction_id: 0,
+                seller_id: deferred.seller_id,
+                reason: DeferralReason::WeightCap,
+                execute_at: execution_block + 1,
+            },
+        ));
+    });
+}
+
+#[test]
+fn tier_default_period_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+
+        // auction without period fails while tier has no default
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 2, 1_000, 0, Default::default()),
+            Error::<Test>::NoDefaultAuctionPeriod
+        );
+
+        // only admin can set tier defaults
+        assert_noop!(
+            DoubleAuctionModule::set_tier_default_period(seller.clone(), 1, Some(30)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(DoubleAuctionModule::set_tier_default_period(
+            RuntimeOrigin::root(),
+            1,
+            Some(30)
+        ));
+
+        // dispatch new auction extrinsic without period
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            0,
+            Default::default()
+        ));
+
+        // assert that tier default period was used
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.auction_period, 30);
+        assert_eq!(auction.end_at, 32);
+    });
+}
+
+#[test]
+fn auction_period_should_follow_block_time() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        BlockTimeMillis::set(12_000);
+
+        // assert that 5 minutes of 12 second blocks last 25 blocks
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            ))),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.auction_period, 25);
+        assert_eq!(auction.end_at, 27);
+    });
+}
+
+#[test]
+fn auction_parameters_should_be_validated() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+
+        // assert that nothing is listed without quantity
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 0, 1_000, 5, Default::default()),
+            Error::<Test>::InvalidQuantity
+        );
+
+        // assert that periods stay within `MinAuctionPeriod` and `MaxAuctionPeriod`
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 2, 1_000, u16::MAX, Default::default()),
+            Error::<Test>::AuctionPeriodTooLong
+        );
+        assert_ok!(DoubleAuctionModule::set_tier_default_period(
+            RuntimeOrigin::root(),
+            1,
+            Some(5)
+        ));
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 2, 1_000, 0, Default::default()),
+            Error::<Test>::AuctionPeriodTooShort
+        );
+
+        // assert that an end beyond the last block is rejected instead of overflowing
+        assert_noop!(
+            DoubleAuctionModule::new(
+                seller,
+                2,
+                1_000,
+                5,
+                ListingOptions {
+                    start_at: Some(u64::MAX - 10),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::AuctionPeriodTooLong
+        );
+    });
+}
+
+#[test]
+fn auction_with_too_few_bidders_should_not_match() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // dispatch new auction extrinsic requiring two bidders
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                min_bidders: Some(2),
+                ..Default::default()
+            }
+        ));
+
+        // single bidder places two bids
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            10_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            20_000,
+            None
+        ));
+
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that auction ended unmatched and bidder was refunded
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionInsufficientBidders {
+                auction_id: 0,
+                bidders: 1,
+                min_bidders: 2,
+            },
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BidRefunded {
+            auction_id: 0,
+            bidder: bob,
+            amount: 20_000,
+        }));
+        assert!(DoubleAuctionModule::unsold_auctions(0).is_some());
+    });
+}
+
+#[test]
+fn test_utils_scenario_should_work() {
+    ExtBuilder::<Test>::default()
+        .block_number(2)
+        .build()
+        .execute_with(|| {
+            // create auction with three competing bids
+            let (auction_id, bidders) = test_utils::create_auction_with_bids::<Test, ()>(3);
+            let auction =
+                DoubleAuctionModule::auctions(auction_id).expect("return indexed auction");
+            assert_eq!(auction.highest_bid.bidder, bidders[2]);
+            assert_eq!(auction.highest_bid.bid, 1_300);
+
+            // run through auction execution
+            test_utils::run_to_block::<Test, ()>(53);
+
+            // assert that highest bidder won
+            System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+                auction_id,
+                seller_id: test_utils::seller::<Test>(),
+                buyer_id: bidders[2].clone(),
+                energy_quantity: 10,
+                starting_price: 1_000,
+                highest_bid: 1_300,
+                clearing_price: 1_300,
+                executed_at: 52,
+                auction_category: Tier { level: 2 },
+                bid_count: 3,
+                end_at: 52,
+                price_basis: PriceBasis::HighestBid,
+            }));
+        });
+}
+
+#[test]
+fn storage_deposit_should_be_held_and_released() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // dispatch new auction extrinsic
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that deposit proportional to auction size is held from seller
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        let deposit = auction.encoded_size() as u128;
+        assert_eq!(DoubleAuctionModule::storage_deposit(0), deposit);
+        assert_eq!(Balances::reserved_balance(&seller_id), deposit);
+
+        // bid records grow the deposit
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 10_000, None));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        let record_size = BidRecordOf::<Test>::max_encoded_len() as u128;
+        assert_eq!(
+            Balances::reserved_balance(&seller_id),
+            auction.encoded_size() as u128 + record_size
+        );
+
+        // cancelling releases the deposit
+        assert_ok!(DoubleAuctionModule::cancel(
+            RuntimeOrigin::signed(seller_id.clone()),
+            0
+        ));
+        assert_eq!(DoubleAuctionModule::storage_deposit(0), 0);
+        assert_eq!(Balances::reserved_balance(&seller_id), 0);
+    });
+}
+
+#[test]
+fn auction_deposit_should_be_slashed_after_repeated_unsold_auctions() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        AuctionDeposit::set(100);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        for _ in 0..3 {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+        assert_eq!(DoubleAuctionModule::auction_deposit(0), 100);
+
+        // assert that cancelling returns the deposit
+        assert_ok!(DoubleAuctionModule::cancel(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2
+        ));
+        assert_eq!(DoubleAuctionModule::auction_deposit(2), 0);
+
+        // assert that the first unsold auction keeps its deposit, the next one is slashed
+        let issuance = Balances::total_issuance();
+        System::set_block_number(52);
+        finalize_block(52);
+        assert_eq!(DoubleAuctionModule::unsold_streak(&seller_id), 2);
+        assert_eq!(DoubleAuctionModule::auction_deposit(0), 100);
+        assert_eq!(DoubleAuctionModule::auction_deposit(1), 0);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionDepositSlashed {
+                auction_id: 1,
+                seller_id: seller_id.clone(),
+                amount: 100,
+                unsold_streak: 2,
+            },
+        ));
+        assert_eq!(Balances::total_issuance(), issuance - 100);
+
+        // assert that a sale returns the deposit and ends the streak
+        assert_ok!(DoubleAuctionModule::relist(
+            RuntimeOrigin::signed(seller_id.clone()),
+            0,
+            5,
+            1_000
+        ));
+        assert_eq!(DoubleAuctionModule::auction_deposit(0), 0);
+        assert_eq!(DoubleAuctionModule::auction_deposit(3), 100);
+        assert_ok!(DoubleAuctionModule::bid(buyer, 3, 2_000, None));
+        System::set_block_number(102);
+        finalize_block(102);
+        assert_eq!(DoubleAuctionModule::auction_deposit(3), 0);
+        assert_eq!(DoubleAuctionModule::unsold_streak(&seller_id), 0);
+    });
+}
+
+#[test]
+fn stepped_migration_should_run_on_idle() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // nothing to do without an ongoing migration
+        DoubleAuctionModule::on_idle(2, Weight::from_parts(1_000_000_000, 1_000_000));
+        assert!(DoubleAuctionModule::ongoing_migration().is_none());
+
+        // start migration on runtime upgrade
+        crate::migrations::StartSteppedMigration::<Test>::on_runtime_upgrade();
+        assert!(DoubleAuctionModule::ongoing_migration().is_some());
+
+        // migration is stepped from on_idle until complete
+        DoubleAuctionModule::on_idle(2, Weight::from_parts(1_000_000_000, 1_000_000));
+        assert!(DoubleAuctionModule::ongoing_migration().is_none());
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::MigrationCompleted));
+    });
+}
+
+#[test]
+fn migration_to_v2_should_bound_leaderboards() {
+    new_test_ext().execute_with(|| {
+        let account = |seed: &[u8; 32]| AccountId::from(AccountId32::from(seed.clone()));
+        let ranking = vec![
+            (account(b"000000000000000000000ALICE000000"), 9u128),
+            (account(b"000000000000000000000BOB00000000"), 5),
+            (account(b"00000000000000000000CHARLIE00000"), 1),
+        ];
+
+        // leaderboard of version 1, longer than `MaxLeaderboardSize`
+        frame_support::storage::unhashed::put(
+            &crate::Leaderboards::<Test>::hashed_key_for(0),
+            &Leaderboard {
+                sellers: ranking.clone(),
+                buyers: Vec::<(AccountId, u128)>::new(),
+            },
+        );
+        StorageVersion::new(1).put::<DoubleAuctionModule>();
+
+        crate::migrations::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        // assert that the ranking is cut and the storage version bumped
+        let leaderboard = DoubleAuctionModule::leaderboard(0).expect("leaderboard of era");
+        assert_eq!(leaderboard.sellers, ranking[..2].to_vec());
+        assert!(leaderboard.buyers.is_empty());
+        assert_eq!(StorageVersion::get::<DoubleAuctionModule>(), 2);
+    });
+}
+
+#[test]
+fn migration_to_v3_should_keep_auction_ids_in_orders() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        for _ in 0..2 {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(alice_id.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+
+        // orders of version 2, holding copies of whole auctions
+        let copies = |ids: &[u64]| -> Vec<crate::AuctionDataOf<Test>> {
+            ids.iter()
+                .map(|id| DoubleAuctionModule::auctions(id).expect("return indexed auction"))
+                .collect()
+        };
+        frame_support::storage::unhashed::put(
+            &crate::SellOrdersOf::<Test>::hashed_key_for(&alice_id),
+            &copies(&[0, 1]),
+        );
+        frame_support::storage::unhashed::put(
+            &crate::BuyOrdersOf::<Test>::hashed_key_for(&bob_id),
+            &copies(&[1]),
+        );
+        StorageVersion::new(2).put::<DoubleAuctionModule>();
+
+        crate::migrations::MigrateToV3::<Test>::on_runtime_upgrade();
+
+        // assert that only the ids are kept and the storage version bumped
+        assert_eq!(
+            DoubleAuctionModule::sell_orders_of(&alice_id).into_inner(),
+            vec![0, 1]
+        );
+        assert_eq!(
+            DoubleAuctionModule::buy_orders_of(&bob_id).into_inner(),
+            vec![1]
+        );
+        assert_eq!(DoubleAuctionModule::seller_auctions(&alice_id).len(), 2);
+        assert_eq!(StorageVersion::get::<DoubleAuctionModule>(), 3);
+    });
+}
+
+#[test]
+fn execution_telemetry_should_be_recorded() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxExecutionsPerBlock::set(2);
+
+        // create three auctions ending in the same block
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        for _ in 0..3 {
+            assert_ok!(DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+
+        // bid on two of them
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 1, 30_000, None));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 2, 20_000, None));
+
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that two auctions were executed and one was deferred
+        let telemetry =
+            DoubleAuctionModule::last_execution_telemetry().expect("telemetry recorded");
+        assert_eq!(telemetry.block, execution_block);
+        assert_eq!(telemetry.executed, 2);
+        assert_eq!(telemetry.deferred, 1);
+        assert_eq!(telemetry.unmatched, 0);
+        assert_eq!(telemetry.energy_cleared, 4);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::BlockExecutionSummary {
+                block: execution_block,
+                executed: 2,
+                deferred: 1,
+                unmatched: 0,
+                energy_cleared: 4,
+            },
+        ));
+
+        // assert that deferred auction without bids is unmatched in the next block
+        System::set_block_number(execution_block + 1);
+        finalize_block(execution_block + 1);
+        let telemetry =
+            DoubleAuctionModule::last_execution_telemetry().expect("telemetry recorded");
+        assert_eq!(telemetry.block, execution_block + 1);
+        assert_eq!(telemetry.executed, 0);
+        assert_eq!(telemetry.unmatched, 1);
+        assert_eq!(telemetry.energy_cleared, 0);
+
+        // assert that idle blocks leave telemetry untouched
+        finalize_block(execution_block + 2);
+        assert_eq!(
+            DoubleAuctionModule::last_execution_telemetry().map(|t| t.block),
+            Some(execution_block + 1)
+        );
+    });
+}
+
+#[test]
+fn referral_rewards_should_accrue_and_be_claimed() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let referrer_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // assert that self referral and referral cycles are rejected
+        assert_noop!(
+            DoubleAuctionModule::register_referrer(
+                RuntimeOrigin::signed(buyer_id.clone()),
+                buyer_id.clone()
+            ),
+            Error::<Test>::SelfReferral
+        );
+        assert_ok!(DoubleAuctionModule::register_referrer(
+            RuntimeOrigin::signed(buyer_id.clone()),
+            referrer_id.clone()
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::ReferrerRegistered {
+                account: buyer_id.clone(),
+                referrer: referrer_id.clone(),
+            },
+        ));
+        assert_noop!(
+            DoubleAuctionModule::register_referrer(
+                RuntimeOrigin::signed(referrer_id.clone()),
+                buyer_id.clone()
+            ),
+            Error::<Test>::ReferralCycle
+        );
+        assert_noop!(
+            DoubleAuctionModule::register_referrer(
+                RuntimeOrigin::signed(buyer_id.clone()),
+                seller_id.clone()
+            ),
+            Error::<Test>::ReferrerAlreadySet
+        );
+
+        // settle an auction between seller and referred buyer
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(buyer_id.clone()),
+            0,
+            10_000,
+            None
+        ));
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that both parties paid commission and the buyer's referrer accrued its share
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::CommissionCharged {
+                auction_id: 0,
+                payer: buyer_id.clone(),
+                amount: 1_000,
+            },
+        ));
+        assert_eq!(
+            Balances::free_balance(&DoubleAuctionModule::account_id()),
+            2_000
+        );
+        assert_eq!(DoubleAuctionModule::referral_rewards(&referrer_id), 500);
+        assert_eq!(DoubleAuctionModule::referral_rewards(&seller_id), 0);
+
+        // assert that rewards are paid out once
+        let balance_before = Balances::free_balance(&referrer_id);
+        assert_ok!(DoubleAuctionModule::claim_referral_rewards(
+            RuntimeOrigin::signed(referrer_id.clone())
+        ));
+        assert_eq!(Balances::free_balance(&referrer_id), balance_before + 500);
+        assert_eq!(DoubleAuctionModule::referral_rewards(&referrer_id), 0);
+        assert_noop!(
+            DoubleAuctionModule::claim_referral_rewards(RuntimeOrigin::signed(referrer_id)),
+            Error::<Test>::NoReferralRewards
+        );
+    });
+}
+
+#[test]
+fn market_fee_should_be_collected_above_exempt_quantity() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        FeeRate::set(Permill::from_percent(5));
+        FeeExemptQuantity::set(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        for quantity in [1, 2] {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id.clone()),
+                quantity,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 10_000, None));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 1, 10_000, None));
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that only the auction above the exempt quantity paid the fee to the treasury
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::FeeCharged {
+            auction_id: 1,
+            seller_id,
+            amount: 500,
+        }));
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::DoubleAuctionModule(Event::FeeCharged { auction_id: 0, .. })
+        )));
+        assert_eq!(Balances::free_balance(&treasury_account()), 500);
+    });
+}
+
+#[test]
+fn sponsored_account_should_participate_without_funds() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let sponsor_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        let prosumer_id = AccountId::from(AccountId32::from(
+            b"0000000000000000000000DAVE000000".clone(),
+        ));
+
+        // assert that accounts can not sponsor themselves
+        assert_noop!(
+            DoubleAuctionModule::sponsor(
+                RuntimeOrigin::signed(sponsor_id.clone()),
+                sponsor_id.clone(),
+                1_000,
+                1
+            ),
+            Error::<Test>::SelfSponsorship
+        );
+
+        // assert that only accounts registered as sponsors can sponsor
+        assert_noop!(
+            DoubleAuctionModule::sponsor(
+                RuntimeOrigin::signed(sponsor_id.clone()),
+                prosumer_id.clone(),
+                1_000,
+                1
+            ),
+            Error::<Test>::NotRegistered
+        );
+        assert_ok!(DoubleAuctionModule::register_participant(
+            RuntimeOrigin::signed(sponsor_id.clone()),
+            PartyType::Prosumer
+        ));
+        assert_noop!(
+            DoubleAuctionModule::sponsor(
+                RuntimeOrigin::signed(sponsor_id.clone()),
+                prosumer_id.clone(),
+                1_000,
+                1
+            ),
+            Error::<Test>::RoleNotAllowed
+        );
+
+        // assert that only governance grants the sponsor role
+        assert_noop!(
+            DoubleAuctionModule::register_participant(
+                RuntimeOrigin::signed(sponsor_id.clone()),
+                PartyType::Sponsor
+            ),
+            Error::<Test>::RoleNotAllowed
+        );
+        assert_noop!(
+            DoubleAuctionModule::register_sponsor(
+                RuntimeOrigin::signed(sponsor_id.clone()),
+                sponsor_id.clone()
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(DoubleAuctionModule::register_sponsor(
+            RuntimeOrigin::root(),
+            sponsor_id.clone()
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::ParticipantRegistered {
+                who: sponsor_id.clone(),
+                role: PartyType::Sponsor,
+            },
+        ));
+        assert_ok!(DoubleAuctionModule::sponsor(
+            RuntimeOrigin::signed(sponsor_id.clone()),
+            prosumer_id.clone(),
+            1_000,
+            1
+        ));
+
+        // assert that another sponsor can not take over the sponsorship
+        let other_sponsor_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::register_sponsor(
+            RuntimeOrigin::root(),
+            other_sponsor_id.clone()
+        ));
+        assert_noop!(
+            DoubleAuctionModule::sponsor(
+                RuntimeOrigin::signed(other_sponsor_id),
+                prosumer_id.clone(),
+                0,
+                0
+            ),
+            Error::<Test>::AlreadySponsored
+        );
+
+        // unfunded prosumer lists surplus energy, fee waived and deposit held from sponsor
+        let post_info = DoubleAuctionModule::new(
+            RuntimeOrigin::signed(prosumer_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default(),
+        )
+        .expect("auction is created");
+        assert_eq!(post_info.pays_fee, Pays::No);
+        let deposit = DoubleAuctionModule::storage_deposit(0);
+        assert!(deposit > 0);
+        assert_eq!(Balances::reserved_balance(&sponsor_id), deposit);
+        assert_eq!(Balances::reserved_balance(&prosumer_id), 0);
+        assert_eq!(
+            DoubleAuctionModule::deposit_payer(0),
+            Some(sponsor_id.clone())
+        );
+
+        // assert that fee-less calls are limited per era
+        let post_info =
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(prosumer_id.clone()), 0, 1_000, None)
+                .expect("bid is placed");
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+
+        // assert that ending the sponsorship keeps held deposits until the auction leaves state
+        assert_ok!(DoubleAuctionModule::end_sponsorship(
+            RuntimeOrigin::signed(prosumer_id.clone()),
+            prosumer_id.clone()
+        ));
+        assert!(DoubleAuctionModule::sponsorship_of(&prosumer_id).is_none());
+        assert!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(prosumer_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        )
+        .is_err());
+
+        assert_ok!(DoubleAuctionModule::cancel(
+            RuntimeOrigin::signed(prosumer_id),
+            0
+        ));
+        assert_eq!(Balances::reserved_balance(&sponsor_id), 0);
+        assert_eq!(DoubleAuctionModule::deposit_payer(0), None);
+    });
+}
+
+#[test]
+fn relayed_signed_bid_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let relayer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // smart meter signs a bid off-chain
+        let meter = sr25519::Pair::from_seed(&[7u8; 32]);
+        let meter_id: AccountId = MultiSigner::from(meter.public()).into_account();
+        let payload = BidPayload {
+            bidder: meter_id.clone(),
+            auction_id: 0,
+            bid: 2_000,
+            memo: None,
+            nonce: 0,
+            expires_at: 5,
+        };
+        let signature =
+            MultiSignature::from(meter.sign(&DoubleAuctionModule::bid_signing_payload(&payload)));
+
+        // assert that a signature over the bare payload, valid on any chain, is rejected
+        let unbound = MultiSignature::from(meter.sign(&payload.encode()));
+        assert_noop!(
+            DoubleAuctionModule::bid_signed(relayer.clone(), payload.clone(), unbound),
+            Error::<Test>::InvalidSignature
+        );
+
+        // assert that an expired payload is rejected
+        System::set_block_number(6);
+        assert_noop!(
+            DoubleAuctionModule::bid_signed(relayer.clone(), payload.clone(), signature.clone()),
+            Error::<Test>::BidPayloadExpired
+        );
+        System::set_block_number(2);
+
+        // assert that a tampered payload is rejected
+        let mut tampered = payload.clone();
+        tampered.bid = 3_000;
+        assert_noop!(
+            DoubleAuctionModule::bid_signed(relayer.clone(), tampered, signature.clone()),
+            Error::<Test>::InvalidSignature
+        );
+
+        // assert that the gateway relays the bid on behalf of the meter
+        assert_ok!(DoubleAuctionModule::bid_signed(
+            relayer.clone(),
+            payload.clone(),
+            signature.clone()
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, meter_id.clone());
+        assert_eq!(auction.highest_bid.bid, 2_000);
+        assert_eq!(DoubleAuctionModule::bid_nonce(&meter_id), 1);
+
+        // assert that the payload can not be replayed
+        assert_noop!(
+            DoubleAuctionModule::bid_signed(relayer, payload, signature),
+            Error::<Test>::InvalidNonce
+        );
+    });
+}
+
+#[test]
+fn era_leaderboard_should_rank_settled_volume() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // settle two auctions of different size
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            5,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            1,
+            2_000,
+            None
+        ));
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+
+        // assert that participants are ranked by settled volume
+        let leaderboard = DoubleAuctionModule::leaderboard(0).expect("leaderboard of era");
+        assert_eq!(leaderboard.sellers, vec![(seller_id.clone(), 7)]);
+        assert_eq!(leaderboard.buyers, vec![(charlie, 5), (bob, 2)]);
+        assert_eq!(DoubleAuctionModule::era_volume(0, &seller_id).sold, 7);
+
+        // assert that volumes are dropped when the era rolls over, leaderboard is kept
+        System::set_block_number(100);
+        DoubleAuctionModule::on_initialize(100);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::EraEnded {
+            era: 0,
+        }));
+        assert_eq!(DoubleAuctionModule::current_era(), 1);
+        assert_eq!(DoubleAuctionModule::era_volume(0, &seller_id).sold, 0);
+        assert!(DoubleAuctionModule::leaderboard(0).is_some());
+    });
+}
+
+#[test]
+fn tier_matching_algorithm_should_switch_at_era_boundary() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // assert that only governance can switch
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        assert_noop!(
+            DoubleAuctionModule::set_tier_matching_algorithm(
+                seller,
+                1,
+                MatchingAlgorithm::PayAsBid
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // assert that the switch waits for the next era
+        assert_ok!(DoubleAuctionModule::set_tier_matching_algorithm(
+            RuntimeOrigin::root(),
+            1,
+            MatchingAlgorithm::PayAsBid
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::MatchingAlgorithmScheduled {
+                tier: 1,
+                algorithm: MatchingAlgorithm::PayAsBid,
+                effective_era: 1,
+            },
+        ));
+        assert_eq!(
+            DoubleAuctionModule::matching_algorithm(1),
+            MatchingAlgorithm::SingleWinner
+        );
+
+        System::set_block_number(100);
+        DoubleAuctionModule::on_initialize(100);
+        assert_eq!(
+            DoubleAuctionModule::matching_algorithm(1),
+            MatchingAlgorithm::PayAsBid
+        );
+        assert!(DoubleAuctionModule::pending_matching_algorithm(1).is_none());
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::MatchingAlgorithmChanged {
+                tier: 1,
+                algorithm: MatchingAlgorithm::PayAsBid,
+            },
+        ));
+    });
+}
+
+#[test]
+fn seller_match_rule_should_override_tier_algorithm() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::AllOrNothing),
+                ..Default::default()
+            }
+        ));
+
+        // assert that the rule is kept with the listing
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.match_rule, Some(MatchingAlgorithm::AllOrNothing));
+
+        // assert that the listing settles with its rule
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 2_000, None));
+        let execution_block = System::block_number() + 50;
+        System::set_block_number(execution_block);
+        finalize_block(execution_block);
+        let telemetry =
+            DoubleAuctionModule::last_execution_telemetry().expect("telemetry recorded");
+        assert_eq!(telemetry.executed, 1);
+        assert_eq!(telemetry.energy_cleared, 2);
+    });
+}
+
+#[test]
+fn auction_summary_should_follow_auction() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that summary is created with the auction
+        let summary = DoubleAuctionModule::auction_summary(0).expect("summary of auction");
+        assert_eq!(summary.best_bid, 1_000);
+        assert_eq!(summary.quantity_remaining, 2);
+        assert_eq!(summary.end_at, 52);
+        assert_eq!(summary.status, AuctionStatus::Open);
+
+        // assert that summary follows the best bid
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 2_000, None));
+        assert_eq!(
+            DoubleAuctionModule::auction_summary(0).map(|s| s.best_bid),
+            Some(2_000)
+        );
+
+        // assert that summary leaves state with the auction
+        System::set_block_number(52);
+        finalize_block(52);
+        assert!(DoubleAuctionModule::auction_summary(0).is_none());
+    });
+}
+
+#[test]
+fn settlements_should_be_committed_in_digest() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(buyer_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that the digest commits to the block's settlements
+        let settlements = vec![SettlementResult {
+            auction_id: 0u64,
+            seller_id,
+            buyer_id,
+            quantity: 2u128,
+            price: 2_000u128,
+        }];
+        let commitment = BlakeTwo256::hash_of(&settlements);
+        assert!(System::digest().logs.contains(&DigestItem::Other(
+            (SETTLEMENT_DIGEST_ID, commitment).encode()
+        )));
+    });
+}
+
+#[test]
+fn outcome_notifications_should_be_kept_per_account() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // one auction is contested, two expire unsold
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            3_000,
+            None
+        ));
+
+        // assert that the outbid buyer is notified
+        let notifications = DoubleAuctionModule::notifications_of(&bob);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].auction_id, 0);
+        assert_eq!(notifications[0].outcome, Outcome::Outbid { by: 3_000 });
+
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that the winner is notified
+        let notifications = DoubleAuctionModule::notifications_of(&charlie);
+        assert_eq!(notifications[0].outcome, Outcome::Won { price: 3_000 });
+        assert_eq!(notifications[0].at, 52);
+
+        // assert that the seller's oldest notification was dropped when full
+        let outcomes: Vec<_> = DoubleAuctionModule::notifications_of(&seller_id)
+            .into_iter()
+            .map(|notification| notification.outcome)
+            .collect();
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes
+            .iter()
+            .all(|outcome| *outcome == Outcome::ExpiredUnsold));
+    });
+}
+
+#[test]
+fn suggest_starting_price_should_follow_recent_clearings() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // assert that there is no suggestion without clearings
+        assert_eq!(DoubleAuctionModule::suggest_starting_price(1, 3), None);
+
+        // clear two auctions of the first tier
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            4,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 2_000, None));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 1, 6_000, None));
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that the suggestion is the volume-weighted average price with its spread
+        let suggestion =
+            DoubleAuctionModule::suggest_starting_price(1, 3).expect("suggestion of tier");
+        assert_eq!(suggestion.price, 4_000);
+        assert_eq!(suggestion.low, 3_600);
+        assert_eq!(suggestion.high, 4_400);
+        assert_eq!(DoubleAuctionModule::suggest_starting_price(2, 3), None);
+    });
+}
+
+#[test]
+fn trade_history_should_prune_trades_older_than_history_depth() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+
+        // settle one auction of the first tier from each listing block
+        for (auction_id, listed_at, bid) in [(0, 2, 2_000), (1, 52, 3_000), (2, 160, 4_000)] {
+            System::set_block_number(listed_at);
+            assert_ok!(DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+            assert_ok!(DoubleAuctionModule::bid(
+                buyer.clone(),
+                auction_id,
+                bid,
+                None
+            ));
+            System::set_block_number(listed_at + 50);
+            finalize_block(listed_at + 50);
+        }
+
+        // assert that the trade of block 52 left the history by block 210
+        assert_eq!(
+            DoubleAuctionModule::trade_history(1).into_inner(),
+            vec![(102, 2, 3_000), (210, 2, 4_000)]
+        );
+        assert!(DoubleAuctionModule::trade_history(2).is_empty());
+    });
+}
+
+#[test]
+fn price_oracle_should_average_recent_trades_of_tier() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // assert that there is no price without trades
+        assert_eq!(DoubleAuctionModule::price_oracle(1), None);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+
+        // trade 2 units at 1_000 per unit in block 52, then 4 units at 3_000 in block 102
+        for (auction_id, listed_at, quantity, bid) in [(0, 2, 2, 2_000), (1, 52, 4, 12_000)] {
+            System::set_block_number(listed_at);
+            assert_ok!(DoubleAuctionModule::new(
+                seller.clone(),
+                quantity,
+                1_000,
+                5,
+                Default::default()
+            ));
+            assert_ok!(DoubleAuctionModule::bid(
+                buyer.clone(),
+                auction_id,
+                bid,
+                None
+            ));
+            System::set_block_number(listed_at + 50);
+            finalize_block(listed_at + 50);
+        }
+
+        // assert that prices are weighted by the blocks they held and by quantity
+        System::set_block_number(112);
+        assert_eq!(
+            DoubleAuctionModule::price_oracle(1),
+            Some(OraclePrice {
+                time_weighted: 1_333,
+                volume_weighted: 2_333,
+                volume: 6,
+                trades: 2,
+            })
+        );
+
+        // assert that trades leave the oracle with its window
+        System::set_block_number(160);
+        assert_eq!(
+            DoubleAuctionModule::price_oracle(1).map(|oracle| oracle.trades),
+            Some(1)
+        );
+        System::set_block_number(210);
+        assert_eq!(DoubleAuctionModule::price_oracle(1), None);
+    });
+}
+
+#[test]
+fn bid_below_min_increment_should_not_lead() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MinBidIncrement::set(100);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that a bid within the increment does not replace the highest bid
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 1_000, None));
+        assert_noop!(
+            DoubleAuctionModule::bid(buyer.clone(), 0, 1_050, None),
+            Error::<Test>::BidNotLeading
+        );
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bid, 1_000);
+
+        // assert that a bid of at least the increment does
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 1_100, None));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bid, 1_100);
+
+        // assert that market constants are published
+        assert_eq!(DoubleAuctionModule::quantity_unit(), b"kWh".to_vec());
+    });
+}
+
+#[test]
+fn bid_should_lead_from_starting_price() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that a bid below the starting price is rejected
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(bob.clone()), 0, 999, None),
+            Error::<Test>::BidTooLow
+        );
+
+        // assert that the first bid leads at the starting price
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            1_000,
+            None
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, bob);
+        assert_eq!(auction.highest_bid.bid, 1_000);
+
+        // assert that an equal bid leaves the earlier bid leading
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(charlie), 0, 1_000, None),
+            Error::<Test>::BidNotLeading
+        );
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, bob);
+        assert_eq!(auction.bid_count, 1);
+    });
+}
+
+#[test]
+fn bids_should_be_kept_per_bidder() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that a bidder's record is replaced by its next leading bid
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            3_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            4_000,
+            None
+        ));
+        assert_eq!(
+            DoubleAuctionModule::bid_of(0, &bob).map(|record| record.bid),
+            Some(4_000)
+        );
+        assert_eq!(
+            DoubleAuctionModule::bid_of(0, &charlie).map(|record| record.bid),
+            Some(3_000)
+        );
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.bid_count, 2);
+
+        // assert that records leave state with the auction
+        System::set_block_number(52);
+        finalize_block(52);
+        assert!(DoubleAuctionModule::bid_of(0, &bob).is_none());
+        assert!(DoubleAuctionModule::bid_of(0, &charlie).is_none());
+    });
+}
+
+#[test]
+fn top_up_bid_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that only existing bids can be topped up
+        assert_noop!(
+            DoubleAuctionModule::top_up_bid(RuntimeOrigin::signed(bob.clone()), 0, 500),
+            Error::<Test>::BidDoesNotExist
+        );
+
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            3_000,
+            None
+        ));
+
+        // assert that topping up retakes the lead in place
+        assert_ok!(DoubleAuctionModule::top_up_bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            1_500
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, bob.clone());
+        assert_eq!(auction.highest_bid.bid, 3_500);
+        assert_eq!(auction.bid_count, 2);
+        assert_eq!(
+            DoubleAuctionModule::bid_of(0, &bob).map(|record| record.bid),
+            Some(3_500)
+        );
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BidToppedUp {
+            auction_id: 0,
+            bidder: bob.clone(),
+            bid: 3_500,
+        }));
+
+        // assert that the leader can raise its own bid
+        assert_ok!(DoubleAuctionModule::top_up_bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            500
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bid, 4_000);
+    });
+}
+
+#[test]
+fn transfer_auction_should_work() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that only the seller can transfer
+        assert_noop!(
+            DoubleAuctionModule::transfer_auction(
+                RuntimeOrigin::signed(bob.clone()),
+                0,
+                bob.clone()
+            ),
+            Error::<Test>::NotAuctionOwner
+        );
+
+        // assert that the listing and its deposit move to the new seller
+        let deposit = DoubleAuctionModule::storage_deposit(0);
+        assert_ok!(DoubleAuctionModule::transfer_auction(
+            RuntimeOrigin::signed(alice.clone()),
+            0,
+            bob.clone()
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.seller_id, bob.clone());
+        assert_eq!(auction.highest_bid.bidder, bob.clone());
+        assert_eq!(DoubleAuctionModule::sell_orders_of(&alice).len(), 1);
+        assert_eq!(DoubleAuctionModule::sell_orders_of(&bob)[0], 0);
+        assert_eq!(Balances::reserved_balance(&bob), deposit);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionTransferred {
+                auction_id: 0,
+                previous_seller_id: alice.clone(),
+                seller_id: bob,
+            },
+        ));
+
+        // assert that listings with bids can not be transferred
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            1,
+            2_000,
+            None
+        ));
+        assert_noop!(
+            DoubleAuctionModule::transfer_auction(RuntimeOrigin::signed(alice), 1, charlie),
+            Error::<Test>::AuctionHasBids
+        );
+    });
+}
+
+#[test]
+fn transfer_auction_should_check_new_seller_and_pairs() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(bob.clone()),
+            2,
+            1_000,
+            10,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that the sell leg of a pair stays with its owner
+        assert_ok!(DoubleAuctionModule::pair_orders(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            1
+        ));
+        assert_noop!(
+            DoubleAuctionModule::transfer_auction(RuntimeOrigin::signed(bob), 1, charlie.clone()),
+            Error::<Test>::AuctionAlreadyPaired
+        );
+
+        // assert that the new seller must be allowed to list
+        RequireRegistration::set(true);
+        assert_noop!(
+            DoubleAuctionModule::transfer_auction(
+                RuntimeOrigin::signed(alice.clone()),
+                2,
+                charlie.clone()
+            ),
+            Error::<Test>::NotRegistered
+        );
+        RequireRegistration::set(false);
+
+        // assert that listings are not handed over while the market is paused
+        assert_ok!(DoubleAuctionModule::pause_market(RuntimeOrigin::root()));
+        assert_noop!(
+            DoubleAuctionModule::transfer_auction(RuntimeOrigin::signed(alice), 2, charlie),
+            Error::<Test>::MarketPaused
+        );
+    });
+}
+
+#[test]
+fn market_activity_should_keep_recent_events() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            3,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::cancel(seller, 1));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 2_000, None));
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that the feed keeps the latest events, oldest first
+        let feed: Vec<_> = DoubleAuctionModule::market_activity()
+            .into_iter()
+            .map(|activity| (activity.kind, activity.auction_id, activity.price))
+            .collect();
+        assert_eq!(
+            feed,
+            vec![
+                (ActivityKind::Created, 1, 1_000),
+                (ActivityKind::Canceled, 1, 1_000),
+                (ActivityKind::Matched, 0, 2_000),
+            ]
+        );
+    });
+}
+
+#[test]
+fn halted_tier_should_reject_orders_and_defer_execution() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 2_000, None));
+
+        // assert that only governance can halt a tier
+        assert_noop!(
+            DoubleAuctionModule::set_tier_halted(seller.clone(), 1, true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(DoubleAuctionModule::set_tier_halted(
+            RuntimeOrigin::root(),
+            1,
+            true
+        ));
+
+        // assert that listings and bids of the halted tier are rejected, other tiers trade
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 2, 1_000, 5, Default::default()),
+            Error::<Test>::TierHalted
+        );
+        assert_noop!(
+            DoubleAuctionModule::bid(buyer, 0, 3_000, None),
+            Error::<Test>::TierHalted
+        );
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            10,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that execution is deferred while halted
+        System::set_block_number(52);
+        finalize_block(52);
+        assert!(DoubleAuctionModule::auctions(0).is_some());
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionExecutionDeferred {
+                auction_id: 0,
+                seller_id: AccountId::from(AccountId32::from(
+                    b"000000000000000000000ALICE000000".clone(),
+                )),
+                reason: DeferralReason::TierHalted,
+                execute_at: 53,
+            },
+        ));
+
+        // assert that execution resumes once lifted
+        assert_ok!(DoubleAuctionModule::set_tier_halted(
+            RuntimeOrigin::root(),
+            1,
+            false
+        ));
+        System::set_block_number(53);
+        finalize_block(53);
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+    });
+}
+
+#[test]
+fn tier_calls_should_reject_unserviced_tiers() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        // assert that tiers outside 1..=MaxTiers are rejected
+        assert_noop!(
+            DoubleAuctionModule::set_tier_min_bidders(RuntimeOrigin::root(), 0, 2),
+            Error::<Test>::InvalidTier
+        );
+        assert_noop!(
+            DoubleAuctionModule::set_tier_halted(RuntimeOrigin::root(), 3, true),
+            Error::<Test>::InvalidTier
+        );
+        assert_eq!(DoubleAuctionModule::suggest_starting_price(3, 10), None);
+
+        // assert that the highest serviced tier is accepted
+        assert_ok!(DoubleAuctionModule::set_tier_min_bidders(
+            RuntimeOrigin::root(),
+            2,
+            2
+        ));
+    });
+}
+
+#[test]
+fn market_buy_should_sweep_cheapest_auctions_within_budget() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let charlie = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        )));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let bob = RuntimeOrigin::signed(bob_id.clone());
+
+        // alice offers 2 kWh for 500 per unit, charlie 3 kWh for 400 per unit
+        assert_ok!(DoubleAuctionModule::new(
+            alice,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            charlie,
+            3,
+            1_200,
+            5,
+            Default::default()
+        ));
+
+        // assert that nothing fills when no auction fits the requested quantity
+        assert_noop!(
+            DoubleAuctionModule::market_buy(bob.clone(), 1, 1, 10_000),
+            Error::<Test>::NoMarketLiquidity
+        );
+
+        // assert that the cheapest auction fills first and the budget is respected
+        assert_ok!(DoubleAuctionModule::market_buy(bob.clone(), 1, 5, 1_500));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::MarketBuyExecuted {
+                buyer_id: bob_id.clone(),
+                tier: 1,
+                energy_quantity: 3,
+                total_price: 1_200,
+                fills: 1,
+            },
+        ));
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+        assert!(DoubleAuctionModule::auctions(0).is_some());
+
+        // assert that a second order fills the remaining auction
+        assert_ok!(DoubleAuctionModule::market_buy(bob, 1, 5, 1_500));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::MarketBuyExecuted {
+                buyer_id: bob_id,
+                tier: 1,
+                energy_quantity: 2,
+                total_price: 1_000,
+                fills: 1,
+            },
+        ));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+    });
+}
+
+#[test]
+fn market_sell_should_fill_best_buy_orders_first() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // charlie wants 3 kWh at up to 500 per kWh, bob 2 kWh at up to 600 per kWh
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            3,
+            500,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(bob_id.clone()),
+            1,
+            2,
+            600,
+            None
+        ));
+
+        // a tier without buy orders has no liquidity
+        assert_noop!(
+            DoubleAuctionModule::market_sell(RuntimeOrigin::signed(alice_id.clone()), 2, 4, 0),
+            Error::<Test>::NoMarketLiquidity
+        );
+
+        // proceeds below the seller's minimum fail the sale
+        assert_noop!(
+            DoubleAuctionModule::market_sell(RuntimeOrigin::signed(alice_id.clone()), 1, 4, 3_000),
+            Error::<Test>::ProceedsBelowMinimum
+        );
+
+        // dispatch signed extrinsic for market sell
+        assert_ok!(DoubleAuctionModule::market_sell(
+            RuntimeOrigin::signed(alice_id.clone()),
+            1,
+            4,
+            2_000
+        ));
+
+        // assert that bob's order was filled first and charlie's with the rest
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BuyOrderFilled {
+            order_id: 1,
+            buyer_id: bob_id,
+            auction_id: 0,
+            energy_quantity: 2,
+            price: 1_200,
+        }));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BuyOrderFilled {
+            order_id: 0,
+            buyer_id: charlie_id,
+            auction_id: 1,
+            energy_quantity: 2,
+            price: 1_000,
+        }));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::MarketSellExecuted {
+                seller_id: alice_id,
+                tier: 1,
+                energy_quantity: 4,
+                total_price: 2_200,
+                fills: 2,
+            },
+        ));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+        let orders = DoubleAuctionModule::buy_orders(1);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].quantity, 1);
+    });
+}
+
+#[test]
+fn force_execute_should_settle_auction_before_end() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 2_000, None));
+
+        // assert that only governance can force execution
+        assert_noop!(
+            DoubleAuctionModule::force_execute(seller, 0),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            DoubleAuctionModule::force_execute(RuntimeOrigin::root(), 1),
+            Error::<Test>::AuctionDoesNotExist
+        );
+
+        // assert that the auction settles and leaves the execution queue
+        assert_ok!(DoubleAuctionModule::force_execute(RuntimeOrigin::root(), 0));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::ForceExecuted {
+            auction_id: 0,
+            matched: true,
+        }));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert_eq!(DoubleAuctionModule::auction_execution_queue(52, 0), None);
+    });
+}
+
+#[test]
+fn only_seller_or_force_origin_should_cancel_auction() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        for _ in 0..2 {
+            assert_ok!(DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+
+        // assert that other signers cannot cancel the seller's auction
+        assert_noop!(
+            DoubleAuctionModule::cancel(buyer.clone(), 0),
+            Error::<Test>::NotAuctionOwner
+        );
+        assert_noop!(
+            DoubleAuctionModule::force_cancel(buyer, 0),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // assert that the seller and governance can
+        assert_ok!(DoubleAuctionModule::cancel(seller, 0));
+        assert_ok!(DoubleAuctionModule::force_cancel(RuntimeOrigin::root(), 1));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+        assert_noop!(
+            DoubleAuctionModule::force_cancel(RuntimeOrigin::root(), 1),
+            Error::<Test>::AuctionDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn lapsed_match_should_fall_to_next_bidder() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        AcceptanceWindow::set(5);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            2_500,
+            None
+        ));
+
+        // assert that the match waits for the highest bidder to accept
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionAwaitingAcceptance {
+                auction_id: 0,
+                buyer_id: charlie_id.clone(),
+                accept_by: 57,
+            },
+        ));
+        assert_noop!(
+            DoubleAuctionModule::accept_match(RuntimeOrigin::signed(bob_id.clone()), 0),
+            Error::<Test>::NotMatchedBuyer
+        );
+
+        // assert that the lapsed match falls to the next highest bidder
+        System::set_block_number(57);
+        finalize_block(57);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AcceptanceLapsed {
+            auction_id: 0,
+            buyer_id: charlie_id,
+        }));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionAwaitingAcceptance {
+                auction_id: 0,
+                buyer_id: bob_id.clone(),
+                accept_by: 62,
+            },
+        ));
+
+        // assert that accepting settles the auction
+        assert_ok!(DoubleAuctionModule::accept_match(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id: AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            )),
+            buyer_id: bob_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 57,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 62,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::awaiting_acceptance(0).is_none());
+    });
+}
+
+#[test]
+fn winner_unable_to_pay_should_be_skipped() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let dave_id = AccountId::from(AccountId32::from(
+            b"0000000000000000000000DAVE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+
+        // unfunded dave outbids bob
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(dave_id.clone()),
+            0,
+            3_000,
+            None
+        ));
+
+        // assert that dave is skipped and bob wins at his own bid
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::WinnerSkipped {
+            auction_id: 0,
+            buyer_id: dave_id.clone(),
+        }));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id,
+            buyer_id: bob_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 52,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert_eq!(DoubleAuctionModule::skipped_wins(&dave_id), 1);
+    });
+}
+
+#[test]
+fn organization_members_should_share_limits() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let admin_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let trader_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        let admin = RuntimeOrigin::signed(admin_id.clone());
+        let trader = RuntimeOrigin::signed(trader_id.clone());
+
+        assert_ok!(DoubleAuctionModule::create_organization(
+            admin.clone(),
+            3_000,
+            2_500
+        ));
+        assert_noop!(
+            DoubleAuctionModule::set_organization_member(
+                trader.clone(),
+                0,
+                trader_id.clone(),
+                true
+            ),
+            Error::<Test>::NotOrganizationAdmin
+        );
+        assert_ok!(DoubleAuctionModule::set_organization_member(
+            admin.clone(),
+            0,
+            trader_id.clone(),
+            true
+        ));
+        assert_eq!(DoubleAuctionModule::organization_of(&trader_id), Some(0));
+
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that bids above the shared limits are rejected
+        assert_noop!(
+            DoubleAuctionModule::bid(trader.clone(), 0, 3_500, None),
+            Error::<Test>::OrganizationBidLimitExceeded
+        );
+        assert_noop!(
+            DoubleAuctionModule::bid(trader.clone(), 0, 2_800, None),
+            Error::<Test>::OrganizationBudgetExceeded
+        );
+
+        // assert that bids are attributed to the member
+        assert_ok!(DoubleAuctionModule::bid(trader, 0, 2_000, None));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::OrganizationBidPlaced {
+                organization: 0,
+                member: trader_id.clone(),
+                auction_id: 0,
+                bid: 2_000,
+            },
+        ));
+
+        // assert that settled purchases are counted against the shared budget
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::OrganizationBudgetSpent {
+                organization: 0,
+                member: trader_id,
+                auction_id: 0,
+                amount: 2_000,
+            },
+        ));
+        assert_eq!(DoubleAuctionModule::organization(0).unwrap().spent, 2_000);
+
+        // assert that other members are bound by what is left of the budget
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_noop!(
+            DoubleAuctionModule::bid(admin, 1, 1_500, None),
+            Error::<Test>::OrganizationBudgetExceeded
+        );
+    });
+}
+
+#[test]
+fn cancelled_auction_with_bids_should_delay_identical_listing() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 2_000, None));
+        assert_ok!(DoubleAuctionModule::cancel(seller.clone(), 0));
+
+        // assert that the same quantity can not be listed during the cooldown
+        assert_noop!(
+            DoubleAuctionModule::new(seller.clone(), 2, 1_000, 5, Default::default()),
+            Error::<Test>::ListingCooldown
+        );
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            3,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that the quantity can be listed again after the cooldown
+        System::set_block_number(22);
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_eq!(
+            DoubleAuctionModule::listing_cooldown(
+                AccountId::from(AccountId32::from(
+                    b"000000000000000000000ALICE000000".clone()
+                )),
+                2
+            ),
+            None
+        );
+    });
+}
+
+#[test]
+fn bid_histogram_should_count_bids_over_recent_eras() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // unit prices of 1_000, 1_500 and 2_500 in buckets of 500, the last bucket is open
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 2_000, None));
+        assert_ok!(DoubleAuctionModule::bid(buyer.clone(), 0, 3_000, None));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 5_000, None));
+        assert_eq!(DoubleAuctionModule::bid_histogram(1), vec![0, 0, 1, 2]);
+        assert_eq!(DoubleAuctionModule::bid_histogram(2), vec![0, 0, 0, 0]);
+
+        // assert that bids of eras outside the window are dropped
+        System::set_block_number(200);
+        DoubleAuctionModule::on_initialize(200);
+        assert_eq!(DoubleAuctionModule::bid_histogram(1), vec![0, 0, 0, 0]);
+        assert!(DoubleAuctionModule::era_bid_histogram(1, 0).is_empty());
+    });
+}
+
+#[test]
+fn tier_subscribers_should_be_notified_of_listings_and_clearings() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        let charlie = RuntimeOrigin::signed(charlie_id.clone());
+
+        assert_ok!(DoubleAuctionModule::subscribe_tier(charlie.clone(), 1));
+        assert_noop!(
+            DoubleAuctionModule::subscribe_tier(charlie.clone(), 1),
+            Error::<Test>::AlreadySubscribed
+        );
+        assert_ok!(DoubleAuctionModule::subscribe_tier(buyer.clone(), 1));
+        assert_noop!(
+            DoubleAuctionModule::subscribe_tier(seller.clone(), 1),
+            Error::<Test>::TooManySubscribers
+        );
+
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 2_000, None));
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that the subscriber followed the auction without taking part
+        let outcomes: Vec<_> = DoubleAuctionModule::notifications_of(&charlie_id)
+            .into_iter()
+            .map(|notification| notification.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Listed {
+                    tier: 1,
+                    price: 1_000
+                },
+                Outcome::Cleared {
+                    tier: 1,
+                    price: 2_000
+                },
+            ]
+        );
+
+        assert_ok!(DoubleAuctionModule::unsubscribe_tier(charlie.clone(), 1));
+        assert_noop!(
+            DoubleAuctionModule::unsubscribe_tier(charlie, 1),
+            Error::<Test>::NotSubscribed
+        );
+    });
+}
+
+#[test]
+#[should_panic(expected = "`FeeRate`, `CommissionRate` and `ReferralShare` must be below 100%")]
+fn integrity_test_should_reject_full_fee_rate() {
+    new_test_ext().execute_with(|| {
+        // assert that the mock runtime is sound
+        <DoubleAuctionModule as Hooks<u64>>::integrity_test();
+
+        // assert that a fee taking the whole payment fails fast
+        FeeRate::set(Permill::one());
+        <DoubleAuctionModule as Hooks<u64>>::integrity_test();
+    });
+}
+
+#[test]
+fn governance_should_override_market_parameters() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that only governance can tune the market
+        assert_noop!(
+            DoubleAuctionModule::set_parameter(
+                buyer.clone(),
+                MarketParameter::MinBidIncrement(Some(500))
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(DoubleAuctionModule::set_parameter(
+            RuntimeOrigin::root(),
+            MarketParameter::MinBidIncrement(Some(500))
+        ));
+        assert_eq!(DoubleAuctionModule::min_bid_increment(), 500);
+
+        // assert that governance cannot stop auctions from executing
+        assert_noop!(
+            DoubleAuctionModule::set_parameter(
+                RuntimeOrigin::root(),
+                MarketParameter::MaxExecutionsPerBlock(Some(0))
+            ),
+            Error::<Test>::InvalidParameter
+        );
+
+        // assert that the override applies to bids
+        assert_ok!(DoubleAuctionModule::bid(buyer, 0, 1_200, None));
+        assert_eq!(
+            DoubleAuctionModule::auctions(0).unwrap().highest_bid.bidder,
+            seller_id
+        );
+
+        // assert that clearing the override restores the constant
+        assert_ok!(DoubleAuctionModule::set_parameter(
+            RuntimeOrigin::root(),
+            MarketParameter::MinBidIncrement(None)
+        ));
+        assert_eq!(DoubleAuctionModule::min_bid_increment(), 1);
+        assert_eq!(
+            DoubleAuctionModule::commission_rate(),
+            Perbill::from_percent(10)
+        );
+    });
+}
+
+#[test]
+fn settlement_lag_should_delay_settlement_after_bidding_closes() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+
+        assert_noop!(
+            DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                ListingOptions {
+                    settlement_lag: Some(20_000),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::SettlementLagTooLong
+        );
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                settlement_lag: Some(10),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(buyer_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+
+        // assert that bidding closes at the end of the auction period
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::SettlementScheduled {
+                auction_id: 0,
+                buyer_id: buyer_id.clone(),
+                settle_at: 62,
+            },
+        ));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::awaiting_settlement(0).is_some());
+
+        // assert that the auction settles after the lag
+        System::set_block_number(62);
+        finalize_block(62);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id,
+            buyer_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 62,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 62,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::awaiting_settlement(0).is_none());
+    });
+}
+
+#[test]
+fn execution_handler_should_receive_settlements_once_paid() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let buyer_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                settlement_lag: Some(10),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(buyer_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+
+        // assert that a match waiting for settlement is not reported yet
+        System::set_block_number(52);
+        finalize_block(52);
+        assert!(ExecutedAuctions::get().is_empty());
+
+        // assert that the handler is told of the settled trade at its clearing price
+        System::set_block_number(62);
+        finalize_block(62);
+        assert_eq!(
+            ExecutedAuctions::get(),
+            vec![(0, seller_id, buyer_id, 2, 2_000)]
+        );
+    });
+}
+
+#[test]
+fn order_pair_should_settle_buy_leg_once_sell_leg_clears() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let operator_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let operator = RuntimeOrigin::signed(operator_id.clone());
+        let charlie = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        )));
+
+        // operator buys from alice until block 52 and sells until block 102
+        assert_ok!(DoubleAuctionModule::new(
+            alice,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            operator.clone(),
+            2,
+            1_000,
+            10,
+            Default::default()
+        ));
+
+        assert_noop!(
+            DoubleAuctionModule::pair_orders(operator.clone(), 1, 2_000, 0),
+            Error::<Test>::NotAuctionOwner
+        );
+        assert_ok!(DoubleAuctionModule::pair_orders(operator, 0, 2_000, 1));
+        assert_ok!(DoubleAuctionModule::bid(charlie, 1, 3_000, None));
+
+        // assert that the bought leg waits for the sell leg
+        System::set_block_number(52);
+        finalize_block(52);
+        assert!(DoubleAuctionModule::awaiting_settlement(0).is_some());
+
+        // assert that both legs settle once the sell leg is matched
+        System::set_block_number(102);
+        finalize_block(102);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::OrderPairSettled {
+            owner: operator_id.clone(),
+            buy_auction_id: 0,
+            sell_auction_id: 1,
+        }));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id: AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            )),
+            buyer_id: operator_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 102,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::order_pair(0).is_none());
+    });
+}
+
+#[test]
+fn order_pair_should_unwind_buy_leg_when_sell_leg_fails() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let operator_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let operator = RuntimeOrigin::signed(operator_id.clone());
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        assert_ok!(DoubleAuctionModule::new(
+            alice,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            operator.clone(),
+            2,
+            1_000,
+            10,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            1_500,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::pair_orders(operator, 0, 2_000, 1));
+
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that the unsold sell leg rejects the pair and the buy leg falls to charlie
+        System::set_block_number(102);
+        finalize_block(102);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::OrderPairRejected {
+                owner: operator_id,
+                buy_auction_id: 0,
+                sell_auction_id: 1,
+            },
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id: AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            )),
+            buyer_id: charlie_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 1_500,
+            clearing_price: 1_500,
+            executed_at: 102,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+    });
+}
+
+#[test]
+fn market_status_should_report_tier_health() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+
+        // auctions of the first tier end at blocks 52 and 102
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            3,
+            1_000,
+            10,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::set_tier_halted(
+            RuntimeOrigin::root(),
+            2,
+            true
+        ));
+
+        System::set_block_number(12);
+        assert_eq!(
+            DoubleAuctionModule::market_status(),
+            vec![
+                MarketStatus {
+                    tier: 1,
+                    state: TierState::Open,
+                    open_auctions: 2,
+                    awaiting_acceptance: 0,
+                    awaiting_settlement: 0,
+                    next_clearing_in: Some(40),
+                },
+                MarketStatus {
+                    tier: 2,
+                    state: TierState::Halted,
+                    open_auctions: 0,
+                    awaiting_acceptance: 0,
+                    awaiting_settlement: 0,
+                    next_clearing_in: None,
+                },
+            ]
+        );
+
+        // assert that an ongoing migration is reported for open tiers only
+        DoubleAuctionModule::start_stepped_migration();
+        let states: Vec<TierState> = DoubleAuctionModule::market_status()
+            .into_iter()
+            .map(|status| status.state)
+            .collect();
+        assert_eq!(states, vec![TierState::Migrating, TierState::Halted]);
+    });
+}
+
+#[test]
+fn market_status_should_count_matches_awaiting_acceptance() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        AcceptanceWindow::set(5);
+
+        let bob = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            ))),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(bob.clone(), 0, 2_000, None));
+
+        // assert that the match is counted in its tier until accepted
+        System::set_block_number(52);
+        finalize_block(52);
+        let status = DoubleAuctionModule::market_status()[0].clone();
+        assert_eq!(status.open_auctions, 0);
+        assert_eq!(status.awaiting_acceptance, 1);
+        assert_eq!(DoubleAuctionModule::tier_queue_depths(1), (1, 0));
+
+        assert_ok!(DoubleAuctionModule::accept_match(bob, 0));
+        assert_eq!(
+            DoubleAuctionModule::market_status()[0].awaiting_acceptance,
+            0
+        );
+        assert_eq!(DoubleAuctionModule::tier_queue_depths(1), (0, 0));
+    });
+}
+
+#[test]
+fn simulation_should_match_without_moving_funds() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        Simulation::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let dave_id = AccountId::from(AccountId32::from(
+            b"0000000000000000000000DAVE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that no storage deposit is held
+        assert_eq!(Balances::reserved_balance(&seller_id), 0);
+
+        // unfunded dave bids on the paper market
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(dave_id.clone()),
+            0,
+            3_000,
+            None
+        ));
+
+        // assert that dave is matched and nobody is charged
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id: seller_id.clone(),
+            buyer_id: dave_id.clone(),
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 3_000,
+            clearing_price: 3_000,
+            executed_at: 52,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert_eq!(DoubleAuctionModule::skipped_wins(&dave_id), 0);
+        assert_eq!(
+            Balances::free_balance(&DoubleAuctionModule::account_id()),
+            0
+        );
+        assert_eq!(Balances::free_balance(&seller_id), 1_000_000_000);
+    });
+}
+
+#[test]
+fn standing_buy_order_should_take_crossing_listings() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // charlie wants 4 kWh of the first tier at up to 600 per kWh
+        assert_noop!(
+            DoubleAuctionModule::place_buy_order(
+                RuntimeOrigin::signed(charlie_id.clone()),
+                3,
+                4,
+                600,
+                None
+            ),
+            Error::<Test>::InvalidTier
+        );
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            4,
+            600,
+            None
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::BuyOrderPlaced {
+            order_id: 0,
+            buyer_id: charlie_id.clone(),
+            tier: 1,
+            energy_quantity: 4,
+            max_unit_price: 600,
+        }));
+
+        // assert that a crossing listing is bought at the order's price
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BuyOrderFilled {
+            order_id: 0,
+            buyer_id: charlie_id.clone(),
+            auction_id: 0,
+            energy_quantity: 2,
+            price: 1_200,
+        }));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id: alice_id.clone(),
+            buyer_id: charlie_id.clone(),
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 1_200,
+            clearing_price: 1_200,
+            executed_at: 2,
+            auction_category: Tier { level: 1 },
+            bid_count: 0,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert_eq!(DoubleAuctionModule::buy_orders(1)[0].quantity, 2);
+
+        // assert that listings above the remaining quantity or the price stay open
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id),
+            3,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(bob_id.clone()),
+            2,
+            1_500,
+            5,
+            Default::default()
+        ));
+        assert!(DoubleAuctionModule::auctions(1).is_some());
+        assert!(DoubleAuctionModule::auctions(2).is_some());
+
+        // assert that only the buyer withdraws the order
+        assert_noop!(
+            DoubleAuctionModule::cancel_buy_order(RuntimeOrigin::signed(bob_id), 1, 0),
+            Error::<Test>::NotBuyOrderOwner
+        );
+        assert_ok!(DoubleAuctionModule::cancel_buy_order(
+            RuntimeOrigin::signed(charlie_id),
+            1,
+            0
+        ));
+        assert!(DoubleAuctionModule::buy_orders(1).is_empty());
+    });
+}
+
+#[test]
+fn partial_fill_should_split_quantity_across_bids() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // 4 kWh at 100 per kWh, split across buyers
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            4,
+            400,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::PartialFill),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            4,
+            400,
+            5,
+            Default::default()
+        ));
+
+        assert_noop!(
+            DoubleAuctionModule::bid_quantity(RuntimeOrigin::signed(bob_id.clone()), 1, 2, 400),
+            Error::<Test>::NotPartialFill
+        );
+        assert_noop!(
+            DoubleAuctionModule::bid_quantity(RuntimeOrigin::signed(bob_id.clone()), 0, 5, 800),
+            Error::<Test>::InvalidBidQuantity
+        );
+        assert_noop!(
+            DoubleAuctionModule::bid_quantity(RuntimeOrigin::signed(bob_id.clone()), 0, 1, 50),
+            Error::<Test>::BidBelowReserve
+        );
+
+        // bob pays 200 per kWh for 2 kWh, charlie 150 per kWh for 3 kWh
+        assert_ok!(DoubleAuctionModule::bid_quantity(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            3,
+            450
+        ));
+        assert_ok!(DoubleAuctionModule::bid_quantity(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2,
+            400
+        ));
+
+        // assert that bob fills first and charlie takes the rest pro rata
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id: seller_id.clone(),
+            buyer_id: bob_id.clone(),
+            energy_quantity: 2,
+            starting_price: 400,
+            highest_bid: 400,
+            clearing_price: 400,
+            executed_at: 52,
+            auction_category: Tier { level: 1 },
+            bid_count: 2,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id,
+            buyer_id: charlie_id.clone(),
+            energy_quantity: 2,
+            starting_price: 400,
+            highest_bid: 300,
+            clearing_price: 300,
+            executed_at: 52,
+            auction_category: Tier { level: 1 },
+            bid_count: 2,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert_eq!(
+            DoubleAuctionModule::auction_fills(0).into_inner(),
+            vec![
+                Fill {
+                    buyer: bob_id,
+                    quantity: 2,
+                    price: 400,
+                },
+                Fill {
+                    buyer: charlie_id,
+                    quantity: 2,
+                    price: 300,
+                },
+            ]
+        );
+    });
+}
+
+#[test]
+fn on_chain_settlement_should_hold_bids_and_pay_seller() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that leading bids are held and released once outbid
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_eq!(DoubleAuctionModule::escrowed(0, &bob_id), 2_000);
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            3_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::top_up_bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            500
+        ));
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_eq!(DoubleAuctionModule::escrowed(0, &bob_id), 0);
+        assert_eq!(Balances::reserved_balance(&charlie_id), 3_500);
+
+        // assert that the losing bid is released and the winning bid paid to the seller
+        System::set_block_number(52);
+        finalize_block(52);
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_eq!(Balances::reserved_balance(&charlie_id), 0);
+        assert_eq!(Balances::reserved_balance(&seller_id), 0);
+        assert_eq!(Balances::free_balance(&bob_id), 1_000_000_000);
+        // both parties pay a 10% commission
+        assert_eq!(
+            Balances::free_balance(&charlie_id),
+            1_000_000_000 - 3_500 - 350
+        );
+        assert_eq!(
+            Balances::free_balance(&seller_id),
+            1_000_000_000 + 3_500 - 350
+        );
+    });
+}
+
+#[test]
+fn escrow_should_release_bids_on_cancel() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_eq!(Balances::reserved_balance(&bob_id), 2_000);
+
+        // assert that cancelling the auction releases the escrowed bid
+        assert_ok!(DoubleAuctionModule::cancel(seller, 0));
+        assert_eq!(DoubleAuctionModule::escrowed(0, &bob_id), 0);
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_eq!(Balances::free_balance(&bob_id), 1_000_000_000);
+    });
+}
+
+#[test]
+fn asset_auction_should_hold_bids_and_pay_seller_in_its_asset() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                payment_asset: Some(STABLECOIN),
+                ..Default::default()
+            }
+        ));
+        assert_eq!(DoubleAuctionModule::payment_asset(0), Some(STABLECOIN));
+
+        // assert that leading bids are held in the asset and released once outbid
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_eq!(DoubleAuctionModule::escrowed(0, &bob_id), 2_000);
+        assert_eq!(Assets::balance(STABLECOIN, &bob_id), 1_000_000_000 - 2_000);
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            3_000,
+            None
+        ));
+        assert_eq!(Assets::balance(STABLECOIN, &bob_id), 1_000_000_000);
+        assert_eq!(
+            Assets::balance(STABLECOIN, DoubleAuctionModule::account_id()),
+            3_000
+        );
+
+        // assert that the winning bid and both commissions are paid in the asset
+        System::set_block_number(52);
+        finalize_block(52);
+        assert_eq!(
+            Assets::balance(STABLECOIN, &charlie_id),
+            1_000_000_000 - 3_000 - 300
+        );
+        assert_eq!(
+            Assets::balance(STABLECOIN, &seller_id),
+            1_000_000_000 + 3_000 - 300
+        );
+        assert_eq!(
+            Assets::balance(STABLECOIN, DoubleAuctionModule::account_id()),
+            600
+        );
+        assert_eq!(Balances::free_balance(&charlie_id), 1_000_000_000);
+        assert_eq!(Balances::free_balance(&seller_id), 1_000_000_000);
+    });
+}
+
+#[test]
+fn new_should_fail_for_unpayable_asset() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+
+        // assert that an asset that does not exist is rejected
+        assert_noop!(
+            DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                ListingOptions {
+                    payment_asset: Some(STABLECOIN + 1),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::UnknownAsset
+        );
+
+        // assert that uniform-price clearings, selling to orders paying in currency, are rejected
+        assert_noop!(
+            DoubleAuctionModule::new(
+                seller,
+                2,
+                1_000,
+                5,
+                ListingOptions {
+                    match_rule: Some(MatchingAlgorithm::UniformPrice),
+                    payment_asset: Some(STABLECOIN),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::AssetPaymentUnsupported
+        );
+    });
+}
+
+#[test]
+fn bid_should_fail_beyond_max_bids_per_auction() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxBidsPerAuction::set(1);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            None
+        ));
+
+        // assert that a new bidder is rejected once the auction is full
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(charlie), 0, 3_000, None),
+            Error::<Test>::TooManyBids
+        );
+
+        // assert that existing bidders can still raise their bid
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob),
+            0,
+            3_000,
+            None
+        ));
+        assert_eq!(
+            DoubleAuctionModule::auctions(0).expect("auction").bid_count,
+            1
+        );
+    });
+}
+
+#[test]
+fn cancel_bid_should_restore_next_best_bid() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            3_000,
+            None
+        ));
+        assert_eq!(Balances::reserved_balance(&charlie), 3_000);
+
+        // assert that the leader withdraws and bob leads again
+        assert_ok!(DoubleAuctionModule::cancel_bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::BidCanceled {
+            auction_id: 0,
+            bidder: charlie.clone(),
+            amount: 3_000,
+        }));
+        let auction = DoubleAuctionModule::auctions(0).expect("auction");
+        assert_eq!(auction.highest_bid.bidder, bob);
+        assert_eq!(auction.highest_bid.bid, 2_000);
+        assert_eq!(auction.bid_count, 1);
+        assert_eq!(Balances::reserved_balance(&charlie), 0);
+        assert!(DoubleAuctionModule::bid_of(0, &charlie).is_none());
+
+        // assert that a withdrawn bid cannot be withdrawn again
+        assert_noop!(
+            DoubleAuctionModule::cancel_bid(RuntimeOrigin::signed(charlie), 0),
+            Error::<Test>::BidDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn auction_below_reserve_should_end_unsold() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                reserve_price: Some(ReservePrice {
+                    price: 2_500,
+                    relist: false
+                }),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            3,
+            1_000,
+            5,
+            ListingOptions {
+                reserve_price: Some(ReservePrice {
+                    price: 2_500,
+                    relist: true
+                }),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            1,
+            2_000,
+            None
+        ));
+
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that neither auction is matched below its reserve
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionUnsold {
+            auction_id: 0,
+            seller_id: seller_id.clone(),
+            highest_bid: 2_000,
+            reserve_price: 2_500,
+            relisted_as: None,
+        }));
+        assert!(DoubleAuctionModule::unsold_auctions(0).is_some());
+
+        // assert that the auction asking for it is listed again for another period
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionUnsold {
+            auction_id: 1,
+            seller_id: seller_id.clone(),
+            highest_bid: 2_000,
+            reserve_price: 2_500,
+            relisted_as: Some(2),
+        }));
+        assert!(DoubleAuctionModule::unsold_auctions(1).is_none());
+        let relisted = DoubleAuctionModule::auctions(2).expect("relisted auction");
+        assert_eq!(relisted.quantity, 3);
+        assert_eq!(relisted.end_at, 102);
+        assert_eq!(
+            relisted.reserve_price.map(|reserve| reserve.price),
+            Some(2_500)
+        );
+        assert_eq!(relisted.bid_count, 0);
+    });
+}
+
+#[test]
+fn sealed_bid_auction_should_match_revealed_bids_only() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::SealedBid),
+                ..Default::default()
+            }
+        ));
+
+        // assert that open bids are rejected and commitments hide the price
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(bob.clone()), 0, 2_000, None),
+            Error::<Test>::SealedBidAuction
+        );
+        let bob_salt = [1u8; 32];
+        assert_ok!(DoubleAuctionModule::commit_bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            DoubleAuctionModule::sealed_commitment(&bob, 0, 2_000, &bob_salt)
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::BidCommitted {
+            auction_id: 0,
+            bidder: bob.clone(),
+        }));
+        assert_ok!(DoubleAuctionModule::commit_bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            DoubleAuctionModule::sealed_commitment(&charlie, 0, 3_000, &[2u8; 32])
+        ));
+        assert_eq!(
+            DoubleAuctionModule::auctions(0)
+                .expect("auction")
+                .highest_bid
+                .bid,
+            1_000
+        );
+        assert_noop!(
+            DoubleAuctionModule::reveal_bid(RuntimeOrigin::signed(bob.clone()), 0, 2_000, bob_salt),
+            Error::<Test>::NotRevealPhase
+        );
+
+        // assert that reveals must match the commitment once the reveal phase starts
+        System::set_block_number(42);
+        assert_noop!(
+            DoubleAuctionModule::commit_bid(
+                RuntimeOrigin::signed(bob.clone()),
+                0,
+                DoubleAuctionModule::sealed_commitment(&bob, 0, 4_000, &bob_salt)
+            ),
+            Error::<Test>::CommitPhaseOver
+        );
+        assert_noop!(
+            DoubleAuctionModule::reveal_bid(RuntimeOrigin::signed(bob.clone()), 0, 2_500, bob_salt),
+            Error::<Test>::InvalidReveal
+        );
+        assert_ok!(DoubleAuctionModule::reveal_bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_000,
+            bob_salt
+        ));
+        assert!(DoubleAuctionModule::sealed_bid(0, &bob).is_none());
+
+        // assert that charlie's higher but unrevealed bid is not matched
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id,
+            buyer_id: bob,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 52,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::sealed_bid(0, &charlie).is_none());
+    });
+}
+
+#[test]
+fn vickrey_auction_should_clear_at_second_highest_bid() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::Vickrey),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            3_000,
+            None
+        ));
+
+        // assert that charlie wins with its bid and pays bob's
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id: seller_id.clone(),
+            buyer_id: charlie.clone(),
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 3_000,
+            clearing_price: 2_000,
+            executed_at: 52,
+            auction_category: Tier { level: 1 },
+            bid_count: 2,
+            end_at: 52,
+            price_basis: PriceBasis::SecondPrice,
+        }));
+        assert_eq!(Balances::reserved_balance(&charlie), 0);
+        assert_eq!(
+            Balances::free_balance(&charlie),
+            1_000_000_000 - 2_000 - 200
+        );
+        assert_eq!(
+            Balances::free_balance(&seller_id),
+            1_000_000_000 + 2_000 - 200
+        );
+    });
+}
+
+#[test]
+fn dutch_auction_should_sell_to_first_bid_reaching_the_ask() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+        assert_noop!(
+            DoubleAuctionModule::new_dutch(seller.clone(), 2, 1_000, 3_000, 500, 10, 5),
+            Error::<Test>::InvalidPriceDecay
+        );
+        assert_ok!(DoubleAuctionModule::new_dutch(
+            seller, 2, 3_000, 1_000, 500, 10, 5
+        ));
+        assert_eq!(DoubleAuctionModule::dutch_price(0), Some(3_000));
+
+        // assert that the ask drops every interval and bids below it are rejected
+        System::set_block_number(22);
+        assert_eq!(DoubleAuctionModule::dutch_price(0), Some(2_000));
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(bob.clone()), 0, 1_500, None),
+            Error::<Test>::BidBelowAsk
+        );
+
+        // assert that the first bid reaching the ask buys at the ask
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob.clone()),
+            0,
+            2_500,
+            None
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id,
+            buyer_id: bob,
+            energy_quantity: 2,
+            starting_price: 3_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 22,
+            auction_category: Tier { level: 1 },
+            bid_count: 0,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::price_decay(0).is_none());
+    });
+}
+
+#[test]
+fn uniform_price_auctions_should_clear_together_at_interval() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // charlie wants 4 kWh of the first tier at up to 700 per kWh
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            4,
+            700,
+            None
+        ));
+
+        // alice asks 500 and bob 600 per kWh
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::UniformPrice),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(bob_id),
+            2,
+            1_200,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::UniformPrice),
+                ..Default::default()
+            }
+        ));
+
+        // assert that uniform-price auctions take no open bids and wait for the clearing
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(charlie_id.clone()), 0, 1_500, None),
+            Error::<Test>::UniformPriceAuction
+        );
+        assert!(DoubleAuctionModule::auctions(0).is_some());
+        assert!(DoubleAuctionModule::auctions(1).is_some());
+
+        // assert that both clear at the midpoint of the marginal ask and the lowest limit
+        System::set_block_number(10);
+        DoubleAuctionModule::on_initialize(10);
+        finalize_block(10);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::TierCleared {
+            tier: 1,
+            zone: 0,
+            clearing_price: 650,
+            matched_volume: 4,
+            results: BoundedVec::truncate_from(Vec::from([
+                (0, charlie_id.clone(), 2, 1_300),
+                (1, charlie_id.clone(), 2, 1_300),
+            ])),
+        }));
+
+        // assert that the sales are only reported by the clearing
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::DoubleAuctionModule(
+                Event::AuctionExecuted { .. }
+                    | Event::AuctionMatched { .. }
+                    | Event::AuctionStatusChanged { .. }
+                    | Event::BuyOrderFilled { .. }
+            )
+        )));
+        // assert that the funds they move are still recorded
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::CommissionCharged {
+                auction_id: 0,
+                payer: charlie_id.clone(),
+                amount: 130,
+            },
+        ));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+        assert!(DoubleAuctionModule::buy_orders(1).is_empty());
+    });
+}
+
+#[test]
+fn late_bid_should_extend_auction() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        ExtensionThreshold::set(10);
+        ExtensionPeriod::set(20);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that a bid before the last blocks keeps the deadline
+        assert_ok!(DoubleAuctionModule::bid(bob.clone(), 0, 1_100, None));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 52);
+
+        // assert that a bid in the last blocks moves the deadline and the queue entry
+        System::set_block_number(45);
+        assert_ok!(DoubleAuctionModule::bid(bob, 0, 1_200, None));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExtended {
+            auction_id: 0,
+            seller_id,
+            previous_end_at: 52,
+            end_at: 72,
+        }));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 72);
+
+        finalize_block(52);
+        assert!(DoubleAuctionModule::auctions(0).is_some());
+        finalize_block(72);
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+    });
+}
+
+#[test]
+fn governance_should_tune_extension_window() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let bob = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            ))),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::set_parameter(
+            RuntimeOrigin::root(),
+            MarketParameter::ExtensionThreshold(Some(10))
+        ));
+        assert_ok!(DoubleAuctionModule::set_parameter(
+            RuntimeOrigin::root(),
+            MarketParameter::ExtensionPeriod(Some(30))
+        ));
+        assert_eq!(DoubleAuctionModule::extension_threshold(), 10);
+        assert_eq!(DoubleAuctionModule::extension_period(), 30);
+
+        // assert that a late bid is extended by the overridden window
+        System::set_block_number(45);
+        assert_ok!(DoubleAuctionModule::bid(bob.clone(), 0, 1_100, None));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 82);
+
+        // assert that clearing the overrides restores the constants
+        assert_ok!(DoubleAuctionModule::set_parameter(
+            RuntimeOrigin::root(),
+            MarketParameter::ExtensionThreshold(None)
+        ));
+        assert_eq!(DoubleAuctionModule::extension_threshold(), 0);
+        System::set_block_number(80);
+        assert_ok!(DoubleAuctionModule::bid(bob, 0, 1_200, None));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 82);
+    });
+}
+
+#[test]
+fn buyout_should_settle_auction_immediately() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+        let bob = RuntimeOrigin::signed(bob_id.clone());
+
+        // assert that the buy-it-now price cannot undercut the starting price
+        assert_noop!(
+            DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                ListingOptions {
+                    buyout_price: Some(900),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::InvalidBuyoutPrice
+        );
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_noop!(
+            DoubleAuctionModule::buyout(bob.clone(), 0),
+            Error::<Test>::NoBuyoutPrice
+        );
+
+        // assert that buying out settles at the buy-it-now price in the same block
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                buyout_price: Some(2_000),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::buyout(bob.clone(), 1));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 1,
+            seller_id: seller_id.clone(),
+            buyer_id: bob_id.clone(),
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 2,
+            auction_category: Tier { level: 1 },
+            bid_count: 0,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+        assert!(DoubleAuctionModule::buyout_price(1).is_none());
+
+        // assert that a bid below the price waits, one reaching it buys at the price
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                buyout_price: Some(2_000),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(bob.clone(), 2, 1_500, None));
+        assert!(DoubleAuctionModule::auctions(2).is_some());
+        assert_ok!(DoubleAuctionModule::bid(bob, 2, 2_500, None));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 2,
+            seller_id,
+            buyer_id: bob_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 2,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::auctions(2).is_none());
+    });
+}
+
+#[test]
+fn tier_boundaries_should_categorize_new_auctions() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+
+        // assert that without boundaries the threshold splits the tiers
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            6,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_eq!(
+            DoubleAuctionModule::auctions(0)
+                .unwrap()
+                .auction_category
+                .level,
+            2
+        );
+
+        // assert that only governance sets ascending boundaries within the tiers
+        assert_noop!(
+            DoubleAuctionModule::set_tier_boundaries(seller.clone(), vec![10]),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            DoubleAuctionModule::set_tier_boundaries(RuntimeOrigin::root(), vec![10, 20]),
+            Error::<Test>::InvalidTierBoundaries
+        );
+        assert_noop!(
+            DoubleAuctionModule::set_tier_boundaries(RuntimeOrigin::root(), vec![0]),
+            Error::<Test>::InvalidTierBoundaries
+        );
+        assert_ok!(DoubleAuctionModule::set_tier_boundaries(
+            RuntimeOrigin::root(),
+            vec![10]
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::TierBoundariesSet {
+                boundaries: vec![10].try_into().unwrap(),
+            },
+        ));
+
+        // assert that new auctions follow the configured bands
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            6,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_eq!(
+            DoubleAuctionModule::auctions(1)
+                .unwrap()
+                .auction_category
+                .level,
+            1
+        );
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            10,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_eq!(
+            DoubleAuctionModule::auctions(2)
+                .unwrap()
+                .auction_category
+                .level,
+            2
+        );
+    });
+}
+
+#[test]
+fn runtime_api_should_serve_open_auctions_by_tier() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let seller = RuntimeOrigin::signed(seller_id.clone());
+        assert_ok!(DoubleAuctionModule::new(
+            seller.clone(),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                buyout_price: Some(3_000),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            6,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that each tier lists its own auctions
+        let tier_one = DoubleAuctionModule::open_auctions(1);
+        assert_eq!(tier_one.len(), 1);
+        assert_eq!(tier_one[0].auction_id, 0);
+        assert_eq!(tier_one[0].buyout_price, Some(3_000));
+        assert_eq!(DoubleAuctionModule::open_auctions(2)[0].auction_id, 1);
+
+        // assert that the best bid appears once a bid leads
+        assert_eq!(DoubleAuctionModule::best_bid(0), None);
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            1_500,
+            None
+        ));
+        assert_eq!(DoubleAuctionModule::best_bid(0), Some((bob_id, 1_500)));
+        let detail = DoubleAuctionModule::auction_detail(0).unwrap();
+        assert_eq!(detail.seller_id, seller_id);
+        assert_eq!(detail.highest_bid, 1_500);
+        assert_eq!(detail.bid_count, 1);
+        assert!(DoubleAuctionModule::auction_detail(2).is_none());
+    });
+}
+
+#[test]
+fn due_auctions_should_carry_over_when_idle_time_runs_out() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let buyer = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        for auction_id in 0..2 {
+            assert_ok!(DoubleAuctionModule::new(
+                seller.clone(),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+            assert_ok!(DoubleAuctionModule::bid(
+                buyer.clone(),
+                auction_id,
+                2_000,
+                None
+            ));
+        }
+
+        // assert that finalization alone executes nothing
+        System::set_block_number(52);
+        DoubleAuctionModule::on_finalize(52);
+        assert!(DoubleAuctionModule::auctions(0).is_some());
+        assert!(DoubleAuctionModule::auctions(1).is_some());
+
+        // assert that idle time for a single auction executes one and carries the other over
+        System::set_block_number(53);
+        let used = DoubleAuctionModule::on_idle(53, <() as crate::WeightInfo>::execute_auctions(1));
+        assert!(used.all_lte(<() as crate::WeightInfo>::execute_auctions(1)));
+        DoubleAuctionModule::on_finalize(53);
+        assert_eq!(
+            DoubleAuctionModule::auctions(0).is_none() as u32
+                + DoubleAuctionModule::auctions(1).is_none() as u32,
+            1
+        );
+        assert_eq!(DoubleAuctionModule::execution_cursor(), Some(52));
+        let telemetry =
+            DoubleAuctionModule::last_execution_telemetry().expect("telemetry recorded");
+        assert_eq!((telemetry.executed, telemetry.deferred), (1, 1));
+
+        // assert that the next block's idle time executes the carried over auction
+        System::set_block_number(54);
+        finalize_block(54);
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+        assert_eq!(DoubleAuctionModule::execution_cursor(), Some(55));
+    });
+}
+
+#[test]
+fn genesis_should_seed_participants_tiers_and_auctions() {
+    let alice_id = AccountId::from(AccountId32::from(
+        b"000000000000000000000ALICE000000".clone(),
+    ));
+    let mut storage = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(alice_id.clone(), 1_000_000_000)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    GenesisBuild::<Test>::assimilate_storage(
+        &crate::GenesisConfig::<Test> {
+            auction_index: 0,
+            tier_boundaries: vec![3],
+            participants: vec![(alice_id.clone(), PartyType::Seller, true)],
+            auctions: vec![(alice_id.clone(), 4, 1_000, 50)],
+        },
+        &mut storage,
+    )
+    .unwrap();
+
+    sp_io::TestExternalities::from(storage).execute_with(|| {
+        // assert that the market starts populated
+        assert_eq!(
+            DoubleAuctionModule::tier_boundaries(),
+            Some(vec![3].try_into().unwrap())
+        );
+        assert_eq!(
+            DoubleAuctionModule::participant(&alice_id).map(|p| (p.role, p.kyc_verified)),
+            Some((PartyType::Seller, true))
+        );
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.seller_id, alice_id);
+        assert_eq!(auction.auction_category.level, 2);
+        assert_eq!(auction.end_at, 50);
+        assert_eq!(auction.auction_status, AuctionStatus::Open);
+        assert_eq!(DoubleAuctionModule::auctions_index(), Some(1));
+    });
+}
+
+#[test]
+fn update_auction_should_work_before_first_bid() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that only the seller can update
+        assert_noop!(
+            DoubleAuctionModule::update_auction(
+                RuntimeOrigin::signed(bob.clone()),
+                0,
+                6,
+                3_000,
+                10
+            ),
+            Error::<Test>::NotAuctionOwner
+        );
+
+        // assert that the listing is corrected and re-queued
+        assert_ok!(DoubleAuctionModule::update_auction(
+            RuntimeOrigin::signed(alice.clone()),
+            0,
+            6,
+            3_000,
+            10
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.quantity, 6);
+        assert_eq!(auction.auction_category.level, 2);
+        assert_eq!(auction.starting_bid.bid, 3_000);
+        assert_eq!(auction.highest_bid.bid, 3_000);
+        assert_eq!(auction.end_at, 102);
+        assert!(DoubleAuctionModule::auction_execution_queue(52, 0).is_none());
+        assert!(DoubleAuctionModule::auction_execution_queue(102, 0).is_some());
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionUpdated {
+            auction_id: 0,
+            seller_id: alice.clone(),
+            energy_quantity: 6,
+            starting_price: 3_000,
+            previous_end_at: 52,
+            end_at: 102,
+        }));
+
+        // assert that listings with bids can not be updated
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob),
+            0,
+            4_000,
+            None
+        ));
+        assert_noop!(
+            DoubleAuctionModule::update_auction(RuntimeOrigin::signed(alice), 0, 2, 1_000, 5),
+            Error::<Test>::AuctionHasBids
+        );
+    });
+}
+
+#[test]
+fn bid_batch_should_follow_its_mode() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        for _ in 0..2 {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(alice.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+        let bids: BoundedVec<(u64, u128), ConstU32<4>> =
+            vec![(0, 2_000), (1, 500)].try_into().unwrap();
+
+        // assert that a failing bid fails the whole batch
+        assert_noop!(
+            DoubleAuctionModule::bid_batch(
+                RuntimeOrigin::signed(bob.clone()),
+                bids.clone(),
+                BatchMode::AllOrNothing
+            ),
+            Error::<Test>::BidTooLow
+        );
+
+        // assert that a failing bid is skipped in best effort
+        assert_ok!(DoubleAuctionModule::bid_batch(
+            RuntimeOrigin::signed(bob.clone()),
+            bids,
+            BatchMode::BestEffort
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        assert_eq!(auction.highest_bid.bidder, bob.clone());
+        assert_eq!(auction.highest_bid.bid, 2_000);
+        let auction = DoubleAuctionModule::auctions(1).expect("return indexed auction");
+        assert_eq!(auction.bid_count, 0);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BatchBidFailed {
+            buyer_id: bob.clone(),
+            auction_id: 1,
+            error: Error::<Test>::BidTooLow.into(),
+        }));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::BatchBidCompleted {
+                buyer_id: bob,
+                placed: 1,
+                failed: 1,
+            },
+        ));
+    });
+}
+
+#[test]
+fn auctions_ending_in_a_full_block_should_end_in_the_next_free_one() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxAuctionsPerEndBlock::set(2);
+
+        let alice = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        for _ in 0..3 {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(alice.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+
+        // assert that the third auction ends a block later
+        assert_eq!(DoubleAuctionModule::auctions(1).unwrap().end_at, 52);
+        let auction = DoubleAuctionModule::auctions(2).expect("return indexed auction");
+        assert_eq!(auction.end_at, 53);
+        assert_eq!(auction.auction_period, 51);
+        assert!(DoubleAuctionModule::auction_execution_queue(53, 2).is_some());
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionEndAdjusted {
+                auction_id: 2,
+                requested_end_at: 52,
+                end_at: 53,
+            },
+        ));
+    });
+}
+
+#[test]
+fn extended_auction_should_end_in_the_next_free_block() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxAuctionsPerEndBlock::set(2);
+
+        let alice = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        for _ in 0..3 {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(alice.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+
+        // block 53 has room for one more auction
+        assert_ok!(DoubleAuctionModule::extend_auction(
+            RuntimeOrigin::signed(alice.clone()),
+            0,
+            1
+        ));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 53);
+
+        // dispatch signed extrinsic for extend auction into the full block
+        assert_ok!(DoubleAuctionModule::extend_auction(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            1
+        ));
+
+        // assert that the auction ends in the next block with room
+        let auction = DoubleAuctionModule::auctions(1).expect("return indexed auction");
+        assert_eq!(auction.end_at, 54);
+        assert!(DoubleAuctionModule::auction_execution_queue(52, 1).is_none());
+        assert!(DoubleAuctionModule::auction_execution_queue(53, 1).is_none());
+        assert!(DoubleAuctionModule::auction_execution_queue(54, 1).is_some());
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExtended {
+            auction_id: 1,
+            seller_id: alice,
+            previous_end_at: 52,
+            end_at: 54,
+        }));
+    });
+}
+
+#[test]
+fn quote_bid_should_price_the_bid_to_beat() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // 4 kWh at 100 per kWh, split across buyers or sold whole
+        for match_rule in [Some(MatchingAlgorithm::PartialFill), None] {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id.clone()),
+                4,
+                400,
+                5,
+                ListingOptions {
+                    match_rule,
+                    ..Default::default()
+                }
+            ));
+        }
+        assert_ok!(DoubleAuctionModule::bid_quantity(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2,
+            300
+        ));
+        assert_ok!(DoubleAuctionModule::bid_quantity(
+            RuntimeOrigin::signed(charlie_id),
+            0,
+            1,
+            120
+        ));
+
+        // assert that partial fills are quoted against the bids they would push out
+        assert_eq!(DoubleAuctionModule::quote_bid(0, 1), Some(100));
+        assert_eq!(DoubleAuctionModule::quote_bid(0, 2), Some(241));
+        assert_eq!(DoubleAuctionModule::quote_bid(0, 3), Some(451));
+        assert_eq!(DoubleAuctionModule::quote_bid(0, 5), None);
+
+        // assert that whole auctions are quoted above their leading bid
+        assert_eq!(DoubleAuctionModule::quote_bid(1, 2), None);
+        assert_eq!(DoubleAuctionModule::quote_bid(1, 4), Some(400));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id),
+            1,
+            500,
+            None
+        ));
+        assert_eq!(DoubleAuctionModule::quote_bid(1, 4), Some(501));
+        assert_eq!(DoubleAuctionModule::quote_bid(2, 4), None);
+    });
+}
+
+#[test]
+fn auction_without_bids_should_be_relisted_at_a_discount() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxRelistAttempts::set(1);
+        RelistDiscount::set(Permill::from_percent(10));
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // auction expires without bids
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // assert that it was listed again at a discount
+        assert!(DoubleAuctionModule::unsold_auctions(0).is_none());
+        let auction = DoubleAuctionModule::auctions(1).expect("return relisted auction");
+        assert_eq!(auction.starting_bid.bid, 900);
+        assert_eq!(auction.end_at, 102);
+        assert_eq!(DoubleAuctionModule::relist_attempts(1), 1);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionExpiredUnsold {
+                auction_id: 0,
+                seller_id: seller_id.clone(),
+                energy_quantity: 2,
+                starting_price: 1_000,
+                expired_at: 52,
+            },
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionRelisted {
+            previous_auction_id: 0,
+            auction_id: 1,
+            seller_id,
+        }));
+
+        // assert that it is archived once out of attempts
+        System::set_block_number(102);
+        finalize_block(102);
+        assert!(DoubleAuctionModule::auctions(2).is_none());
+        assert!(DoubleAuctionModule::unsold_auctions(1).is_some());
+        assert_eq!(DoubleAuctionModule::relist_attempts(1), 0);
+    });
+}
+
+#[test]
+fn open_auctions_and_bids_should_be_limited_per_account() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxOpenAuctionsPerSeller::set(1);
+        MaxOpenBidsPerBuyer::set(1);
+
+        let alice = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        for seller in [&alice, &bob] {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ));
+        }
+
+        // assert that a seller's second open auction is rejected
+        assert_noop!(
+            DoubleAuctionModule::new(
+                RuntimeOrigin::signed(alice.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ),
+            Error::<Test>::TooManyOpenAuctions
+        );
+        assert_eq!(DoubleAuctionModule::open_auction_count(&alice), 1);
+
+        // assert that a buyer raises its bid but cannot bid on a second auction
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            1_100,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0,
+            1_200,
+            None
+        ));
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(charlie.clone()), 1, 1_100, None),
+            Error::<Test>::TooManyOpenBids
+        );
+        assert_eq!(DoubleAuctionModule::open_bid_count(&charlie), 1);
+
+        // assert that cancelling frees the slots
+        assert_ok!(DoubleAuctionModule::cancel_bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            0
+        ));
+        assert_eq!(DoubleAuctionModule::open_bid_count(&charlie), 0);
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie.clone()),
+            1,
+            1_100,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::cancel(
+            RuntimeOrigin::signed(alice.clone()),
+            0
+        ));
+        assert_eq!(DoubleAuctionModule::open_auction_count(&alice), 0);
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+
+        // assert that execution frees the slots
+        System::set_block_number(52);
+        finalize_block(52);
+        assert_eq!(DoubleAuctionModule::open_auction_count(&bob), 0);
+        assert_eq!(DoubleAuctionModule::open_bid_count(&charlie), 0);
+    });
+}
+
+#[test]
+fn buy_orders_should_only_take_listings_their_zone_reaches() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // charlie wants 4 kWh of the first tier in zone 2 at up to 600 per kWh
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            4,
+            600,
+            Some(2)
+        ));
+
+        // assert that a listing of an unlinked zone stays open
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                zone: Some(1),
+                ..Default::default()
+            }
+        ));
+        assert_eq!(
+            DoubleAuctionModule::auctions(0).expect("open auction").zone,
+            1
+        );
+        assert_eq!(DoubleAuctionModule::buy_orders(1)[0].quantity, 4);
+
+        // assert that only the admin links distinct zones
+        assert_noop!(
+            DoubleAuctionModule::set_zone_link(
+                RuntimeOrigin::signed(bob_id.clone()),
+                1,
+                2,
+                Some(Permill::from_percent(10))
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            DoubleAuctionModule::set_zone_link(RuntimeOrigin::root(), 1, 1, None),
+            Error::<Test>::InvalidZoneLink
+        );
+        assert_ok!(DoubleAuctionModule::set_zone_link(
+            RuntimeOrigin::root(),
+            1,
+            2,
+            Some(Permill::from_percent(10))
+        ));
+        assert_eq!(
+            DoubleAuctionModule::zone_link(2, 1),
+            Some(Permill::from_percent(10))
+        );
+
+        // assert that a linked listing is bought net of the energy lost on the way
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(bob_id),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                zone: Some(1),
+                ..Default::default()
+            }
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BuyOrderFilled {
+            order_id: 0,
+            buyer_id: charlie_id,
+            auction_id: 1,
+            energy_quantity: 2,
+            price: 1_080,
+        }));
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+    });
+}
+
+#[test]
+fn delivery_window_should_hold_payment_until_delivery_is_confirmed() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        let window = DeliveryWindow {
+            delivery_start: 60,
+            delivery_end: 80,
+        };
+
+        // assert that a window closing before bidding does is rejected
+        assert_noop!(
+            DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id.clone()),
+                2,
+                1_000,
+                5,
+                ListingOptions {
+                    delivery_window: Some(DeliveryWindow {
+                        delivery_start: 10,
+                        delivery_end: 20,
+                    }),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::InvalidDeliveryWindow
+        );
+        for (auction_id, buyer_id) in [(0, &bob_id), (1, &charlie_id)] {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id.clone()),
+                2,
+                1_000,
+                5,
+                ListingOptions {
+                    delivery_window: Some(window),
+                    ..Default::default()
+                }
+            ));
+            assert_ok!(DoubleAuctionModule::bid(
+                RuntimeOrigin::signed(buyer_id.clone()),
+                auction_id,
+                1_100,
+                None
+            ));
+        }
+
+        // assert that settled payments stay held
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::DeliveryAwaited {
+            auction_id: 0,
+            seller_id: seller_id.clone(),
+            buyer_id: bob_id.clone(),
+            delivery_start: 60,
+            delivery_end: 80,
+        }));
+        assert_eq!(Balances::reserved_balance(&bob_id), 1_100);
+        assert_eq!(Balances::reserved_balance(&charlie_id), 1_100);
+        let seller_balance = Balances::free_balance(&seller_id);
+
+        // assert that a confirmed delivery pays the seller
+        assert_noop!(
+            DoubleAuctionModule::confirm_delivery(RuntimeOrigin::signed(charlie_id.clone()), 0),
+            Error::<Test>::DeliveryDoesNotExist
+        );
+        assert_ok!(DoubleAuctionModule::confirm_delivery(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0
+        ));
+        assert!(DoubleAuctionModule::delivery(0, &bob_id).is_none());
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_eq!(Balances::free_balance(&seller_id), seller_balance + 1_100);
+
+        // assert that a disputed delivery is frozen until arbitration refunds the buyer
+        assert_ok!(DoubleAuctionModule::dispute_delivery(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::DeliveryDisputed {
+            auction_id: 1,
+            seller_id: seller_id.clone(),
+            buyer_id: charlie_id.clone(),
+        }));
+        assert_noop!(
+            DoubleAuctionModule::confirm_delivery(RuntimeOrigin::signed(charlie_id.clone()), 1),
+            Error::<Test>::DeliveryUnderDispute
+        );
+        assert_noop!(
+            DoubleAuctionModule::resolve_delivery(
+                RuntimeOrigin::signed(seller_id.clone()),
+                1,
+                charlie_id.clone(),
+                false
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(DoubleAuctionModule::resolve_delivery(
+            RuntimeOrigin::root(),
+            1,
+            charlie_id.clone(),
+            true
+        ));
+        assert_eq!(Balances::reserved_balance(&charlie_id), 0);
+        assert_eq!(Balances::free_balance(&seller_id), seller_balance + 1_100);
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::DeliveryResolved {
+            auction_id: 1,
+            seller_id,
+            buyer_id: charlie_id,
+            refunded: true,
+        }));
+    });
+}
+
+#[test]
+fn reputation_should_track_trades_and_gate_trading() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            1_100,
+            None
+        ));
+
+        // assert that a completed trade counts for both parties
+        System::set_block_number(52);
+        finalize_block(52);
+        assert_eq!(
+            DoubleAuctionModule::reputation(&seller_id),
+            ReputationStats {
+                completed_trades: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(DoubleAuctionModule::reputation(&bob_id).score(), 1);
+
+        // assert that accounts below the minimum can neither list nor bid
+        MinReputation::set(1);
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_noop!(
+            DoubleAuctionModule::new(
+                RuntimeOrigin::signed(charlie_id.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ),
+            Error::<Test>::ReputationTooLow
+        );
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(charlie_id.clone()), 1, 1_100, None),
+            Error::<Test>::ReputationTooLow
+        );
+
+        // assert that buying by any other way needs the same score
+        assert_noop!(
+            DoubleAuctionModule::market_buy(RuntimeOrigin::signed(charlie_id.clone()), 1, 2, 5_000),
+            Error::<Test>::ReputationTooLow
+        );
+        assert_noop!(
+            DoubleAuctionModule::place_buy_order(
+                RuntimeOrigin::signed(charlie_id.clone()),
+                1,
+                2,
+                700,
+                None
+            ),
+            Error::<Test>::ReputationTooLow
+        );
+        assert_noop!(
+            DoubleAuctionModule::new_demand(RuntimeOrigin::signed(charlie_id), 2, 5_000, 5),
+            Error::<Test>::ReputationTooLow
+        );
+
+        // assert that cancellations lower the score
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            1,
+            1_100,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::cancel_bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            1
+        ));
+        assert_eq!(DoubleAuctionModule::reputation(&bob_id).cancellations, 1);
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(bob_id), 1, 1_100, None),
+            Error::<Test>::ReputationTooLow
+        );
+    });
+}
+
+#[test]
+fn upheld_delivery_dispute_should_slash_seller_bond() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+        NonDeliverySlash::set(Permill::from_percent(50));
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+
+        // assert that a seller bonds capacity
+        assert_ok!(DoubleAuctionModule::bond_capacity(
+            RuntimeOrigin::signed(seller_id.clone()),
+            1_000
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::CapacityBonded {
+            seller_id: seller_id.clone(),
+            amount: 1_000,
+            bonded: 1_000,
+        }));
+        assert_eq!(Balances::reserved_balance(&seller_id), 1_000);
+
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                delivery_window: Some(DeliveryWindow {
+                    delivery_start: 60,
+                    delivery_end: 80,
+                }),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            1_100,
+            None
+        ));
+        System::set_block_number(52);
+        finalize_block(52);
+        assert_ok!(DoubleAuctionModule::dispute_delivery(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0
+        ));
+        let buyer_balance = Balances::free_balance(&bob_id);
+
+        // assert that upholding the dispute pays half the price from the bond to the buyer
+        assert_ok!(DoubleAuctionModule::resolve_delivery(
+            RuntimeOrigin::root(),
+            0,
+            bob_id.clone(),
+            true
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::SellerSlashed {
+            auction_id: 0,
+            seller_id: seller_id.clone(),
+            buyer_id: bob_id.clone(),
+            amount: 550,
+        }));
+        assert_eq!(DoubleAuctionModule::seller_bond(&seller_id), 450);
+        assert_eq!(Balances::reserved_balance(&seller_id), 450);
+        assert_eq!(Balances::free_balance(&bob_id), buyer_balance + 1_100 + 550);
+        assert_eq!(DoubleAuctionModule::reputation(&seller_id).disputes, 1);
+    });
+}
+
+#[test]
+fn paused_market_should_reject_orders_but_keep_executing() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            1_100,
+            None
+        ));
+
+        // assert that only the admin pauses the market
+        assert_noop!(
+            DoubleAuctionModule::pause_market(RuntimeOrigin::signed(seller_id.clone())),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(DoubleAuctionModule::pause_market(RuntimeOrigin::root()));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::MarketPaused));
+        assert_eq!(
+            DoubleAuctionModule::market_status()[0].state,
+            TierState::Paused
+        );
+
+        // assert that listings and bids are rejected
+        assert_noop!(
+            DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id.clone()),
+                2,
+                1_000,
+                5,
+                Default::default()
+            ),
+            Error::<Test>::MarketPaused
+        );
+        assert_noop!(
+            DoubleAuctionModule::bid(RuntimeOrigin::signed(bob_id.clone()), 0, 1_200, None),
+            Error::<Test>::MarketPaused
+        );
+
+        // assert that every other way of taking or placing an order is rejected
+        assert_noop!(
+            DoubleAuctionModule::market_buy(RuntimeOrigin::signed(bob_id.clone()), 1, 2, 5_000),
+            Error::<Test>::MarketPaused
+        );
+        assert_noop!(
+            DoubleAuctionModule::place_buy_order(
+                RuntimeOrigin::signed(bob_id.clone()),
+                1,
+                2,
+                700,
+                None
+            ),
+            Error::<Test>::MarketPaused
+        );
+        assert_noop!(
+            DoubleAuctionModule::new_demand(RuntimeOrigin::signed(bob_id.clone()), 2, 5_000, 5),
+            Error::<Test>::MarketPaused
+        );
+        assert_noop!(
+            DoubleAuctionModule::create_recurring_auction(
+                RuntimeOrigin::signed(seller_id.clone()),
+                2,
+                1_000,
+                5,
+                100
+            ),
+            Error::<Test>::MarketPaused
+        );
+
+        // assert that listed auctions still execute
+        System::set_block_number(52);
+        finalize_block(52);
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert_eq!(DoubleAuctionModule::reputation(&bob_id).completed_trades, 1);
+
+        // assert that resuming accepts listings again
+        assert_ok!(DoubleAuctionModule::resume_market(RuntimeOrigin::root()));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::MarketResumed));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+    });
+}
+
+#[test]
+fn force_remove_should_clear_auction_stuck_awaiting_settlement() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                settlement_lag: Some(10),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            1_100,
+            None
+        ));
+        System::set_block_number(52);
+        finalize_block(52);
+        let status = DoubleAuctionModule::awaiting_settlement(0)
+            .expect("auction awaits settlement")
+            .auction_status;
+
+        // assert that only the force origin removes auctions
+        assert_noop!(
+            DoubleAuctionModule::force_remove(RuntimeOrigin::signed(seller_id.clone()), 0),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            DoubleAuctionModule::force_remove(RuntimeOrigin::root(), 1),
+            Error::<Test>::AuctionDoesNotExist
+        );
+
+        // assert that the auction and its settlement are gone and the bid refunded
+        assert_ok!(DoubleAuctionModule::force_remove(RuntimeOrigin::root(), 0));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(Event::ForceRemoved {
+            auction_id: 0,
+            seller_id,
+            status,
+        }));
+        assert!(DoubleAuctionModule::awaiting_settlement(0).is_none());
+        assert!(DoubleAuctionModule::settlement_queue(62, 0).is_none());
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_eq!(DoubleAuctionModule::open_bid_count(&bob_id), 0);
+
+        // assert that nothing settles when the queue comes due
+        System::set_block_number(62);
+        finalize_block(62);
+        assert_eq!(Balances::free_balance(&bob_id), 1_000_000_000);
+    });
+}
+
+#[test]
+fn migration_to_v4_should_place_auctions_and_buy_orders_in_default_zone() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            4,
+            600,
+            None
+        ));
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        let order = DoubleAuctionModule::buy_orders(1)[0].clone();
+
+        // auction and buy order of version 3, without zone and delivery window
+        let encoded = auction.encode();
+        frame_support::storage::unhashed::put_raw(
+            &crate::Auctions::<Test>::hashed_key_for(0),
+            &encoded[..encoded.len() - 5],
+        );
+        frame_support::storage::unhashed::put(
+            &crate::BuyOrders::<Test>::hashed_key_for(1),
+            &vec![(
+                order.order_id,
+                order.buyer.clone(),
+                order.quantity,
+                order.max_unit_price,
+                order.placed_at,
+            )],
+        );
+        StorageVersion::new(3).put::<DoubleAuctionModule>();
+
+        crate::migrations::MigrateToV4::<Test>::on_runtime_upgrade();
+
+        // assert that both are restored in the default zone and the storage version bumped
+        assert_eq!(DoubleAuctionModule::auctions(0), Some(auction));
+        assert_eq!(DoubleAuctionModule::buy_orders(1).into_inner(), vec![order]);
+        assert_eq!(StorageVersion::get::<DoubleAuctionModule>(), 4);
+    });
+}
+
+#[test]
+fn migration_to_v6_should_keep_market_parameters() {
+    new_test_ext().execute_with(|| {
+        // market parameters of version 5, without the extension window
+        frame_support::storage::unhashed::put(
+            &crate::Parameters::<Test>::hashed_key(),
+            &(Some(500u128), None::<Perbill>, Some(3u32)),
+        );
+        StorageVersion::new(5).put::<DoubleAuctionModule>();
+
+        crate::migrations::MigrateToV6::<Test>::on_runtime_upgrade();
+
+        // assert that the overrides are kept and the storage version bumped
+        assert_eq!(
+            DoubleAuctionModule::parameters(),
+            crate::MarketParameters {
+                min_bid_increment: Some(500),
+                commission_rate: None,
+                max_executions_per_block: Some(3),
+                extension_threshold: None,
+                extension_period: None,
+            }
+        );
+        assert_eq!(DoubleAuctionModule::min_bid_increment(), 500);
+        assert_eq!(StorageVersion::get::<DoubleAuctionModule>(), 6);
+    });
+}
+
+#[test]
+fn migration_to_v7_should_count_matched_auctions_by_tier() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        AcceptanceWindow::set(5);
+
+        let bob = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+                b"000000000000000000000ALICE000000".clone(),
+            ))),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(bob, 0, 2_000, None));
+        System::set_block_number(52);
+        finalize_block(52);
+
+        // matched auction of version 6, not counted
+        crate::TierQueueDepths::<Test>::remove(1);
+        StorageVersion::new(6).put::<DoubleAuctionModule>();
+
+        crate::migrations::MigrateToV7::<Test>::on_runtime_upgrade();
+
+        // assert that the match is counted in its tier and the storage version bumped
+        assert_eq!(DoubleAuctionModule::tier_queue_depths(1), (1, 0));
+        assert_eq!(StorageVersion::get::<DoubleAuctionModule>(), 7);
+    });
+}
+
+#[test]
+fn try_state_should_hold_through_bidding_and_catch_corrupted_indexes() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::do_try_state());
+
+        // assert that invariants hold as bids come and go
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            3_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::do_try_state());
+        assert_ok!(DoubleAuctionModule::cancel_bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0
+        ));
+        assert_ok!(DoubleAuctionModule::do_try_state());
+
+        // assert that a stale execution queue entry is caught
+        crate::AuctionsExecutionQueue::<Test>::insert(60, 7, ());
+        assert_eq!(
+            DoubleAuctionModule::do_try_state(),
+            Err("execution queue holds an auction that does not exist")
+        );
+        crate::AuctionsExecutionQueue::<Test>::remove(60, 7);
+
+        // assert that a highest bid out of line with the bids is caught
+        let auction = DoubleAuctionModule::auctions(0).expect("return indexed auction");
+        let mut corrupted = auction.clone();
+        corrupted.highest_bid.bid = 2_500;
+        crate::Auctions::<Test>::insert(0, corrupted);
+        assert_eq!(
+            DoubleAuctionModule::do_try_state(),
+            Err("highest bid differs from its bidder's bid")
+        );
+        crate::Auctions::<Test>::insert(0, auction);
+
+        // assert that a stale participant order is caught
+        crate::BuyOrdersOf::<Test>::insert(&bob_id, BoundedVec::truncate_from(vec![0, 7]));
+        assert_eq!(
+            DoubleAuctionModule::do_try_state(),
+            Err("buy orders track an auction that does not exist")
+        );
+        crate::BuyOrdersOf::<Test>::insert(&bob_id, BoundedVec::truncate_from(vec![0]));
+
+        // assert that invariants hold once the auction executes
+        System::set_block_number(52);
+        finalize_block(52);
+        assert_ok!(DoubleAuctionModule::do_try_state());
+    });
+}
+
+#[test]
+fn market_buy_should_only_take_fills_the_buyer_can_pay() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let alice = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let charlie = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        )));
+        let dave_id = AccountId::from(AccountId32::from(
+            b"0000000000000000000000DAVE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            alice,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            charlie.clone(),
+            3,
+            1_200,
+            5,
+            Default::default()
+        ));
+        assert_eq!(
+            DoubleAuctionModule::tier_auctions(1).into_inner(),
+            vec![0, 1]
+        );
+
+        // assert that a buyer without funds takes nothing
+        assert_noop!(
+            DoubleAuctionModule::market_buy(RuntimeOrigin::signed(dave_id.clone()), 1, 5, 10_000),
+            Error::<Test>::NoMarketLiquidity
+        );
+
+        // assert that only the fills the buyer can pay are taken
+        Balances::make_free_balance_be(&dave_id, 1_100);
+        assert_ok!(DoubleAuctionModule::market_buy(
+            RuntimeOrigin::signed(dave_id.clone()),
+            1,
+            5,
+            10_000
+        ));
+        System::assert_last_event(RuntimeEvent::DoubleAuctionModule(
+            Event::MarketBuyExecuted {
+                buyer_id: dave_id,
+                tier: 1,
+                energy_quantity: 2,
+                total_price: 1_000,
+                fills: 1,
+            },
+        ));
+        assert_eq!(DoubleAuctionModule::tier_auctions(1).into_inner(), vec![1]);
+
+        // assert that a full tier takes no further listing
+        MaxAuctionsPerTier::set(1);
+        assert_noop!(
+            DoubleAuctionModule::new(charlie, 3, 1_200, 5, Default::default()),
+            Error::<Test>::TierFull
+        );
+    });
+}
+
+#[test]
+fn order_pair_sell_leg_should_stay_after_extended_buy_leg() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        ExtensionThreshold::set(10);
+        ExtensionPeriod::set(20);
+
+        let alice = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let operator_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let operator = RuntimeOrigin::signed(operator_id.clone());
+        let charlie = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        )));
+
+        // operator buys from alice until block 52 and sells until block 62
+        assert_ok!(DoubleAuctionModule::new(
+            alice,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            operator.clone(),
+            2,
+            1_000,
+            6,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::pair_orders(
+            operator.clone(),
+            0,
+            2_000,
+            1
+        ));
+
+        // assert that the owner can not shorten the sell leg to end before the buy leg
+        assert_noop!(
+            DoubleAuctionModule::update_auction(operator, 1, 2, 1_000, 4),
+            Error::<Test>::PairLegsOutOfOrder
+        );
+
+        // assert that a late bid moving the buy leg past the sell leg pushes the sell leg too
+        System::set_block_number(45);
+        assert_ok!(DoubleAuctionModule::bid(charlie, 0, 3_000, None));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 72);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::AuctionExecutionDeferred {
+                auction_id: 1,
+                seller_id: operator_id.clone(),
+                reason: DeferralReason::PairedBuyLeg,
+                execute_at: 73,
+            },
+        ));
+        assert_eq!(DoubleAuctionModule::auctions(1).unwrap().end_at, 73);
+        assert!(DoubleAuctionModule::auction_execution_queue(62, 1).is_none());
+        assert!(DoubleAuctionModule::auction_execution_queue(73, 1).is_some());
+
+        // assert that the outbid owner's pair is rejected once the buy leg ends
+        System::set_block_number(72);
+        finalize_block(72);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::OrderPairRejected {
+                owner: operator_id,
+                buy_auction_id: 0,
+                sell_auction_id: 1,
+            },
+        ));
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+
+        // assert that the cancelled sell leg's queue entry is skipped
+        System::set_block_number(73);
+        finalize_block(73);
+        assert!(DoubleAuctionModule::auction_execution_queue(73, 1).is_none());
+    });
+}
+
+#[test]
+fn late_bids_should_only_extend_when_leading_and_within_max_duration() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        ExtensionThreshold::set(10);
+        ExtensionPeriod::set(14_400);
+
+        let seller = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        )));
+        let bob = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        )));
+        let charlie = RuntimeOrigin::signed(AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        )));
+        assert_ok!(DoubleAuctionModule::new(
+            seller,
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        assert_ok!(DoubleAuctionModule::bid(bob.clone(), 0, 1_100, None));
+
+        // assert that a losing late bid is rejected and keeps the deadline
+        System::set_block_number(45);
+        assert_noop!(
+            DoubleAuctionModule::bid(charlie.clone(), 0, 1_000, None),
+            Error::<Test>::BidNotLeading
+        );
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 52);
+
+        // assert that a leading late bid extends up to `MaxAuctionDuration` from the start
+        assert_ok!(DoubleAuctionModule::bid(charlie, 0, 1_200, None));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 14_402);
+
+        // assert that no later bid extends it further
+        System::set_block_number(14_400);
+        assert_ok!(DoubleAuctionModule::bid(bob, 0, 1_300, None));
+        assert_eq!(DoubleAuctionModule::auctions(0).unwrap().end_at, 14_402);
+    });
+}
+
+#[test]
+fn winner_whose_payment_fails_should_fall_to_next_bidder() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        let charlie_free = Balances::free_balance(&charlie_id);
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            3_000,
+            None
+        ));
+
+        // bob's held bid is taken from under the escrow and his free funds run out
+        let _ = Balances::slash_reserved(&bob_id, 3_000);
+        Balances::make_free_balance_be(&bob_id, 1);
+
+        // assert that bob's failed payment passes the auction to charlie
+        System::set_block_number(52);
+        finalize_block(52);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::WinnerSkipped {
+            auction_id: 0,
+            buyer_id: bob_id.clone(),
+        }));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id,
+            buyer_id: charlie_id.clone(),
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 2_000,
+            clearing_price: 2_000,
+            executed_at: 52,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert_eq!(Balances::reserved_balance(&charlie_id), 0);
+        assert_eq!(Balances::free_balance(&charlie_id), charlie_free - 2_000);
+        assert_eq!(Balances::free_balance(&bob_id), 1);
+        assert_eq!(DoubleAuctionModule::skipped_wins(&bob_id), 1);
+    });
+}
+
+#[test]
+fn cancel_bid_should_hold_funds_of_promoted_bidder() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        OnChainSettlement::set(true);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+        let dave_id = AccountId::from(AccountId32::from(
+            b"0000000000000000000000DAVE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id),
+            2,
+            1_000,
+            5,
+            Default::default()
+        ));
+        Balances::make_free_balance_be(&dave_id, 10_000);
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(dave_id.clone()),
+            0,
+            3_000,
+            None
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            4_000,
+            None
+        ));
+        assert_eq!(Balances::reserved_balance(&dave_id), 0);
+
+        // dave can no longer cover his bid once it would lead again
+        Balances::make_free_balance_be(&dave_id, 1);
+
+        // assert that dave is dropped and charlie's bid is held as the new lead
+        assert_ok!(DoubleAuctionModule::cancel_bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::BidRefunded {
+            auction_id: 0,
+            bidder: dave_id.clone(),
+            amount: 3_000,
+        }));
+        let auction = DoubleAuctionModule::auctions(0).unwrap();
+        assert_eq!(auction.highest_bid.bidder, charlie_id);
+        assert_eq!(auction.highest_bid.bid, 2_000);
+        assert_eq!(auction.bid_count, 1);
+        assert_eq!(Balances::reserved_balance(&bob_id), 0);
+        assert_eq!(Balances::reserved_balance(&charlie_id), 2_000);
+        assert!(DoubleAuctionModule::bid_of(0, &dave_id).is_none());
+    });
+}
+
+#[test]
+fn uniform_price_clearing_should_sell_at_most_max_matches() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        MaxMatchesPerClearing::set(1);
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // charlie wants 4 kWh of the first tier at up to 700 per kWh
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            4,
+            700,
+            None
+        ));
+
+        // alice asks 500 and bob 600 per kWh
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(alice_id),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::UniformPrice),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(bob_id),
+            2,
+            1_200,
+            5,
+            ListingOptions {
+                match_rule: Some(MatchingAlgorithm::UniformPrice),
+                ..Default::default()
+            }
+        ));
+
+        // assert that the first clearing only sells the cheapest ask
+        System::set_block_number(10);
+        DoubleAuctionModule::on_initialize(10);
+        finalize_block(10);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::TierCleared {
+            tier: 1,
+            zone: 0,
+            clearing_price: 600,
+            matched_volume: 2,
+            results: BoundedVec::truncate_from(Vec::from([(0, charlie_id.clone(), 2, 1_200)])),
+        }));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+        assert!(DoubleAuctionModule::auctions(1).is_some());
+
+        // assert that the dearer ask sells at the next clearing
+        System::set_block_number(20);
+        DoubleAuctionModule::on_initialize(20);
+        finalize_block(20);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::TierCleared {
+            tier: 1,
+            zone: 0,
+            clearing_price: 650,
+            matched_volume: 2,
+            results: BoundedVec::truncate_from(Vec::from([(1, charlie_id, 2, 1_300)])),
+        }));
+        assert!(DoubleAuctionModule::auctions(1).is_none());
+    });
+}
+
+#[test]
+fn uniform_price_clearing_should_wait_for_demand_quorum() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+        ClearingQuorum::set(Perbill::from_percent(50));
+
+        let alice_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        let charlie_id = AccountId::from(AccountId32::from(
+            b"00000000000000000000CHARLIE00000".clone(),
+        ));
+
+        // alice asks 500 and bob 600 per kWh, 4 kWh offered in all
+        for (seller_id, price) in [(alice_id, 1_000), (bob_id, 1_200)] {
+            assert_ok!(DoubleAuctionModule::new(
+                RuntimeOrigin::signed(seller_id),
+                2,
+                price,
+                5,
+                ListingOptions {
+                    match_rule: Some(MatchingAlgorithm::UniformPrice),
+                    ..Default::default()
+                }
+            ));
+        }
+
+        // charlie wants 1 kWh at up to 700 per kWh
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            1,
+            700,
+            None
+        ));
+
+        // assert that a quarter of the supply does not clear
+        System::set_block_number(10);
+        DoubleAuctionModule::on_initialize(10);
+        finalize_block(10);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::ClearingPostponed { tier: 1, zone: 0 },
+        ));
+        assert!(DoubleAuctionModule::auctions(0).is_some());
+        assert!(DoubleAuctionModule::auctions(1).is_some());
+
+        // charlie wants 2 kWh more
+        assert_ok!(DoubleAuctionModule::place_buy_order(
+            RuntimeOrigin::signed(charlie_id.clone()),
+            1,
+            2,
+            700,
+            None
+        ));
+
+        // assert that the next round clears once demand reaches the quorum
+        System::set_block_number(20);
+        DoubleAuctionModule::on_initialize(20);
+        finalize_block(20);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::TierCleared {
+            tier: 1,
+            zone: 0,
+            clearing_price: 600,
+            matched_volume: 2,
+            results: BoundedVec::truncate_from(Vec::from([(0, charlie_id, 2, 1_200)])),
+        }));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+    });
+}
+
+#[test]
+fn top_up_bid_should_be_placed_like_a_bid() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        let bob_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000BOB00000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::new(
+            RuntimeOrigin::signed(seller_id.clone()),
+            2,
+            1_000,
+            5,
+            ListingOptions {
+                buyout_price: Some(3_000),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(DoubleAuctionModule::bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            2_000,
+            None
+        ));
+
+        // assert that a paused market takes no top-ups
+        assert_ok!(DoubleAuctionModule::pause_market(RuntimeOrigin::root()));
+        assert_noop!(
+            DoubleAuctionModule::top_up_bid(RuntimeOrigin::signed(bob_id.clone()), 0, 1_000),
+            Error::<Test>::MarketPaused
+        );
+        assert_ok!(DoubleAuctionModule::resume_market(RuntimeOrigin::root()));
+
+        // assert that a top-up reaching the buy-it-now price buys the auction at that price
+        assert_ok!(DoubleAuctionModule::top_up_bid(
+            RuntimeOrigin::signed(bob_id.clone()),
+            0,
+            1_500
+        ));
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(Event::AuctionExecuted {
+            auction_id: 0,
+            seller_id,
+            buyer_id: bob_id,
+            energy_quantity: 2,
+            starting_price: 1_000,
+            highest_bid: 3_000,
+            clearing_price: 3_000,
+            executed_at: 2,
+            auction_category: Tier { level: 1 },
+            bid_count: 1,
+            end_at: 52,
+            price_basis: PriceBasis::HighestBid,
+        }));
+        assert!(DoubleAuctionModule::auctions(0).is_none());
+    });
+}
+
+#[test]
+fn recurring_listing_should_be_skipped_while_paused() {
+    new_test_ext().execute_with(|| {
+        // go to block after genesis
+        // genesis block does not emit event
+        System::set_block_number(2);
+
+        let seller_id = AccountId::from(AccountId32::from(
+            b"000000000000000000000ALICE000000".clone(),
+        ));
+        assert_ok!(DoubleAuctionModule::create_recurring_auction(
+            RuntimeOrigin::signed(seller_id),
+            2,
+            1_000,
+            5,
+            100
+        ));
+        assert_ok!(DoubleAuctionModule::pause_market(RuntimeOrigin::root()));
+
+        // assert that the listing due while paused is skipped for the next interval
+        System::set_block_number(102);
+        DoubleAuctionModule::on_initialize(102);
+        System::assert_has_event(RuntimeEvent::DoubleAuctionModule(
+            Event::RecurringAuctionSkipped {
+                recurring_id: 0,
+                error: Error::<Test>::MarketPaused.into(),
+            },
+        ));
+        assert!(DoubleAuctionModule::auctions(1).is_none());
     });
 }
@@ -0,0 +1,89 @@
+//! Extension points of the double auction pallet.
+use scale_info::prelude::vec::Vec;
+
+/// Facts about an auction due for execution, used to order execution.
+#[derive(Clone, PartialEq, Eq, frame_support::RuntimeDebug)]
+pub struct ExecutionCandidate<AuctionId, Price, Quantity> {
+    pub auction_id: AuctionId,
+    pub quantity: Quantity,
+    pub highest_bid: Price,
+    pub bid_count: u32,
+}
+
+/// Order in which auctions due in the same block are executed.
+///
+/// When more auctions are due than `MaxExecutionsPerBlock`, the ones sorted last are deferred.
+pub trait ExecutionPriority<AuctionId, Price, Quantity> {
+    fn sort(candidates: &mut Vec<ExecutionCandidate<AuctionId, Price, Quantity>>);
+}
+
+/// Reaction of downstream pallets (settlement, metering, certificates) to an executed auction.
+pub trait OnAuctionExecuted<AuctionId, AccountId, Quantity, Price> {
+    fn on_auction_executed(
+        auction_id: AuctionId,
+        seller: &AccountId,
+        buyer: &AccountId,
+        quantity: Quantity,
+        price: Price,
+    );
+}
+
+impl<AuctionId, AccountId, Quantity, Price> OnAuctionExecuted<AuctionId, AccountId, Quantity, Price>
+    for ()
+{
+    fn on_auction_executed(
+        _auction_id: AuctionId,
+        _seller: &AccountId,
+        _buyer: &AccountId,
+        _quantity: Quantity,
+        _price: Price,
+    ) {
+    }
+}
+
+/// Oldest auction first.
+impl<AuctionId: Ord, Price, Quantity> ExecutionPriority<AuctionId, Price, Quantity> for () {
+    fn sort(candidates: &mut Vec<ExecutionCandidate<AuctionId, Price, Quantity>>) {
+        candidates.sort_by(|a, b| a.auction_id.cmp(&b.auction_id));
+    }
+}
+
+/// Auction with the fewest bids first, ties broken by age.
+pub struct SmallestBookFirst;
+impl<AuctionId: Ord, Price, Quantity> ExecutionPriority<AuctionId, Price, Quantity>
+    for SmallestBookFirst
+{
+    fn sort(candidates: &mut Vec<ExecutionCandidate<AuctionId, Price, Quantity>>) {
+        candidates.sort_by(|a, b| {
+            a.bid_count
+                .cmp(&b.bid_count)
+                .then_with(|| a.auction_id.cmp(&b.auction_id))
+        });
+    }
+}
+
+/// Auction with the highest bid first, ties broken by age.
+///
+/// The highest bid is the total paid for the whole quantity, so it is the traded value.
+pub struct HighestValueFirst;
+impl<AuctionId: Ord, Price: Ord, Quantity> ExecutionPriority<AuctionId, Price, Quantity>
+    for HighestValueFirst
+{
+    fn sort(candidates: &mut Vec<ExecutionCandidate<AuctionId, Price, Quantity>>) {
+        candidates.sort_by(|a, b| {
+            b.highest_bid
+                .cmp(&a.highest_bid)
+                .then_with(|| a.auction_id.cmp(&b.auction_id))
+        });
+    }
+}
+
+/// Keys the benchmarks sign relayed bids with, in the runtime's signature scheme.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Signature, AccountId> {
+    /// Account of the key signing relayed bids.
+    fn signer() -> AccountId;
+
+    /// Sign `message` with the key of `signer`.
+    fn sign(message: &[u8]) -> Signature;
+}
@@ -0,0 +1,927 @@
+//! Weights for pallet_double_auction
+//!
+//! Estimated for the benchmarks in `benchmarking.rs`, regenerate on reference hardware with:
+//! `node benchmark pallet --chain=dev --steps=50 --repeat=20 --pallet=pallet_double_auction
+//! --extrinsic=* --execution=wasm --wasm-execution=compiled --output=./src/weights.rs`
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_double_auction.
+pub trait WeightInfo {
+	fn new() -> Weight;
+	fn bid(b: u32, ) -> Weight;
+	fn cancel(b: u32, ) -> Weight;
+	fn execute_auctions(q: u32, ) -> Weight;
+	fn market_buy(a: u32, ) -> Weight;
+	fn market_sell(o: u32, ) -> Weight;
+	fn extend_auction() -> Weight;
+	fn relist() -> Weight;
+	fn set_tier_default_period() -> Weight;
+	fn set_tier_min_bidders() -> Weight;
+	fn register_referrer() -> Weight;
+	fn claim_referral_rewards() -> Weight;
+	fn sponsor() -> Weight;
+	fn end_sponsorship() -> Weight;
+	fn bid_signed(b: u32, ) -> Weight;
+	fn set_tier_matching_algorithm() -> Weight;
+	fn top_up_bid(b: u32, ) -> Weight;
+	fn transfer_auction() -> Weight;
+	fn set_tier_halted() -> Weight;
+	fn force_execute(b: u32, ) -> Weight;
+	fn accept_match(b: u32, ) -> Weight;
+	fn create_organization() -> Weight;
+	fn set_organization_member() -> Weight;
+	fn set_organization_limits() -> Weight;
+	fn subscribe_tier(s: u32, ) -> Weight;
+	fn unsubscribe_tier(s: u32, ) -> Weight;
+	fn set_parameter() -> Weight;
+	fn pair_orders(b: u32, ) -> Weight;
+	fn place_buy_order(o: u32, ) -> Weight;
+	fn cancel_buy_order(o: u32, ) -> Weight;
+	fn bid_quantity(b: u32, ) -> Weight;
+	fn cancel_bid(b: u32, ) -> Weight;
+	fn commit_bid(b: u32, ) -> Weight;
+	fn reveal_bid(b: u32, ) -> Weight;
+	fn new_dutch() -> Weight;
+	fn buyout(b: u32, ) -> Weight;
+	fn set_tier_boundaries(t: u32, ) -> Weight;
+	fn cancel_recurring() -> Weight;
+	fn register_participant() -> Weight;
+	fn set_participant_status() -> Weight;
+	fn register_sponsor() -> Weight;
+	fn new_demand() -> Weight;
+	fn offer() -> Weight;
+	fn cancel_demand() -> Weight;
+	fn update_auction() -> Weight;
+	fn set_zone_link() -> Weight;
+	fn confirm_delivery() -> Weight;
+	fn dispute_delivery() -> Weight;
+	fn resolve_delivery() -> Weight;
+	fn bond_capacity() -> Weight;
+	fn pause_market() -> Weight;
+	fn resume_market() -> Weight;
+}
+
+/// Weights for pallet_double_auction using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: DoubleAuction AuctionIndex (r:1 w:1)
+	// Storage: DoubleAuction ListingCooldowns (r:1 w:0)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BuyOrders (r:1 w:0)
+	// Storage: DoubleAuction Auctions (r:0 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:0 w:1)
+	fn new() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BidHistory (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	/// The range of component `b` is `[0, 9]`.
+	fn bid(b: u32, ) -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BidHistory (r:1 w:1)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:0 w:1)
+	/// The range of component `b` is `[0, 9]`.
+	fn cancel(b: u32, ) -> Weight {
+		Weight::from_parts(41_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(b.into())))
+	}
+	// Storage: DoubleAuction ExecutionCursor (r:1 w:1)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:1 w:1)
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction EraVolumes (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	/// The range of component `q` is `[0, 10]`.
+	fn execute_auctions(q: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			// Standard Error: 15_000
+			.saturating_add(Weight::from_parts(65_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(q.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((14_u64).saturating_mul(q.into())))
+	}
+	// Storage: DoubleAuction TierAuctions (r:1 w:1)
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	/// The range of component `a` is `[1, 100]`.
+	fn market_buy(a: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(70_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((14_u64).saturating_mul(a.into())))
+	}
+	// Storage: DoubleAuction BuyOrders (r:1 w:1)
+	// Storage: DoubleAuction AuctionIndex (r:1 w:1)
+	// Storage: DoubleAuction TierAuctions (r:1 w:1)
+	// Storage: DoubleAuction Auctions (r:1 w:2)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:2)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	/// The range of component `o` is `[1, 100]`.
+	fn market_sell(o: u32, ) -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(112_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((19_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((20_u64).saturating_mul(o.into())))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:2 w:2)
+	// Storage: DoubleAuction PairedSellLegs (r:1 w:0)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	fn extend_auction() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	// Storage: DoubleAuction UnsoldAuctions (r:1 w:1)
+	// Storage: DoubleAuction StorageDeposits (r:1 w:2)
+	// Storage: DoubleAuction AuctionDeposits (r:1 w:2)
+	// Storage: DoubleAuction AuctionIndex (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction TierAuctions (r:1 w:1)
+	// Storage: DoubleAuction BuyOrders (r:1 w:0)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: DoubleAuction Auctions (r:0 w:1)
+	fn relist() -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction TierDefaultPeriods (r:0 w:1)
+	fn set_tier_default_period() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction TierMinBidders (r:0 w:1)
+	fn set_tier_min_bidders() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Referrers (r:2 w:1)
+	fn register_referrer() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction ReferralRewards (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_referral_rewards() -> Weight {
+		Weight::from_parts(42_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: DoubleAuction Participants (r:1 w:0)
+	// Storage: DoubleAuction Sponsorships (r:1 w:1)
+	fn sponsor() -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Sponsorships (r:1 w:1)
+	fn end_sponsorship() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: DoubleAuction BidNonces (r:1 w:1)
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BidHistory (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	/// The range of component `b` is `[0, 9]`.
+	fn bid_signed(b: u32, ) -> Weight {
+		Weight::from_parts(112_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction PendingMatchingAlgorithms (r:0 w:1)
+	fn set_tier_matching_algorithm() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BidHistory (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	/// The range of component `b` is `[1, 10]`.
+	fn top_up_bid(b: u32, ) -> Weight {
+		Weight::from_parts(54_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction StorageDeposits (r:1 w:2)
+	// Storage: DoubleAuction AuctionDeposits (r:1 w:2)
+	// Storage: DoubleAuction SellOrdersOf (r:2 w:2)
+	// Storage: DoubleAuction OpenAuctionCounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	fn transfer_auction() -> Weight {
+		Weight::from_parts(46_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction HaltedTiers (r:0 w:1)
+	fn set_tier_halted() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:0 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction TierAuctions (r:1 w:1)
+	// Storage: DoubleAuction EraVolumes (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	/// The range of component `b` is `[0, 10]`.
+	fn force_execute(b: u32, ) -> Weight {
+		Weight::from_parts(36_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	// Storage: DoubleAuction AwaitingAcceptance (r:1 w:1)
+	// Storage: DoubleAuction AcceptanceDeadlines (r:0 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction EraVolumes (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	/// The range of component `b` is `[1, 10]`.
+	fn accept_match(b: u32, ) -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	// Storage: DoubleAuction OrganizationOf (r:1 w:1)
+	// Storage: DoubleAuction OrganizationIndex (r:1 w:1)
+	// Storage: DoubleAuction Organizations (r:0 w:1)
+	fn create_organization() -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: DoubleAuction Organizations (r:1 w:0)
+	// Storage: DoubleAuction OrganizationOf (r:1 w:1)
+	fn set_organization_member() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Organizations (r:1 w:1)
+	fn set_organization_limits() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction TierSubscribers (r:1 w:1)
+	/// The range of component `s` is `[0, 1]`.
+	fn subscribe_tier(s: u32, ) -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(240_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction TierSubscribers (r:1 w:1)
+	/// The range of component `s` is `[1, 2]`.
+	fn unsubscribe_tier(s: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(260_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Parameters (r:1 w:1)
+	fn set_parameter() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:2 w:1)
+	// Storage: DoubleAuction OrderPairs (r:2 w:1)
+	// Storage: DoubleAuction PairedSellLegs (r:2 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BidHistory (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	/// The range of component `b` is `[0, 9]`.
+	fn pair_orders(b: u32, ) -> Weight {
+		Weight::from_parts(64_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction HaltedTiers (r:1 w:0)
+	// Storage: DoubleAuction MarketPaused (r:1 w:0)
+	// Storage: DoubleAuction BuyOrderIndex (r:1 w:1)
+	// Storage: DoubleAuction BuyOrders (r:1 w:1)
+	// Storage: DoubleAuction Reputation (r:1 w:0)
+	/// The range of component `o` is `[0, 2]`.
+	fn place_buy_order(o: u32, ) -> Weight {
+		Weight::from_parts(26_000_000, 0)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(420_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: DoubleAuction BuyOrders (r:1 w:1)
+	/// The range of component `o` is `[1, 3]`.
+	fn cancel_buy_order(o: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(480_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:2 w:1)
+	// Storage: DoubleAuction BidHistograms (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BidHistory (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	/// The range of component `b` is `[0, 9]`.
+	fn bid_quantity(b: u32, ) -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(2_600_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction ProxyBids (r:0 w:1)
+	// Storage: DoubleAuction OpenBidCounts (r:1 w:1)
+	// Storage: DoubleAuction Escrows (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction OrderPairs (r:1 w:0)
+	// Storage: DoubleAuction Reputation (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	/// The range of component `b` is `[1, 10]`.
+	fn cancel_bid(b: u32, ) -> Weight {
+		Weight::from_parts(44_000_000, 0)
+			// Standard Error: 5_000
+			.saturating_add(Weight::from_parts(3_100_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:0)
+	// Storage: DoubleAuction Participants (r:1 w:0)
+	// Storage: DoubleAuction MarketPaused (r:1 w:0)
+	// Storage: DoubleAuction SealedBids (r:1 w:1)
+	// Storage: DoubleAuction OpenBidCounts (r:1 w:0)
+	/// The range of component `b` is `[0, 9]`.
+	fn commit_bid(b: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(1_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction SealedBids (r:1 w:1)
+	// Storage: DoubleAuction BidHistograms (r:1 w:1)
+	// Storage: DoubleAuction Escrows (r:1 w:1)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction OpenBidCounts (r:1 w:1)
+	// Storage: DoubleAuction BuyOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	/// The range of component `b` is `[0, 9]`.
+	fn reveal_bid(b: u32, ) -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(1_300_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	// Storage: DoubleAuction AuctionIndex (r:1 w:1)
+	// Storage: DoubleAuction ListingCooldowns (r:1 w:0)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BuyOrders (r:1 w:0)
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction TierAuctions (r:1 w:1)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:0 w:1)
+	// Storage: DoubleAuction DutchAuctions (r:0 w:1)
+	fn new_dutch() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction BuyoutPrices (r:1 w:1)
+	// Storage: DoubleAuction Participants (r:1 w:0)
+	// Storage: DoubleAuction MarketPaused (r:1 w:0)
+	// Storage: DoubleAuction OrganizationOf (r:1 w:0)
+	// Storage: DoubleAuction Bids (r:1 w:1)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:0 w:1)
+	// Storage: DoubleAuction TierAuctions (r:1 w:1)
+	// Storage: DoubleAuction EraVolumes (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	/// The range of component `b` is `[0, 10]`.
+	fn buyout(b: u32, ) -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	// Storage: DoubleAuction TierConfig (r:0 w:1)
+	/// The range of component `t` is `[0, 1]`.
+	fn set_tier_boundaries(t: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(650_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction RecurringAuctions (r:1 w:1)
+	// Storage: DoubleAuction RecurringSchedule (r:0 w:1)
+	// Storage: DoubleAuction RecurringOf (r:1 w:1)
+	fn cancel_recurring() -> Weight {
+		Weight::from_parts(28_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: DoubleAuction Participants (r:1 w:1)
+	fn register_participant() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Participants (r:1 w:1)
+	fn set_participant_status() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Participants (r:1 w:1)
+	fn register_sponsor() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Participants (r:1 w:0)
+	// Storage: DoubleAuction MarketPaused (r:1 w:0)
+	// Storage: DoubleAuction Reputation (r:1 w:0)
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction HaltedTiers (r:1 w:0)
+	// Storage: DoubleAuction AuctionIndex (r:1 w:1)
+	// Storage: DoubleAuction Escrows (r:1 w:1)
+	// Storage: DoubleAuction DemandAuctions (r:0 w:1)
+	// Storage: DoubleAuction DemandExecutionQueue (r:0 w:1)
+	fn new_demand() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Storage: DoubleAuction Participants (r:1 w:0)
+	// Storage: DoubleAuction MarketPaused (r:1 w:0)
+	// Storage: DoubleAuction Reputation (r:1 w:0)
+	// Storage: DoubleAuction DemandAuctions (r:1 w:1)
+	fn offer() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction DemandAuctions (r:1 w:1)
+	// Storage: DoubleAuction DemandExecutionQueue (r:0 w:1)
+	// Storage: DoubleAuction Escrows (r:1 w:1)
+	fn cancel_demand() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: DoubleAuction MarketPaused (r:1 w:0)
+	// Storage: DoubleAuction Auctions (r:1 w:1)
+	// Storage: DoubleAuction TierConfig (r:1 w:0)
+	// Storage: DoubleAuction HaltedTiers (r:1 w:0)
+	// Storage: DoubleAuction BuyoutPrices (r:1 w:0)
+	// Storage: DoubleAuction AuctionsExecutionQueue (r:2 w:2)
+	// Storage: DoubleAuction PairedSellLegs (r:1 w:0)
+	// Storage: DoubleAuction StorageDeposits (r:1 w:1)
+	// Storage: DoubleAuction SellOrdersOf (r:1 w:1)
+	// Storage: DoubleAuction BuyOrders (r:1 w:0)
+	// Storage: DoubleAuction AuctionSummaries (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn update_auction() -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	// Storage: DoubleAuction ZoneTopology (r:0 w:2)
+	fn set_zone_link() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: DoubleAuction Deliveries (r:1 w:1)
+	// Storage: DoubleAuction Escrows (r:1 w:1)
+	// Storage: DoubleAuction Reputation (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn confirm_delivery() -> Weight {
+		Weight::from_parts(44_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	// Storage: DoubleAuction Deliveries (r:1 w:1)
+	fn dispute_delivery() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction Deliveries (r:1 w:1)
+	// Storage: DoubleAuction Escrows (r:1 w:1)
+	// Storage: DoubleAuction SellerBonds (r:1 w:1)
+	// Storage: DoubleAuction Reputation (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn resolve_delivery() -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: DoubleAuction Participants (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: DoubleAuction SellerBonds (r:1 w:1)
+	fn bond_capacity() -> Weight {
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: DoubleAuction MarketPaused (r:0 w:1)
+	fn pause_market() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: DoubleAuction MarketPaused (r:0 w:1)
+	fn resume_market() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn new() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn bid(b: u32, ) -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn cancel(b: u32, ) -> Weight {
+		Weight::from_parts(41_000_000, 0)
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(b.into())))
+	}
+	fn execute_auctions(q: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(65_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(q.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((14_u64).saturating_mul(q.into())))
+	}
+	fn market_buy(a: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(70_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((14_u64).saturating_mul(a.into())))
+	}
+	fn market_sell(o: u32, ) -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(Weight::from_parts(112_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((19_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((20_u64).saturating_mul(o.into())))
+	}
+	fn extend_auction() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn relist() -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn set_tier_default_period() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_tier_min_bidders() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn register_referrer() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn claim_referral_rewards() -> Weight {
+		Weight::from_parts(42_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn sponsor() -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn end_sponsorship() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn bid_signed(b: u32, ) -> Weight {
+		Weight::from_parts(112_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn set_tier_matching_algorithm() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn top_up_bid(b: u32, ) -> Weight {
+		Weight::from_parts(54_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn transfer_auction() -> Weight {
+		Weight::from_parts(46_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	fn set_tier_halted() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn force_execute(b: u32, ) -> Weight {
+		Weight::from_parts(36_000_000, 0)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	fn accept_match(b: u32, ) -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	fn create_organization() -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_organization_member() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_organization_limits() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn subscribe_tier(s: u32, ) -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(Weight::from_parts(240_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn unsubscribe_tier(s: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(Weight::from_parts(260_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_parameter() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn pair_orders(b: u32, ) -> Weight {
+		Weight::from_parts(64_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn place_buy_order(o: u32, ) -> Weight {
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(Weight::from_parts(420_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn cancel_buy_order(o: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(480_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn bid_quantity(b: u32, ) -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(Weight::from_parts(2_600_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn cancel_bid(b: u32, ) -> Weight {
+		Weight::from_parts(44_000_000, 0)
+			.saturating_add(Weight::from_parts(3_100_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn commit_bid(b: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(1_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn reveal_bid(b: u32, ) -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(Weight::from_parts(1_300_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	fn new_dutch() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn buyout(b: u32, ) -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	fn set_tier_boundaries(t: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(650_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn cancel_recurring() -> Weight {
+		Weight::from_parts(28_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn register_participant() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_participant_status() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn register_sponsor() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn new_demand() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn offer() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn cancel_demand() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn update_auction() -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn set_zone_link() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn confirm_delivery() -> Weight {
+		Weight::from_parts(44_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn dispute_delivery() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn resolve_delivery() -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn bond_capacity() -> Weight {
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn pause_market() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn resume_market() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}